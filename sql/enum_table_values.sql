@@ -0,0 +1,47 @@
+create or replace function graphql._enum_table_values(table_oid regclass, key_column text)
+    returns jsonb
+    language plpgsql
+    stable
+as $$
+declare
+    description_clause text = 'null';
+    result jsonb;
+begin
+    -- An optional "description" column describes each value
+    if exists(
+        select 1
+        from pg_catalog.pg_attribute pa
+        where
+            pa.attrelid = table_oid
+            and pa.attname = 'description'
+            and pa.attnum > 0
+            and not pa.attisdropped
+    ) then
+        description_clause = 'x.description::text';
+    end if;
+
+    execute format(
+        'select jsonb_agg(jsonb_build_object(%L, x.%I::text, %L, %s) order by x.%I) from %s x',
+        'value',
+        key_column,
+        'description',
+        description_clause,
+        key_column,
+        table_oid
+    ) into result;
+    return coalesce(result, jsonb_build_array());
+end;
+$$;
+
+-- Lookup tables with the enum directive are read along with the schema, which is cached until
+-- the schema version changes. Triggering this on changes to their rows refreshes the enum
+create or replace function graphql.refresh_enum_table()
+    returns trigger
+    security definer
+    language plpgsql
+as $$
+begin
+    perform pg_catalog.nextval('graphql.seq_schema_version');
+    return null;
+end;
+$$;
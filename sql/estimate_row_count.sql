@@ -0,0 +1,12 @@
+create or replace function graphql._estimate_row_count(query text, args text[])
+    returns bigint
+    language plpgsql
+as $$
+declare
+    plan jsonb;
+begin
+    -- The query is planned but not executed
+    execute 'explain (format json) ' || query into plan using args;
+    return (plan -> 0 -> 'Plan' ->> 'Plan Rows')::numeric::bigint;
+end;
+$$;
@@ -0,0 +1,28 @@
+-- Comments mentioning @graphql on objects in schemas on the search path
+select
+    coalesce(
+        jsonb_agg(
+            jsonb_build_object(
+                'entity_type', oi.type,
+                'entity', oi.identity,
+                'comment', pd.description
+            )
+            order by oi.type, oi.identity
+        ),
+        jsonb_build_array()
+    )
+from
+    pg_catalog.pg_description pd
+    cross join lateral pg_catalog.pg_identify_object(pd.classoid, pd.objoid, pd.objsubid) oi
+where
+    pd.description like '%@graphql%'
+    and (
+        oi.schema = any(current_schemas(false))
+        -- Schemas are not within a schema themselves
+        or (
+            pd.classoid = 'pg_catalog.pg_namespace'::regclass
+            and pd.objoid = any(
+                select x::regnamespace::oid from unnest(current_schemas(false)) x
+            )
+        )
+    )
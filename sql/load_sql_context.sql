@@ -51,7 +51,19 @@ select
                             'comment', pg_catalog.obj_description(pt.oid, 'pg_type'),
                             'directives', jsonb_build_object(
                                 'mappings', graphql.comment_directive(pg_catalog.obj_description(pt.oid, 'pg_type')) -> 'mappings',
-                                'name', graphql.comment_directive(pg_catalog.obj_description(pt.oid, 'pg_type')) ->> 'name'
+                                'name', graphql.comment_directive(pg_catalog.obj_description(pt.oid, 'pg_type')) ->> 'name',
+                                -- Entries of the wrong type are dropped so a malformed
+                                -- directive can not prevent the schema from loading
+                                'deprecated', case jsonb_typeof(graphql.comment_directive(pg_catalog.obj_description(pt.oid, 'pg_type')) -> 'deprecated')
+                                    when 'object' then (
+                                        select
+                                            coalesce(jsonb_object_agg(reason.key, reason.value), '{}')
+                                        from
+                                            jsonb_each(graphql.comment_directive(pg_catalog.obj_description(pt.oid, 'pg_type')) -> 'deprecated') reason
+                                        where
+                                            jsonb_typeof(reason.value) = 'string'
+                                    )
+                                end
                             ),
                             'values', jsonb_agg(
                                 jsonb_build_object(
@@ -179,7 +191,12 @@ select
                                 ),
                                 'directives', jsonb_build_object(
                                     'local_name', graphql.comment_directive(pg_catalog.obj_description(pf.oid, 'pg_constraint')) ->> 'local_name',
-                                    'foreign_name', graphql.comment_directive(pg_catalog.obj_description(pf.oid, 'pg_constraint')) ->> 'foreign_name'
+                                    'foreign_name', graphql.comment_directive(pg_catalog.obj_description(pf.oid, 'pg_constraint')) ->> 'foreign_name',
+                                    'omit', coalesce(
+                                        (graphql.comment_directive(pg_catalog.obj_description(pf.oid, 'pg_constraint')) -> 'omit') = to_jsonb(true),
+                                        false
+                                    ),
+                                    'deprecated', graphql.comment_directive(pg_catalog.obj_description(pf.oid, 'pg_constraint')) ->> 'deprecated'
                                 )
                             )
                         )
@@ -219,7 +236,12 @@ select
                                 'introspection_enabled', coalesce(
                                     (graphql.comment_directive(pg_catalog.obj_description(pn.oid, 'pg_namespace')) -> 'introspection') = to_jsonb(true),
                                     false
-                                )
+                                ),
+                                'node_id_format', case graphql.comment_directive(pg_catalog.obj_description(pn.oid, 'pg_namespace')) ->> 'node_id_format'
+                                    when 'type' then 'type'
+                                    when 'opaque' then 'opaque'
+                                    else 'table'
+                                end
                             )
                         )
                     )
@@ -260,6 +282,14 @@ select
                                                 false
                                             )
                                         ),
+                                        'total_count_estimate', jsonb_build_object(
+                                            'enabled', coalesce(
+                                                (
+                                                    d.directive -> 'totalCountEstimate' ->> 'enabled' = 'true'
+                                                ),
+                                                false
+                                            )
+                                        ),
                                         'aggregate', jsonb_build_object(
                                             'enabled', coalesce(
                                                 (
@@ -270,11 +300,84 @@ select
                                         ),
                                         'primary_key_columns', d.directive -> 'primary_key_columns',
                                         'foreign_keys', d.directive -> 'foreign_keys',
-                                        'max_rows', (d.directive ->> 'max_rows')::int
+                                        'max_rows', (d.directive ->> 'max_rows')::int,
+                                        'version', jsonb_build_object(
+                                            'enabled', coalesce(
+                                                (
+                                                    d.directive -> 'version' ->> 'enabled' = 'true'
+                                                ),
+                                                false
+                                            ),
+                                            'column', d.directive -> 'version' ->> 'column'
+                                        ),
+                                        'soft_delete', d.directive ->> 'soft_delete',
+                                        'node_id_alias', d.directive ->> 'node_id_alias',
+                                        -- Entries of the wrong type are dropped so a malformed
+                                        -- directive can not prevent the schema from loading
+                                        'mutations', case jsonb_typeof(d.directive -> 'mutations')
+                                            when 'array' then (
+                                                select
+                                                    coalesce(jsonb_agg(op), '[]')
+                                                from
+                                                    jsonb_array_elements(d.directive -> 'mutations') op
+                                                where
+                                                    jsonb_typeof(op) = 'string'
+                                            )
+                                            when 'object' then (
+                                                select
+                                                    coalesce(
+                                                        jsonb_object_agg(
+                                                            op.key,
+                                                            jsonb_build_object(
+                                                                'name', case jsonb_typeof(op.value -> 'name')
+                                                                    when 'string' then op.value -> 'name'
+                                                                end
+                                                            )
+                                                        ),
+                                                        '{}'
+                                                    )
+                                                from
+                                                    jsonb_each(d.directive -> 'mutations') op
+                                                where
+                                                    jsonb_typeof(op.value) = 'object'
+                                            )
+                                        end,
+                                        'omit', coalesce((d.directive -> 'omit') = to_jsonb(true), false),
+                                        'junction', case jsonb_typeof(d.directive -> 'junction')
+                                            when 'boolean' then d.directive -> 'junction'
+                                        end,
+                                        'interfaces', case jsonb_typeof(d.directive -> 'interfaces')
+                                            when 'array' then (
+                                                select
+                                                    coalesce(jsonb_agg(x), '[]')
+                                                from
+                                                    jsonb_array_elements(d.directive -> 'interfaces') x
+                                                where
+                                                    jsonb_typeof(x) = 'string'
+                                            )
+                                        end
                                     )
                                 from
                                     directives d
                             ),
+                            -- Rows of lookup tables marked with the enum directive
+                            'enum_values', (
+                                select
+                                    graphql._enum_table_values(pc.oid, pa_pk.attname)
+                                from
+                                    pg_catalog.pg_index pi
+                                    join pg_catalog.pg_attribute pa_pk
+                                        on pa_pk.attrelid = pi.indrelid
+                                        and pa_pk.attnum = pi.indkey[0]
+                                where
+                                    pi.indrelid = pc.oid
+                                    and pi.indisprimary
+                                    and pi.indnatts = 1
+                                    and (
+                                        graphql.comment_directive(pg_catalog.obj_description(pc.oid, 'pg_class')) -> 'enum'
+                                    ) = to_jsonb(true)
+                                    and pg_catalog.has_table_privilege(current_user, pc.oid, 'SELECT')
+                            ),
                             'indexes', coalesce(
                                 (
                                     select
@@ -293,7 +396,8 @@ select
                                                     ),
                                                     array[]::text[]
                                                 ),
-                                                'is_unique', pi.indisunique and pi.indpred is null,
+                                                -- Partial and expression indexes do not make their columns unique
+                                                'is_unique', pi.indisunique and pi.indpred is null and pi.indexprs is null,
                                                 'is_primary_key', pi.indisprimary
                                             )
                                         )
@@ -342,6 +446,23 @@ select
                                                 )
                                             ),
                                             'comment', pg_catalog.col_description(pc.oid, pa.attnum),
+                                            -- Lookup table marked with the enum directive that the column references
+                                            'enum_table_oid', (
+                                                select
+                                                    pf.confrelid::bigint
+                                                from
+                                                    pg_catalog.pg_constraint pf
+                                                where
+                                                    pf.contype = 'f'
+                                                    and pf.conrelid = pc.oid
+                                                    and pf.conkey = array[pa.attnum]
+                                                    and (
+                                                        graphql.comment_directive(
+                                                            pg_catalog.obj_description(pf.confrelid, 'pg_class')
+                                                        ) -> 'enum'
+                                                    ) = to_jsonb(true)
+                                                limit 1
+                                            ),
 
                                             'directives', (
                                                 with directives(directive) as (
@@ -350,7 +471,22 @@ select
                                                 select
                                                     jsonb_build_object(
                                                         'name', d.directive ->> 'name',
-                                                        'description', d.directive -> 'description'
+                                                        'description', d.directive -> 'description',
+                                                        -- Entries of the wrong type are dropped so a malformed
+                                                        -- directive can not prevent the schema from loading
+                                                        'omit', case jsonb_typeof(d.directive -> 'omit')
+                                                            when 'boolean' then d.directive -> 'omit'
+                                                            when 'array' then (
+                                                                select
+                                                                    coalesce(jsonb_agg(op), '[]')
+                                                                from
+                                                                    jsonb_array_elements(d.directive -> 'omit') op
+                                                                where
+                                                                    jsonb_typeof(op) = 'string'
+                                                            )
+                                                        end,
+                                                        'deprecated', d.directive ->> 'deprecated',
+                                                        'search_config', d.directive ->> 'search_config'
                                                     )
                                                 from
                                                     directives d
@@ -429,6 +565,26 @@ select
                             -- and returning a single record isn't.
                             'is_set_of', pp.proretset::bool and pp.prorows <> 1,
                             'n_rows', pp.prorows::int,
+                            -- Columns of functions declared as "returns table(...)"
+                            'return_columns', (
+                                select
+                                    jsonb_agg(
+                                        jsonb_build_object(
+                                            'name', ra.name,
+                                            'type_oid', ra.type_oid::bigint,
+                                            'type_name', ra.type_oid::regtype::text
+                                        )
+                                        order by ra.ix
+                                    )
+                                from
+                                    unnest(
+                                        pp.proallargtypes,
+                                        pp.proargmodes,
+                                        pp.proargnames
+                                    ) with ordinality ra(type_oid, mode, name, ix)
+                                where
+                                    ra.mode = 't'
+                            ),
                             'comment', pg_catalog.obj_description(pp.oid, 'pg_proc'),
                             'directives', (
                                 with directives(directive) as (
@@ -437,7 +593,25 @@ select
                                 select
                                     jsonb_build_object(
                                         'name', d.directive ->> 'name',
-                                        'description', d.directive ->> 'description'
+                                        'description', d.directive ->> 'description',
+                                        'omit', coalesce((d.directive -> 'omit') = to_jsonb(true), false),
+                                        'deprecated', d.directive ->> 'deprecated',
+                                        'union', case
+                                            when jsonb_typeof(d.directive -> 'union' -> 'name') = 'string'
+                                                and jsonb_typeof(d.directive -> 'union' -> 'types') = 'array'
+                                                then jsonb_build_object(
+                                                    'name', d.directive -> 'union' -> 'name',
+                                                    'types', (
+                                                        select
+                                                            coalesce(jsonb_agg(x), '[]')
+                                                        from
+                                                            jsonb_array_elements(d.directive -> 'union' -> 'types') x
+                                                        where
+                                                            jsonb_typeof(x) = 'string'
+                                                    )
+                                                )
+                                        end,
+                                        'interface', d.directive ->> 'interface'
                                     )
                                 from
                                     directives d
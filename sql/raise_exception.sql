@@ -6,3 +6,14 @@ begin
     raise exception using errcode='22000', message=message;
 end;
 $$;
+
+create or replace function graphql.exception_conflict(message text)
+    returns text
+    language plpgsql
+as $$
+begin
+    -- Class GQ is not used by Postgres, so errors raised by the database are never
+    -- mistaken for conflicts
+    raise exception using errcode='GQ409', message=message;
+end;
+$$;
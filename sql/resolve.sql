@@ -10,6 +10,7 @@ as $$
 declare
     res jsonb;
     message_text text;
+    error_code text;
 begin
   begin
     select graphql._internal_resolve("query" := "query",
@@ -19,10 +20,20 @@ begin
     return res;
   exception
     when others then
-    get stacked diagnostics message_text = message_text;
+    get stacked diagnostics message_text = message_text,
+                            error_code = returned_sqlstate;
     return
     jsonb_build_object('data', null,
-                       'errors', jsonb_build_array(jsonb_build_object('message', message_text)));
+                       'errors', jsonb_build_array(
+                            case
+                                -- raised by graphql.exception_conflict: expectedVersion conflicts
+                                when error_code = 'GQ409' then jsonb_build_object(
+                                    'message', message_text,
+                                    'extensions', jsonb_build_object('code', 'CONFLICT')
+                                )
+                                else jsonb_build_object('message', message_text)
+                            end
+                       ));
   end;
 end;
 $$;
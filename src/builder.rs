@@ -5,6 +5,7 @@ use crate::error::{GraphQLError, GraphQLResult};
 use crate::graphql::*;
 use crate::gson;
 use crate::parser_util::*;
+use crate::signing;
 use crate::sql_types::*;
 use graphql_parser::query::*;
 use serde::Serialize;
@@ -17,6 +18,12 @@ use std::sync::Arc;
 #[derive(Clone, Debug)]
 pub struct AggregateBuilder {
     pub alias: String,
+
+    // args
+    // Applied to the aggregated records on top of the connection's filter
+    pub filter: FilterBuilder,
+
+    //fields
     pub selections: Vec<AggregateSelection>,
 }
 
@@ -41,10 +48,86 @@ pub enum AggregateSelection {
         alias: String,
         column_builders: Vec<ColumnBuilder>,
     },
+    CountDistinct {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+    },
+    Stddev {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+    },
+    Variance {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+    },
+    BoolAnd {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+    },
+    BoolOr {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+    },
+    ArrayAgg {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+        order_by: OrderByBuilder,
+    },
+    PercentileCont {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+        fraction: f64,
+    },
     Typename {
         alias: String,
         typename: String,
     },
+    GroupBy(GroupByBuilder),
+}
+
+#[derive(Clone, Debug)]
+pub struct GroupByBuilder {
+    pub alias: String,
+
+    // args
+    pub keys: Vec<GroupByKeyBuilder>,
+    pub first: Option<u64>,
+    pub after: Option<u64>,
+    pub order_by: Vec<GroupOrderByElem>,
+
+    //fields
+    pub selections: Vec<GroupSelection>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GroupByKeyBuilder {
+    pub column: Arc<Column>,
+    // date_trunc precision
+    pub truncate: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub enum GroupOrderByKey {
+    Count,
+    Column(Arc<Column>),
+}
+
+#[derive(Clone, Debug)]
+pub struct GroupOrderByElem {
+    pub key: GroupOrderByKey,
+    pub direction: OrderDirection,
+}
+
+#[derive(Clone, Debug)]
+pub enum GroupSelection {
+    Cursor {
+        alias: String,
+    },
+    Key {
+        alias: String,
+        column_builders: Vec<ColumnBuilder>,
+    },
+    Aggregate(AggregateSelection),
 }
 
 #[derive(Clone, Debug)]
@@ -140,90 +223,194 @@ where
     }
 }
 
-fn parse_node_id(encoded: gson::Value) -> GraphQLResult<NodeIdInstance> {
+/// Reads the optional "expectedVersion" argument of update and delete mutations
+fn read_argument_expected_version<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<Option<serde_json::Value>>
+where
+    T: Text<'a> + Eq + AsRef<str>,
+{
+    if field.get_arg(args::EXPECTED_VERSION).is_none() {
+        return Ok(None);
+    }
+    let expected_version: gson::Value = read_argument(
+        args::EXPECTED_VERSION,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+    match expected_version {
+        gson::Value::Absent | gson::Value::Null => Ok(None),
+        gson::Value::String(_) => Ok(Some(gson::gson_to_json(&expected_version)?)),
+        _ => Err(GraphQLError::internal(
+            "Internal Error: failed to parse validated expectedVersion",
+        )),
+    }
+}
+
+/// Arguments accepted by update and delete mutations, including `expectedVersion`
+/// when the table has versioning enabled
+fn allowed_mutation_arguments<'b>(field: &__Field, arg_names: &[&'b str]) -> Vec<&'b str> {
+    let mut allowed_args = arg_names.to_vec();
+    if field.get_arg(args::EXPECTED_VERSION).is_some() {
+        allowed_args.push(args::EXPECTED_VERSION);
+    }
+    allowed_args
+}
+
+/// Reads the optional "includeDeleted" argument of queries on soft deleted tables
+fn read_argument_include_deleted<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<bool>
+where
+    T: Text<'a> + Eq + AsRef<str>,
+{
+    if field.get_arg(args::INCLUDE_DELETED).is_none() {
+        return Ok(false);
+    }
+    let include_deleted: gson::Value = read_argument(
+        args::INCLUDE_DELETED,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+    match include_deleted {
+        gson::Value::Absent | gson::Value::Null => Ok(false),
+        gson::Value::Boolean(x) => Ok(x),
+        _ => Err(GraphQLError::internal(
+            "Internal Error: failed to parse validated includeDeleted",
+        )),
+    }
+}
+
+/// Reads the optional "includeDeprecated" argument of introspection fields
+fn read_argument_include_deprecated<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<bool>
+where
+    T: Text<'a> + Eq + AsRef<str>,
+{
+    let include_deprecated: gson::Value = read_argument(
+        "includeDeprecated",
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+    match include_deprecated {
+        gson::Value::Absent | gson::Value::Null => Ok(false),
+        gson::Value::Boolean(x) => Ok(x),
+        _ => Err(GraphQLError::internal(
+            "Internal Error: failed to parse validated includeDeprecated",
+        )),
+    }
+}
+
+fn parse_node_id(encoded: gson::Value, schema: &__Schema) -> GraphQLResult<NodeIdInstance> {
     extern crate base64;
     use std::str;
 
-    let node_id_base64_encoded_string: String = match encoded {
+    let invalid = |reason: &str| {
+        GraphQLError::validation(format!("Invalid value passed to nodeId argument: {reason}"))
+    };
+
+    let node_id_token: String = match encoded {
         gson::Value::String(s) => s,
-        _ => {
-            return Err(GraphQLError::argument(
-                "Invalid value passed to nodeId argument, Error 1",
-            ));
-        }
+        _ => return Err(invalid("expected a string")),
     };
 
+    let node_id_base64_encoded_string =
+        signing::verify(&node_id_token, signing::TokenPurpose::NodeId).map_err(invalid)?;
+
     let node_id_json_string_utf8: Vec<u8> =
-        base64::decode(node_id_base64_encoded_string).map_err(|_| {
-            GraphQLError::validation("Invalid value passed to nodeId argument. Error 2")
-        })?;
+        base64::decode(node_id_base64_encoded_string).map_err(|_| invalid("not valid base64"))?;
 
-    let node_id_json_string: &str = str::from_utf8(&node_id_json_string_utf8).map_err(|_| {
-        GraphQLError::validation("Invalid value passed to nodeId argument. Error 3")
-    })?;
+    let node_id_json_string: &str =
+        str::from_utf8(&node_id_json_string_utf8).map_err(|_| invalid("not valid utf-8"))?;
 
     let node_id_json: serde_json::Value =
-        serde_json::from_str(node_id_json_string).map_err(|_| {
-            GraphQLError::validation("Invalid value passed to nodeId argument. Error 4")
-        })?;
+        serde_json::from_str(node_id_json_string).map_err(|_| invalid("not valid JSON"))?;
 
     match node_id_json {
         serde_json::Value::Array(x_arr) => {
-            if x_arr.len() < 3 {
-                return Err(GraphQLError::argument(
-                    "Invalid value passed to nodeId argument. Error 5",
-                ));
+            if let Some(node_id) = match_node_id_collection(&x_arr, schema) {
+                return Ok(node_id);
             }
 
-            let mut x_arr_iter = x_arr.into_iter();
-            let schema_name = match x_arr_iter
-                .next()
-                .expect("failed to get schema name from nodeId argument")
-            {
-                serde_json::Value::String(s) => s,
-                _ => {
-                    return Err(GraphQLError::argument(
-                        "Invalid value passed to nodeId argument. Error 6",
-                    ));
-                }
-            };
-
-            let table_name = match x_arr_iter
-                .next()
-                .expect("failed to get table name from nodeId argument")
-            {
-                serde_json::Value::String(s) => s,
-                _ => {
-                    return Err(GraphQLError::argument(
-                        "Invalid value passed to nodeId argument. Error 7",
-                    ));
-                }
-            };
-            let values: Vec<serde_json::Value> = x_arr_iter.collect();
-
-            // Popuate a NodeIdInstance
-            Ok(NodeIdInstance {
-                schema_name,
-                table_name,
-                values,
-            })
+            // Ids of unknown collections in the [schema, table, pkey_val1, ...] layout are
+            // passed on so they can be reported against the collection being queried
+            match x_arr.as_slice() {
+                [
+                    serde_json::Value::String(schema_name),
+                    serde_json::Value::String(table_name),
+                    values @ ..,
+                ] if !values.is_empty() => Ok(NodeIdInstance {
+                    schema_name: schema_name.clone(),
+                    table_name: table_name.clone(),
+                    values: values.to_vec(),
+                }),
+                _ => Err(invalid("does not reference a known collection")),
+            }
         }
-        _ => Err(GraphQLError::argument(
-            "Invalid value passed to nodeId argument. Error 10",
-        )),
+        _ => Err(invalid("expected a JSON array")),
     }
 }
 
+/// Finds the collection whose nodeId prefix leads `elements`. Ids in the
+/// [schema, table, pkey_val1, ...] layout are accepted whatever the configured format
+/// so ids issued before a format change keep working
+fn match_node_id_collection(
+    elements: &[serde_json::Value],
+    schema: &__Schema,
+) -> Option<NodeIdInstance> {
+    let prefixes = node_id_prefixes(schema);
+    let configured = prefixes
+        .iter()
+        .filter_map(|(oid, prefix)| Some((schema.context.tables.get(oid)?, prefix.clone())));
+    let legacy = schema
+        .context
+        .tables
+        .values()
+        .filter(|t| t.primary_key().is_some())
+        .map(|t| (t, vec![t.schema.clone(), t.name.clone()]));
+
+    configured.chain(legacy).find_map(|(table, prefix)| {
+        let matches = elements.len() > prefix.len()
+            && elements
+                .iter()
+                .zip(&prefix)
+                .all(|(elem, expected)| elem.as_str() == Some(expected.as_str()));
+
+        matches.then(|| NodeIdInstance {
+            schema_name: table.schema.clone(),
+            table_name: table.name.clone(),
+            values: elements[prefix.len()..].to_vec(),
+        })
+    })
+}
+
 fn read_argument_node_id<'a, T>(
     field: &__Field,
     query_field: &graphql_parser::query::Field<'a, T>,
     variables: &serde_json::Value,
     variable_definitions: &Vec<VariableDefinition<'a, T>>,
+    schema: &__Schema,
 ) -> GraphQLResult<NodeIdInstance>
 where
     T: Text<'a> + Eq + AsRef<str>,
 {
-    // nodeId is a base64 encoded string of [schema, table, pkey_val1, pkey_val2, ...]
+    // nodeId is a base64 encoded string of [<collection prefix>, pkey_val1, pkey_val2, ...]
     let node_id_base64_encoded_json_string: gson::Value = read_argument(
         args::NODE_ID,
         field,
@@ -232,7 +419,7 @@ where
         variable_definitions,
     )?;
 
-    parse_node_id(node_id_base64_encoded_json_string)
+    parse_node_id(node_id_base64_encoded_json_string, schema)
 }
 
 fn read_argument_objects<'a, T>(
@@ -410,6 +597,7 @@ pub struct UpdateBuilder {
     pub filter: FilterBuilder,
     pub set: SetBuilder,
     pub at_most: i64,
+    pub expected_version: Option<serde_json::Value>,
 
     // metadata
     pub table: Arc<Table>,
@@ -519,7 +707,10 @@ where
     match &type_ {
         __Type::UpdateResponse(xtype) => {
             // Raise for disallowed arguments
-            restrict_allowed_arguments(&[args::SET, args::FILTER, args::AT_MOST], query_field)?;
+            restrict_allowed_arguments(
+                &allowed_mutation_arguments(field, &[args::SET, args::FILTER, args::AT_MOST]),
+                query_field,
+            )?;
 
             let set: SetBuilder =
                 read_argument_set(field, query_field, variables, variable_definitions)?;
@@ -527,6 +718,12 @@ where
                 read_argument_filter(field, query_field, variables, variable_definitions)?;
             let at_most: i64 =
                 read_argument_at_most(field, query_field, variables, variable_definitions)?;
+            let expected_version = read_argument_expected_version(
+                field,
+                query_field,
+                variables,
+                variable_definitions,
+            )?;
 
             let mut builder_fields: Vec<UpdateSelection> = vec![];
 
@@ -573,6 +770,7 @@ where
                 filter,
                 set,
                 at_most,
+                expected_version,
                 table: Arc::clone(&xtype.table),
                 selections: builder_fields,
             })
@@ -589,6 +787,7 @@ pub struct DeleteBuilder {
     // args
     pub filter: FilterBuilder,
     pub at_most: i64,
+    pub expected_version: Option<serde_json::Value>,
 
     // metadata
     pub table: Arc<Table>,
@@ -625,12 +824,21 @@ where
     match &type_ {
         __Type::DeleteResponse(xtype) => {
             // Raise for disallowed arguments
-            restrict_allowed_arguments(&[args::FILTER, args::AT_MOST], query_field)?;
+            restrict_allowed_arguments(
+                &allowed_mutation_arguments(field, &[args::FILTER, args::AT_MOST]),
+                query_field,
+            )?;
 
             let filter: FilterBuilder =
                 read_argument_filter(field, query_field, variables, variable_definitions)?;
             let at_most: i64 =
                 read_argument_at_most(field, query_field, variables, variable_definitions)?;
+            let expected_version = read_argument_expected_version(
+                field,
+                query_field,
+                variables,
+                variable_definitions,
+            )?;
 
             let mut builder_fields: Vec<DeleteSelection> = vec![];
 
@@ -676,6 +884,7 @@ where
             Ok(DeleteBuilder {
                 filter,
                 at_most,
+                expected_version,
                 table: Arc::clone(&xtype.table),
                 selections: builder_fields,
             })
@@ -702,6 +911,13 @@ pub enum FuncCallReturnTypeBuilder {
     List,
     Node(NodeBuilder),
     Connection(ConnectionBuilder),
+    Polymorphic(PolymorphicBuilder),
+}
+
+/// Rows of a union or interface returning function, resolved to nodes of the member tables
+pub struct PolymorphicBuilder {
+    pub members: Vec<(Arc<Table>, NodeBuilder)>,
+    pub include_deleted: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -742,6 +958,41 @@ where
             )?;
 
             let return_type_builder = match func_call_resp_type.return_type.deref() {
+                _ if func_call_resp_type.function.is_polymorphic() => {
+                    let schema = &func_call_resp_type.schema;
+                    let members = schema
+                        .graphql_function_member_tables(&func_call_resp_type.function)
+                        .into_iter()
+                        .map(|table| {
+                            let xtype = NodeType {
+                                table: Arc::clone(&table),
+                                fkey: None,
+                                reverse_reference: None,
+                                schema: Arc::clone(schema),
+                            };
+                            let node_builder = to_node_builder_for_type(
+                                field,
+                                query_field,
+                                &xtype,
+                                fragment_definitions,
+                                variables,
+                                &allowed_args,
+                                variable_definitions,
+                            )?;
+                            Ok((table, node_builder))
+                        })
+                        .collect::<GraphQLResult<Vec<_>>>()?;
+                    let include_deleted = read_argument_include_deleted(
+                        field,
+                        query_field,
+                        variables,
+                        variable_definitions,
+                    )?;
+                    FuncCallReturnTypeBuilder::Polymorphic(PolymorphicBuilder {
+                        members,
+                        include_deleted,
+                    })
+                }
                 __Type::Scalar(_) => FuncCallReturnTypeBuilder::Scalar,
                 __Type::List(_) => FuncCallReturnTypeBuilder::List,
                 __Type::Node(_) => {
@@ -829,6 +1080,19 @@ where
 pub struct ConnectionBuilderSource {
     pub table: Arc<Table>,
     pub fkey: Option<ForeignKeyReversible>,
+    pub hierarchy: Option<HierarchyBuilder>,
+    pub junction: Option<JunctionReference>,
+}
+
+/// Source of `ancestors` and `descendants` connections, walking a self referencing
+/// foreign key recursively from the parent record
+#[derive(Clone, Debug)]
+pub struct HierarchyBuilder {
+    pub fkey: Arc<ForeignKey>,
+    pub direction: HierarchyDirection,
+    pub max_depth: Option<u64>,
+    // Soft deleted records are not walked through
+    pub include_deleted: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -843,6 +1107,7 @@ pub struct ConnectionBuilder {
     pub offset: Option<u64>,
     pub filter: FilterBuilder,
     pub order_by: OrderByBuilder,
+    pub distinct_on: Vec<Arc<Column>>,
 
     // metadata
     pub source: ConnectionBuilderSource,
@@ -866,8 +1131,30 @@ pub enum FilterBuilderElem {
         op: FilterOp,
         value: serde_json::Value, //String, // string repr castable by postgres
     },
+    /// Compares the number of elements in an array column
+    Cardinality {
+        column: Arc<Column>,
+        op: FilterOp,
+        value: serde_json::Value,
+    },
     NodeId(NodeIdInstance),
     Compound(Box<CompoundFilterBuilder>),
+    Relation {
+        fkey: ForeignKeyReversible,
+        // The related table
+        table: Arc<Table>,
+        quantifier: RelationFilterQuantifier,
+        filter: FilterBuilder,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RelationFilterQuantifier {
+    // A to-one relationship, the related record must exist and match
+    One,
+    Some,
+    Every,
+    None,
 }
 
 #[derive(Clone, Debug)]
@@ -930,7 +1217,7 @@ impl OrderDirection {
 impl OrderByBuilderElem {
     fn reverse(&self) -> Self {
         Self {
-            column: Arc::clone(&self.column),
+            key: self.key.clone(),
             direction: self.direction.reverse(),
         }
     }
@@ -960,37 +1247,55 @@ impl FromStr for Cursor {
         extern crate base64;
         use std::str;
 
-        match base64::decode(input) {
-            Ok(vec_u8) => match str::from_utf8(&vec_u8) {
-                Ok(v) => {
-                    let mut elems: Vec<CursorElement> = vec![];
-                    match serde_json::from_str(v) {
-                        Ok(json) => match json {
-                            serde_json::Value::Array(x_arr) => {
-                                for x in &x_arr {
-                                    let element = CursorElement { value: x.clone() };
-                                    elems.push(element);
-                                }
-                                Ok(Cursor { elems })
-                            }
-                            _ => Err("Failed to decode cursor, error 4".to_string()),
-                        },
-                        Err(_) => Err("Failed to decode cursor, error 3".to_string()),
-                    }
-                }
-                Err(_) => Err("Failed to decode cursor, error 2".to_string()),
-            },
-            Err(_) => Err("Failed to decode cursor, error 1".to_string()),
+        let invalid = |reason: &str| format!("Invalid cursor: {reason}");
+
+        let payload = signing::verify(input, signing::TokenPurpose::Cursor).map_err(invalid)?;
+        let vec_u8 = base64::decode(payload).map_err(|_| invalid("not valid base64"))?;
+        let v = str::from_utf8(&vec_u8).map_err(|_| invalid("not valid utf-8"))?;
+        let json: serde_json::Value =
+            serde_json::from_str(v).map_err(|_| invalid("not valid JSON"))?;
+
+        match json {
+            serde_json::Value::Array(x_arr) => Ok(Cursor {
+                elems: x_arr
+                    .into_iter()
+                    .map(|value| CursorElement { value })
+                    .collect(),
+            }),
+            _ => Err(invalid("expected a JSON array")),
         }
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct OrderByBuilderElem {
-    pub column: Arc<Column>,
+    pub key: OrderByKey,
     pub direction: OrderDirection,
 }
 
+#[derive(Clone, Debug)]
+pub enum OrderByKey {
+    Column(Arc<Column>),
+    // Relevance of a full text search on a searchable column
+    SearchRank {
+        column: Arc<Column>,
+        query: String,
+    },
+    // A sort key of a to-one related record
+    Relation {
+        fkey: ForeignKeyReversible,
+        // The related table
+        table: Arc<Table>,
+        key: Box<OrderByKey>,
+    },
+    // The number of to-many related records
+    Count {
+        fkey: ForeignKeyReversible,
+        // The related table
+        table: Arc<Table>,
+    },
+}
+
 #[derive(Clone, Debug)]
 pub struct OrderByBuilder {
     pub elems: Vec<OrderByBuilderElem>,
@@ -1014,6 +1319,7 @@ pub enum PageInfoSelection {
 #[derive(Clone, Debug)]
 pub enum ConnectionSelection {
     TotalCount { alias: String },
+    TotalCountEstimate { alias: String },
     Edge(EdgeBuilder),
     PageInfo(PageInfoBuilder),
     Typename { alias: String, typename: String },
@@ -1031,6 +1337,7 @@ pub struct EdgeBuilder {
 pub enum EdgeSelection {
     Cursor { alias: String },
     Node(NodeBuilder),
+    Depth { alias: String },
     Typename { alias: String, typename: String },
 }
 
@@ -1038,6 +1345,7 @@ pub enum EdgeSelection {
 pub struct NodeBuilder {
     // args
     pub node_id: Option<NodeIdInstance>,
+    pub include_deleted: bool,
 
     pub alias: String,
 
@@ -1049,9 +1357,26 @@ pub struct NodeBuilder {
     pub selections: Vec<NodeSelection>,
 }
 
+#[derive(Clone, Debug)]
+pub struct NodesBuilder {
+    // Number of ids requested. Results are returned in the same order
+    pub ids_count: usize,
+
+    // One lookup per collection referenced by the requested ids
+    pub lookups: Vec<NodesLookup>,
+}
+
+#[derive(Clone, Debug)]
+pub struct NodesLookup {
+    pub node: NodeBuilder,
+    // Position of each id in the request alongside the id
+    pub node_ids: Vec<(usize, NodeIdInstance)>,
+}
+
 #[derive(Clone, Debug)]
 pub struct NodeByPkBuilder {
     pub pk_values: HashMap<String, serde_json::Value>,
+    pub include_deleted: bool,
     pub table: Arc<Table>,
     pub selections: Vec<NodeSelection>,
 }
@@ -1061,8 +1386,10 @@ pub enum NodeSelection {
     Connection(ConnectionBuilder),
     Node(NodeBuilder),
     Column(ColumnBuilder),
+    JsonPath(JsonPathBuilder),
     Function(FunctionBuilder),
     NodeId(NodeIdBuilder),
+    Version(VersionBuilder),
     Typename { alias: String, typename: String },
 }
 
@@ -1077,17 +1404,31 @@ pub struct NodeIdInstance {
 #[derive(Clone, Debug)]
 pub struct NodeIdBuilder {
     pub alias: String,
-    pub schema_name: String,
-    pub table_name: String,
+    // Leading elements identifying the collection
+    pub prefix: Vec<String>,
     pub columns: Vec<Arc<Column>>,
 }
 
+#[derive(Clone, Debug)]
+pub struct VersionBuilder {
+    pub alias: String,
+    pub source: VersionSource,
+}
+
 #[derive(Clone, Debug)]
 pub struct ColumnBuilder {
     pub alias: String,
     pub column: Arc<Column>,
 }
 
+#[derive(Clone, Debug)]
+pub struct JsonPathBuilder {
+    pub alias: String,
+    pub column: Arc<Column>,
+    // Keys and array indexes, as accepted by the #> operator
+    pub path: Vec<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct FunctionBuilder {
     pub alias: String,
@@ -1192,7 +1533,9 @@ fn create_filters(
                 // else its type will be __Type::FilterType(_). Refer to the the method
                 // crate::graphql::FilterEntityType::input_fields() method for details.
                 let is_a_not_filter_type = matches!(filter_iv.type_(), __Type::FilterEntity(_));
-                if k == NOT_FILTER_NAME && is_a_not_filter_type {
+                if let Some(NodeSQLType::Relation(fkey)) = &filter_iv.sql_type {
+                    filters.extend(create_relation_filters(fkey, filter_iv, op_to_v)?);
+                } else if k == NOT_FILTER_NAME && is_a_not_filter_type {
                     if let gson::Value::Object(_) = op_to_v {
                         let inner_filters = create_filters(op_to_v, filter_field_map)?;
                         // If there are no inner filters we avoid creating an argumentless `not` expression. i.e. avoid `not()`
@@ -1220,6 +1563,11 @@ fn create_filters(
                             continue;
                         }
 
+                        if matches!(filter_op, FilterOp::Length) {
+                            filters.extend(create_cardinality_filters(filter_iv, filter_val)?);
+                            continue;
+                        }
+
                         let filter_builder =
                             create_filter_builder_elem(filter_iv, filter_op, filter_val)?;
                         filters.push(filter_builder);
@@ -1270,37 +1618,208 @@ fn create_filters(
     Ok(filters)
 }
 
-fn create_filter_builder_elem(
+fn create_relation_filters(
+    fkey: &ForeignKeyReversible,
     filter_iv: &__InputValue,
-    filter_op: FilterOp,
-    filter_val: &gson::Value,
-) -> GraphQLResult<FilterBuilderElem> {
-    Ok(match &filter_iv.sql_type {
-        Some(NodeSQLType::Column(col)) => FilterBuilderElem::Column {
-            column: Arc::clone(col),
-            op: filter_op,
-            value: gson::gson_to_json(filter_val)?,
-        },
-        Some(NodeSQLType::NodeId(_)) => {
-            FilterBuilderElem::NodeId(parse_node_id(filter_val.clone())?)
+    validated: &gson::Value,
+) -> GraphQLResult<Vec<FilterBuilderElem>> {
+    let mut filters = vec![];
+
+    // (quantifier, nested filter type, nested filter value)
+    let nested: Vec<(RelationFilterQuantifier, __Type, &gson::Value)> = match filter_iv.type_() {
+        filter_type @ __Type::FilterEntity(_) => {
+            vec![(RelationFilterQuantifier::One, filter_type, validated)]
+        }
+        relation_filter_type @ __Type::RelationFilter(_) => {
+            let kv_map = match validated {
+                gson::Value::Object(kv) => kv,
+                _ => return Err(GraphQLError::validation("Invalid relationship filter")),
+            };
+            let relation_filter_field_map = input_field_map(&relation_filter_type);
+
+            let mut nested = vec![];
+            for (k, v) in kv_map {
+                let quantifier = match k.as_str() {
+                    SOME_FILTER_NAME => RelationFilterQuantifier::Some,
+                    EVERY_FILTER_NAME => RelationFilterQuantifier::Every,
+                    NONE_FILTER_NAME => RelationFilterQuantifier::None,
+                    _ => return Err(GraphQLError::validation("Invalid relationship filter")),
+                };
+                let nested_iv = relation_filter_field_map
+                    .get(k)
+                    .ok_or_else(|| GraphQLError::validation("Invalid relationship filter"))?;
+                nested.push((quantifier, nested_iv.type_(), v));
+            }
+            nested
         }
         _ => {
             return Err(GraphQLError::validation(
-                "Filter type error, attempted filter on non-column",
+                "Filter type error, attempted relationship filter on non-relationship",
             ));
         }
-    })
+    };
+
+    for (quantifier, filter_type, value) in nested {
+        if matches!(value, gson::Value::Absent | gson::Value::Null) {
+            continue;
+        }
+        let table = match &filter_type {
+            __Type::FilterEntity(filter_entity) => Arc::clone(&filter_entity.table),
+            _ => return Err(GraphQLError::validation("Invalid relationship filter")),
+        };
+
+        let filter = FilterBuilder {
+            elems: create_filters(value, &input_field_map(&filter_type))?,
+        };
+
+        filters.push(FilterBuilderElem::Relation {
+            fkey: fkey.clone(),
+            table,
+            quantifier,
+            filter,
+        });
+    }
+    Ok(filters)
 }
 
-/// Reads the "orderBy" argument. Auto-appends the primary key
-fn read_argument_order_by<'a, T>(
-    field: &__Field,
-    query_field: &graphql_parser::query::Field<'a, T>,
-    variables: &serde_json::Value,
-    variable_definitions: &Vec<VariableDefinition<'a, T>>,
-) -> GraphQLResult<OrderByBuilder>
-where
-    T: Text<'a> + Eq + AsRef<str>,
+fn validate_filter_value(filter_op: &FilterOp, filter_val: &gson::Value) -> GraphQLResult<()> {
+    if matches!(filter_op, FilterOp::Between)
+        && let gson::Value::Array(bounds) = filter_val
+        && bounds.len() != 2
+    {
+        return Err(GraphQLError::validation(
+            "\"between\" requires exactly two values",
+        ));
+    }
+    Ok(())
+}
+
+/// Reads a `length: IntFilter` on an array column into one filter per operator
+fn create_cardinality_filters(
+    filter_iv: &__InputValue,
+    filter_val: &gson::Value,
+) -> GraphQLResult<Vec<FilterBuilderElem>> {
+    let column = match &filter_iv.sql_type {
+        Some(NodeSQLType::Column(col)) => col,
+        _ => {
+            return Err(GraphQLError::validation(
+                "Filter type error, attempted filter on non-column",
+            ));
+        }
+    };
+
+    let op_to_value_map = match filter_val {
+        gson::Value::Absent | gson::Value::Null => return Ok(vec![]),
+        gson::Value::Object(op_to_value_map) => op_to_value_map,
+        _ => return Err(GraphQLError::validation("Filter re-validation error")),
+    };
+
+    let mut filters = vec![];
+    for (op_str, value) in op_to_value_map {
+        if value == &gson::Value::Absent {
+            continue;
+        }
+        let op = FilterOp::from_str(op_str)?;
+        validate_filter_value(&op, value)?;
+        filters.push(FilterBuilderElem::Cardinality {
+            column: Arc::clone(column),
+            op,
+            value: gson::gson_to_json(value)?,
+        });
+    }
+    Ok(filters)
+}
+
+fn create_filter_builder_elem(
+    filter_iv: &__InputValue,
+    filter_op: FilterOp,
+    filter_val: &gson::Value,
+) -> GraphQLResult<FilterBuilderElem> {
+    validate_filter_value(&filter_op, filter_val)?;
+
+    Ok(match &filter_iv.sql_type {
+        Some(NodeSQLType::Column(col)) => FilterBuilderElem::Column {
+            column: Arc::clone(col),
+            op: filter_op,
+            value: gson::gson_to_json(filter_val)?,
+        },
+        Some(NodeSQLType::NodeId(_)) => {
+            let schema = match filter_iv.type_() {
+                __Type::FilterType(filter_type) => filter_type.schema,
+                _ => return Err(GraphQLError::schema("Could not locate IDFilter type")),
+            };
+            FilterBuilderElem::NodeId(parse_node_id(filter_val.clone(), &schema)?)
+        }
+        _ => {
+            return Err(GraphQLError::validation(
+                "Filter type error, attempted filter on non-column",
+            ));
+        }
+    })
+}
+
+/// Reads the optional "distinctOn" argument into a deduplicated list of columns
+fn read_argument_distinct_on<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<Vec<Arc<Column>>>
+where
+    T: Text<'a> + Eq + AsRef<str>,
+{
+    // [<Table>Field!]
+    let field_enum: EnumType = match field.get_arg(args::DISTINCT_ON) {
+        None => return Ok(vec![]),
+        Some(arg) => match arg.type_().unmodified_type() {
+            __Type::Enum(field_enum) => field_enum,
+            _ => return Err(GraphQLError::schema("Could not locate Field enum type")),
+        },
+    };
+
+    let validated: gson::Value = read_argument(
+        args::DISTINCT_ON,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+
+    let mut columns: Vec<Arc<Column>> = vec![];
+    match validated {
+        gson::Value::Absent | gson::Value::Null => (),
+        gson::Value::Array(x_arr) => {
+            for elem in x_arr.iter() {
+                let field_name = match elem {
+                    gson::Value::String(field_name) => field_name,
+                    _ => return Err(GraphQLError::validation("DistinctOn re-validation error")),
+                };
+                let column = field_enum
+                    .table_field_column(field_name)
+                    .ok_or_else(|| GraphQLError::validation("DistinctOn re-validation error"))?;
+                if !columns.iter().any(|x| x.name == column.name) {
+                    columns.push(column);
+                }
+            }
+        }
+        _ => return Err(GraphQLError::validation("DistinctOn re-validation error")),
+    };
+    Ok(columns)
+}
+
+/// Reads the "orderBy" argument. Auto-appends the primary key
+///
+/// When no ordering is requested, records are sorted by the `distinct_on`
+/// columns so the leading sort keys match as Postgres requires
+fn read_argument_order_by<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+    distinct_on: &[Arc<Column>],
+) -> GraphQLResult<OrderByBuilder>
+where
+    T: Text<'a> + Eq + AsRef<str>,
 {
     // [{"id": "DescNullsLast"}]
     let validated: gson::Value = read_argument(
@@ -1334,42 +1853,8 @@ where
                 // {"id", DescNullsLast}
                 match elem {
                     gson::Value::Absent | gson::Value::Null => continue,
-                    gson::Value::Object(obj) => {
-                        for (column_field_name, order_direction_json) in obj.iter() {
-                            let order_direction = match order_direction_json {
-                                gson::Value::Absent | gson::Value::Null => continue,
-                                gson::Value::String(x) => OrderDirection::from_str(x)?,
-                                _ => {
-                                    return Err(GraphQLError::validation(
-                                        "Order re-validation error 6",
-                                    ));
-                                }
-                            };
-                            let column_input_value: &__InputValue =
-                                match order_field_map.get(column_field_name) {
-                                    Some(input_field) => input_field,
-                                    None => {
-                                        return Err(GraphQLError::validation(
-                                            "Order re-validation error 3",
-                                        ));
-                                    }
-                                };
-
-                            match &column_input_value.sql_type {
-                                Some(NodeSQLType::Column(col)) => {
-                                    let order_rec = OrderByBuilderElem {
-                                        column: Arc::clone(col),
-                                        direction: order_direction,
-                                    };
-                                    orders.push(order_rec);
-                                }
-                                _ => {
-                                    return Err(GraphQLError::validation(
-                                        "Order re-validation error 4",
-                                    ));
-                                }
-                            }
-                        }
+                    gson::Value::Object(_) => {
+                        orders.extend(create_order_by_elems(elem, &order_field_map)?);
                     }
                     _ => return Err(GraphQLError::validation("OrderBy re-validation errror 1")),
                 }
@@ -1378,6 +1863,13 @@ where
         _ => return Err(GraphQLError::validation("OrderBy re-validation errror")),
     };
 
+    if orders.is_empty() {
+        orders.extend(distinct_on.iter().map(|col| OrderByBuilderElem {
+            key: OrderByKey::Column(Arc::clone(col)),
+            direction: OrderDirection::AscNullsLast,
+        }));
+    }
+
     // To acheive consistent pagination, sorting should always include primary key
     let pkey = &order_type
         .table
@@ -1388,7 +1880,7 @@ where
         for col in &order_type.table.columns {
             if &col.name == col_name {
                 let order_rec = OrderByBuilderElem {
-                    column: Arc::clone(col),
+                    key: OrderByKey::Column(Arc::clone(col)),
                     direction: OrderDirection::AscNullsLast,
                 };
                 orders.push(order_rec);
@@ -1399,6 +1891,115 @@ where
     Ok(OrderByBuilder { elems: orders })
 }
 
+/// Converts a validated `<Table>OrderBy` object into order elements, following
+/// relationships into related tables
+fn create_order_by_elems(
+    validated: &gson::Value,
+    order_field_map: &HashMap<String, __InputValue>,
+) -> GraphQLResult<Vec<OrderByBuilderElem>> {
+    let mut orders = vec![];
+
+    let obj = match validated {
+        gson::Value::Absent | gson::Value::Null => return Ok(orders),
+        gson::Value::Object(obj) => obj,
+        _ => return Err(GraphQLError::validation("OrderBy re-validation errror 1")),
+    };
+
+    for (column_field_name, order_value) in obj.iter() {
+        if matches!(order_value, gson::Value::Absent | gson::Value::Null) {
+            continue;
+        }
+        let column_input_value: &__InputValue = match order_field_map.get(column_field_name) {
+            Some(input_field) => input_field,
+            None => {
+                return Err(GraphQLError::validation("Order re-validation error 3"));
+            }
+        };
+
+        match (&column_input_value.sql_type, column_input_value.type_()) {
+            (Some(NodeSQLType::Column(col)), __Type::SearchRankOrderBy(_)) => {
+                let rank_obj = match order_value {
+                    gson::Value::Object(rank_obj) => rank_obj,
+                    _ => return Err(GraphQLError::validation("Order re-validation error 7")),
+                };
+                let query = match rank_obj.get("query") {
+                    Some(gson::Value::String(query)) => query.clone(),
+                    _ => return Err(GraphQLError::validation("Order re-validation error 7")),
+                };
+                let direction = match rank_obj.get("direction") {
+                    Some(direction) => read_order_direction(direction)?,
+                    None => return Err(GraphQLError::validation("Order re-validation error 7")),
+                };
+                orders.push(OrderByBuilderElem {
+                    key: OrderByKey::SearchRank {
+                        column: Arc::clone(col),
+                        query,
+                    },
+                    direction,
+                });
+            }
+            (Some(NodeSQLType::Column(col)), _) => {
+                let order_rec = OrderByBuilderElem {
+                    key: OrderByKey::Column(Arc::clone(col)),
+                    direction: read_order_direction(order_value)?,
+                };
+                orders.push(order_rec);
+            }
+            (Some(NodeSQLType::Relation(fkey)), related_type @ __Type::OrderByEntity(_)) => {
+                let related_table = match &related_type {
+                    __Type::OrderByEntity(order_entity) => Arc::clone(&order_entity.table),
+                    _ => return Err(GraphQLError::validation("Order re-validation error 4")),
+                };
+                let related_orders =
+                    create_order_by_elems(order_value, &input_field_map(&related_type))?;
+
+                for related_order in related_orders {
+                    orders.push(OrderByBuilderElem {
+                        key: OrderByKey::Relation {
+                            fkey: fkey.clone(),
+                            table: Arc::clone(&related_table),
+                            key: Box::new(related_order.key),
+                        },
+                        direction: related_order.direction,
+                    });
+                }
+            }
+            (Some(NodeSQLType::Relation(fkey)), __Type::AggregateOrderBy(aggregate_order)) => {
+                let aggregate_obj = match order_value {
+                    gson::Value::Object(aggregate_obj) => aggregate_obj,
+                    _ => return Err(GraphQLError::validation("Order re-validation error 5")),
+                };
+                for (aggregate_name, direction_value) in aggregate_obj.iter() {
+                    if matches!(direction_value, gson::Value::Absent | gson::Value::Null) {
+                        continue;
+                    }
+                    match aggregate_name.as_str() {
+                        aggregate::COUNT => orders.push(OrderByBuilderElem {
+                            key: OrderByKey::Count {
+                                fkey: fkey.clone(),
+                                table: Arc::clone(&aggregate_order.table),
+                            },
+                            direction: read_order_direction(direction_value)?,
+                        }),
+                        _ => return Err(GraphQLError::validation("Order re-validation error 5")),
+                    }
+                }
+            }
+            _ => {
+                return Err(GraphQLError::validation("Order re-validation error 4"));
+            }
+        }
+    }
+    Ok(orders)
+}
+
+fn read_order_direction(validated: &gson::Value) -> GraphQLResult<OrderDirection> {
+    match validated {
+        gson::Value::String(x) => Ok(OrderDirection::from_str(x)?),
+        _ => Err(GraphQLError::validation("Order re-validation error 6")),
+    }
+}
+
 /// Reads "before" and "after" cursor arguments
 fn read_argument_cursor<'a, T>(
     arg_name: &str,
@@ -1477,6 +2078,15 @@ where
                 args::FILTER,
                 args::ORDER_BY,
             ];
+            if field.get_arg(args::DISTINCT_ON).is_some() {
+                allowed_args.push(args::DISTINCT_ON);
+            }
+            if field.get_arg(args::INCLUDE_DELETED).is_some() {
+                allowed_args.push(args::INCLUDE_DELETED);
+            }
+            if field.get_arg(args::MAX_DEPTH).is_some() {
+                allowed_args.push(args::MAX_DEPTH);
+            }
             allowed_args.extend(extra_allowed_args);
             restrict_allowed_arguments(&allowed_args, query_field)?;
 
@@ -1547,6 +2157,28 @@ where
                 }
             };
 
+            let max_depth: gson::Value = read_argument(
+                args::MAX_DEPTH,
+                field,
+                query_field,
+                variables,
+                variable_definitions,
+            )?;
+            let max_depth: Option<u64> = match max_depth {
+                gson::Value::Absent | gson::Value::Null => None,
+                gson::Value::Number(gson::Number::Integer(n)) if n < 0 => {
+                    return Err(GraphQLError::validation(
+                        "`maxDepth` must be an unsigned integer",
+                    ));
+                }
+                gson::Value::Number(gson::Number::Integer(n)) => Some(n as u64),
+                _ => {
+                    return Err(GraphQLError::internal(
+                        "Internal Error: failed to parse validated maxDepth",
+                    ));
+                }
+            };
+
             let max_rows = xtype
                 .schema
                 .context
@@ -1595,10 +2227,44 @@ where
                 ));
             }
 
-            let filter: FilterBuilder =
+            let mut filter: FilterBuilder =
                 read_argument_filter(field, query_field, variables, variable_definitions)?;
-            let order_by: OrderByBuilder =
-                read_argument_order_by(field, query_field, variables, variable_definitions)?;
+            let distinct_on: Vec<Arc<Column>> =
+                read_argument_distinct_on(field, query_field, variables, variable_definitions)?;
+            let order_by: OrderByBuilder = read_argument_order_by(
+                field,
+                query_field,
+                variables,
+                variable_definitions,
+                &distinct_on,
+            )?;
+
+            // Postgres requires DISTINCT ON expressions to match the leftmost ORDER BY expressions
+            let order_prefix_matches = order_by.elems.len() >= distinct_on.len()
+                && order_by.elems[..distinct_on.len()]
+                    .iter()
+                    .all(|elem| match &elem.key {
+                        OrderByKey::Column(col) => distinct_on.iter().any(|x| x.name == col.name),
+                        _ => false,
+                    });
+            if !order_prefix_matches {
+                return Err(GraphQLError::validation(
+                    "\"orderBy\" must begin with the \"distinctOn\" fields",
+                ));
+            }
+
+            // Soft deleted records are excluded unless explicitly requested
+            let include_deleted =
+                read_argument_include_deleted(field, query_field, variables, variable_definitions)?;
+            if let Some(column) = xtype.table.soft_delete_column()
+                && !include_deleted
+            {
+                filter.elems.push(FilterBuilderElem::Column {
+                    column: Arc::clone(column),
+                    op: FilterOp::Is,
+                    value: serde_json::Value::String("NULL".to_string()),
+                });
+            }
 
             let mut builder_fields: Vec<ConnectionSelection> = vec![];
 
@@ -1640,6 +2306,7 @@ where
                                 &selection_field,
                                 fragment_definitions,
                                 variables,
+                                variable_definitions,
                             )?)
                         }
                         __Type::Scalar(Scalar::Int) => {
@@ -1647,6 +2314,12 @@ where
                                 ConnectionSelection::TotalCount {
                                     alias: alias_or_name(&selection_field),
                                 }
+                            } else if selection_field.name.as_ref()
+                                == connection::TOTAL_COUNT_ESTIMATE
+                            {
+                                ConnectionSelection::TotalCountEstimate {
+                                    alias: alias_or_name(&selection_field),
+                                }
                             } else {
                                 return Err(GraphQLError::internal(format!(
                                     "Unsupported field type for connection field {}",
@@ -1681,9 +2354,25 @@ where
 
             Ok(ConnectionBuilder {
                 alias,
-                source: ConnectionBuilderSource {
-                    table: Arc::clone(&xtype.table),
-                    fkey: xtype.fkey.clone(),
+                source: match (xtype.hierarchy, &xtype.fkey) {
+                    // Hierarchies are joined to the parent record recursively, not directly
+                    (Some(direction), Some(fkey)) => ConnectionBuilderSource {
+                        table: Arc::clone(&xtype.table),
+                        fkey: None,
+                        hierarchy: Some(HierarchyBuilder {
+                            fkey: Arc::clone(&fkey.fkey),
+                            direction,
+                            max_depth,
+                            include_deleted,
+                        }),
+                        junction: None,
+                    },
+                    _ => ConnectionBuilderSource {
+                        table: Arc::clone(&xtype.table),
+                        fkey: xtype.fkey.clone(),
+                        hierarchy: None,
+                        junction: xtype.junction.clone(),
+                    },
                 },
                 first,
                 last,
@@ -1692,106 +2381,510 @@ where
                 after,
                 filter,
                 order_by,
+                distinct_on,
                 selections: builder_fields,
                 max_rows,
             })
         }
-        _ => Err(GraphQLError::internal(format!(
-            "can not build query for non-connection type {:?}",
-            type_.name()
-        ))),
+        _ => Err(GraphQLError::internal(format!(
+            "can not build query for non-connection type {:?}",
+            type_.name()
+        ))),
+    }
+}
+
+fn to_aggregate_builder<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    fragment_definitions: &Vec<FragmentDefinition<'a, T>>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<AggregateBuilder>
+where
+    T: Text<'a> + Eq + AsRef<str> + Clone,
+    T::Value: Hash,
+{
+    let type_ = field.type_().unmodified_type();
+    let __Type::Aggregate(ref _agg_type) = type_ else {
+        return Err(GraphQLError::internal(
+            "Internal Error: Expected AggregateType in to_aggregate_builder",
+        ));
+    };
+
+    let alias = alias_or_name(query_field);
+
+    restrict_allowed_arguments(&[args::FILTER], query_field)?;
+    let filter = read_argument_filter(field, query_field, variables, variable_definitions)?;
+
+    let mut selections = Vec::new();
+    let field_map = field_map(&type_); // Get fields of the AggregateType (count, sum, avg, etc.)
+
+    let type_name = type_.name().ok_or("Aggregate type has no name")?;
+
+    let selection_fields = normalize_selection_set(
+        &query_field.selection_set,
+        fragment_definitions,
+        &type_name,
+        variables,
+    )?;
+
+    for selection_field in selection_fields {
+        let field_name = selection_field.name.as_ref();
+        let sub_field = field_map.get(field_name).ok_or(format!(
+            "Unknown field \"{}\" selected on type \"{}\"",
+            field_name, type_name
+        ))?;
+
+        selections.push(match field_name {
+            aggregate::GROUP_BY => AggregateSelection::GroupBy(to_group_by_builder(
+                sub_field,
+                &selection_field,
+                fragment_definitions,
+                variables,
+                variable_definitions,
+            )?),
+            _ => to_aggregate_selection(
+                field,
+                sub_field,
+                &selection_field,
+                fragment_definitions,
+                variables,
+                variable_definitions,
+            )?,
+        })
+    }
+
+    Ok(AggregateBuilder {
+        alias,
+        filter,
+        selections,
+    })
+}
+
+/// Builds a `count`, per-column aggregate or `__typename` selection on an aggregate type
+fn to_aggregate_selection<'a, T>(
+    field: &__Field,
+    sub_field: &__Field,
+    selection_field: &graphql_parser::query::Field<'a, T>,
+    fragment_definitions: &Vec<FragmentDefinition<'a, T>>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<AggregateSelection>
+where
+    T: Text<'a> + Eq + AsRef<str> + Clone,
+    T::Value: Hash,
+{
+    let field_name = selection_field.name.as_ref();
+    let sub_alias = alias_or_name(selection_field);
+
+    match field_name {
+        aggregate::ARRAY_AGG => restrict_allowed_arguments(&[args::ORDER_BY], selection_field)?,
+        aggregate::PERCENTILE_CONT => {
+            restrict_allowed_arguments(&[args::FRACTION], selection_field)?
+        }
+        _ => restrict_allowed_arguments(&[], selection_field)?,
+    }
+
+    let col_selections = if matches!(field_name, aggregate::COUNT | introspection::TYPENAME) {
+        vec![]
+    } else {
+        to_aggregate_column_builders(sub_field, selection_field, fragment_definitions, variables)?
+    };
+
+    Ok(match field_name {
+        aggregate::COUNT => AggregateSelection::Count { alias: sub_alias },
+        aggregate::SUM => AggregateSelection::Sum {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::AVG => AggregateSelection::Avg {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::MIN => AggregateSelection::Min {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::MAX => AggregateSelection::Max {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::COUNT_DISTINCT => AggregateSelection::CountDistinct {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::STDDEV => AggregateSelection::Stddev {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::VARIANCE => AggregateSelection::Variance {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::BOOL_AND => AggregateSelection::BoolAnd {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::BOOL_OR => AggregateSelection::BoolOr {
+            alias: sub_alias,
+            column_builders: col_selections,
+        },
+        aggregate::ARRAY_AGG => AggregateSelection::ArrayAgg {
+            alias: sub_alias,
+            column_builders: col_selections,
+            order_by: read_argument_order_by(
+                sub_field,
+                selection_field,
+                variables,
+                variable_definitions,
+                &[],
+            )?,
+        },
+        aggregate::PERCENTILE_CONT => AggregateSelection::PercentileCont {
+            alias: sub_alias,
+            column_builders: col_selections,
+            fraction: read_argument_fraction(
+                sub_field,
+                selection_field,
+                variables,
+                variable_definitions,
+            )?,
+        },
+        introspection::TYPENAME => AggregateSelection::Typename {
+            alias: sub_alias,
+            typename: field
+                .type_()
+                .name()
+                .ok_or("Name for aggregate field's type not found")?
+                .to_string(),
+        },
+        _ => {
+            return Err(GraphQLError::internal(format!(
+                "Unknown aggregate field: {}",
+                field_name
+            )));
+        }
+    })
+}
+
+fn read_argument_fraction<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<f64>
+where
+    T: Text<'a> + Eq + AsRef<str>,
+{
+    let fraction: gson::Value = read_argument(
+        args::FRACTION,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+    let fraction = match fraction {
+        gson::Value::Number(gson::Number::Integer(x)) => x as f64,
+        gson::Value::Number(gson::Number::Float(x)) => x,
+        _ => return Err(GraphQLError::validation("Invalid value for \"fraction\"")),
+    };
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(GraphQLError::validation(
+            "\"fraction\" must be between 0 and 1",
+        ));
+    }
+    Ok(fraction)
+}
+
+fn to_group_by_builder<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    fragment_definitions: &Vec<FragmentDefinition<'a, T>>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<GroupByBuilder>
+where
+    T: Text<'a> + Eq + AsRef<str> + Clone,
+    T::Value: Hash,
+{
+    let type_ = field.type_().unmodified_type();
+    let __Type::AggregateGroup(_) = type_ else {
+        return Err(GraphQLError::internal(
+            "Internal Error: Expected AggregateGroupType in to_group_by_builder",
+        ));
+    };
+
+    let alias = alias_or_name(query_field);
+
+    restrict_allowed_arguments(
+        &[
+            args::KEYS,
+            pagination::FIRST,
+            pagination::AFTER,
+            args::ORDER_BY,
+        ],
+        query_field,
+    )?;
+
+    let keys = read_argument_group_by_keys(field, query_field, variables, variable_definitions)?;
+
+    let first: gson::Value = read_argument(
+        pagination::FIRST,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+    let first: Option<u64> = match first {
+        gson::Value::Absent | gson::Value::Null => None,
+        gson::Value::Number(gson::Number::Integer(n)) if n < 0 => {
+            return Err(GraphQLError::validation(
+                "`first` must be an unsigned integer",
+            ));
+        }
+        gson::Value::Number(gson::Number::Integer(n)) => Some(n as u64),
+        _ => {
+            return Err(GraphQLError::internal(
+                "Internal Error: failed to parse validated first",
+            ));
+        }
+    };
+
+    // Group cursors encode the position of the group in the requested order
+    let after: Option<u64> = match read_argument_cursor(
+        pagination::AFTER,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )? {
+        None => None,
+        Some(cursor) => match cursor.elems.as_slice() {
+            [CursorElement { value }] => Some(
+                value
+                    .as_u64()
+                    .ok_or_else(|| GraphQLError::validation("Invalid groupBy cursor"))?,
+            ),
+            _ => return Err(GraphQLError::validation("Invalid groupBy cursor")),
+        },
+    };
+
+    let order_by =
+        read_argument_group_order_by(field, query_field, variables, variable_definitions, &keys)?;
+
+    let mut selections = vec![];
+    let field_map = field_map(&type_);
+    let type_name = type_.name().ok_or("AggregateGroup type has no name")?;
+
+    let selection_fields = normalize_selection_set(
+        &query_field.selection_set,
+        fragment_definitions,
+        &type_name,
+        variables,
+    )?;
+
+    for selection_field in selection_fields {
+        let field_name = selection_field.name.as_ref();
+        let sub_field = field_map.get(field_name).ok_or(format!(
+            "Unknown field \"{}\" selected on type \"{}\"",
+            field_name, type_name
+        ))?;
+
+        selections.push(match field_name {
+            connection::CURSOR => GroupSelection::Cursor {
+                alias: alias_or_name(&selection_field),
+            },
+            aggregate::KEY => {
+                let column_builders = to_aggregate_column_builders(
+                    sub_field,
+                    &selection_field,
+                    fragment_definitions,
+                    variables,
+                )?;
+                for column_builder in &column_builders {
+                    if !keys
+                        .iter()
+                        .any(|k| k.column.name == column_builder.column.name)
+                    {
+                        return Err(GraphQLError::validation(format!(
+                            "\"{}\" is not a groupBy key",
+                            column_builder.alias
+                        )));
+                    }
+                }
+                GroupSelection::Key {
+                    alias: alias_or_name(&selection_field),
+                    column_builders,
+                }
+            }
+            _ => GroupSelection::Aggregate(to_aggregate_selection(
+                field,
+                sub_field,
+                &selection_field,
+                fragment_definitions,
+                variables,
+                variable_definitions,
+            )?),
+        })
+    }
+
+    Ok(GroupByBuilder {
+        alias,
+        keys,
+        first,
+        after,
+        order_by,
+        selections,
+    })
+}
+
+/// Reads the "keys" argument of a `groupBy` selection
+fn read_argument_group_by_keys<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<Vec<GroupByKeyBuilder>>
+where
+    T: Text<'a> + Eq + AsRef<str>,
+{
+    let validated: gson::Value = read_argument(
+        args::KEYS,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+
+    // <Table>Field
+    let field_enum: EnumType = match field
+        .get_arg(args::KEYS)
+        .and_then(|arg| arg.type_().unmodified_type().input_fields())
+        .and_then(|input_fields| {
+            input_fields
+                .into_iter()
+                .find(|x| x.name() == aggregate::FIELD)
+        })
+        .map(|x| x.type_().unmodified_type())
+    {
+        Some(__Type::Enum(field_enum)) => field_enum,
+        _ => return Err(GraphQLError::schema("Could not locate Field enum type")),
+    };
+
+    let mut keys: Vec<GroupByKeyBuilder> = vec![];
+    let gson::Value::Array(x_arr) = validated else {
+        return Err(GraphQLError::validation("GroupBy re-validation error"));
+    };
+    for elem in x_arr.iter() {
+        let gson::Value::Object(obj) = elem else {
+            return Err(GraphQLError::validation("GroupBy re-validation error"));
+        };
+        let column = match obj.get(aggregate::FIELD) {
+            Some(gson::Value::String(field_name)) => field_enum
+                .table_field_column(field_name)
+                .ok_or_else(|| GraphQLError::validation("GroupBy re-validation error"))?,
+            _ => return Err(GraphQLError::validation("GroupBy re-validation error")),
+        };
+        let truncate = match obj.get(aggregate::TRUNCATE) {
+            None | Some(gson::Value::Absent) | Some(gson::Value::Null) => None,
+            Some(gson::Value::String(unit)) => {
+                let is_truncatable = column.type_.as_ref().is_some_and(|t| {
+                    ["date", "timestamp", "timestamptz"].contains(&t.name.as_ref())
+                });
+                if !is_truncatable {
+                    return Err(GraphQLError::validation(
+                        "\"truncate\" may only be used with date and datetime fields",
+                    ));
+                }
+                Some(unit.to_lowercase())
+            }
+            _ => return Err(GraphQLError::validation("GroupBy re-validation error")),
+        };
+        if keys.iter().any(|k| k.column.name == column.name) {
+            return Err(GraphQLError::validation(
+                "Each field may only be used once in groupBy keys",
+            ));
+        }
+        keys.push(GroupByKeyBuilder { column, truncate });
     }
+
+    if keys.is_empty() {
+        return Err(GraphQLError::validation(
+            "groupBy requires at least one key",
+        ));
+    }
+    Ok(keys)
 }
 
-fn to_aggregate_builder<'a, T>(
+/// Reads the "orderBy" argument of a `groupBy` selection. Groups may only be
+/// sorted by their count or by their keys
+fn read_argument_group_order_by<'a, T>(
     field: &__Field,
     query_field: &graphql_parser::query::Field<'a, T>,
-    fragment_definitions: &Vec<FragmentDefinition<'a, T>>,
     variables: &serde_json::Value,
-) -> GraphQLResult<AggregateBuilder>
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+    keys: &[GroupByKeyBuilder],
+) -> GraphQLResult<Vec<GroupOrderByElem>>
 where
-    T: Text<'a> + Eq + AsRef<str> + Clone,
-    T::Value: Hash,
+    T: Text<'a> + Eq + AsRef<str>,
 {
-    let type_ = field.type_().unmodified_type();
-    let __Type::Aggregate(ref _agg_type) = type_ else {
-        return Err(GraphQLError::internal(
-            "Internal Error: Expected AggregateType in to_aggregate_builder",
-        ));
-    };
-
-    let alias = alias_or_name(query_field);
-    let mut selections = Vec::new();
-    let field_map = field_map(&type_); // Get fields of the AggregateType (count, sum, avg, etc.)
-
-    let type_name = type_.name().ok_or("Aggregate type has no name")?;
-
-    let selection_fields = normalize_selection_set(
-        &query_field.selection_set,
-        fragment_definitions,
-        &type_name,
+    let validated: gson::Value = read_argument(
+        args::ORDER_BY,
+        field,
+        query_field,
         variables,
+        variable_definitions,
     )?;
 
-    for selection_field in selection_fields {
-        let field_name = selection_field.name.as_ref();
-        let sub_field = field_map.get(field_name).ok_or(format!(
-            "Unknown field \"{}\" selected on type \"{}\"",
-            field_name, type_name
-        ))?;
-        let sub_alias = alias_or_name(&selection_field);
-
-        let col_selections = if field_name == aggregate::SUM
-            || field_name == aggregate::AVG
-            || field_name == aggregate::MIN
-            || field_name == aggregate::MAX
-        {
-            to_aggregate_column_builders(
-                sub_field,
-                &selection_field,
-                fragment_definitions,
-                variables,
-            )?
-        } else {
-            vec![]
-        };
+    let order_type = field
+        .get_arg(args::ORDER_BY)
+        .ok_or_else(|| GraphQLError::schema("Could not locate groupBy orderBy argument"))?
+        .type_()
+        .unmodified_type();
+    let order_field_map = input_field_map(&order_type);
 
-        selections.push(match field_name {
-            aggregate::COUNT => AggregateSelection::Count { alias: sub_alias },
-            aggregate::SUM => AggregateSelection::Sum {
-                alias: sub_alias,
-                column_builders: col_selections,
-            },
-            aggregate::AVG => AggregateSelection::Avg {
-                alias: sub_alias,
-                column_builders: col_selections,
-            },
-            aggregate::MIN => AggregateSelection::Min {
-                alias: sub_alias,
-                column_builders: col_selections,
-            },
-            aggregate::MAX => AggregateSelection::Max {
-                alias: sub_alias,
-                column_builders: col_selections,
-            },
-            introspection::TYPENAME => AggregateSelection::Typename {
-                alias: sub_alias,
-                typename: field
-                    .type_()
-                    .name()
-                    .ok_or("Name for aggregate field's type not found")?
-                    .to_string(),
-            },
-            _ => {
-                return Err(GraphQLError::internal(format!(
-                    "Unknown aggregate field: {}",
-                    field_name
-                )));
+    let mut orders = vec![];
+    match validated {
+        gson::Value::Absent | gson::Value::Null => (),
+        gson::Value::Array(x_arr) => {
+            for elem in x_arr.iter() {
+                let obj = match elem {
+                    gson::Value::Absent | gson::Value::Null => continue,
+                    gson::Value::Object(obj) => obj,
+                    _ => return Err(GraphQLError::validation("OrderBy re-validation errror")),
+                };
+                for (field_name, order_value) in obj.iter() {
+                    if matches!(order_value, gson::Value::Absent | gson::Value::Null) {
+                        continue;
+                    }
+                    let direction = read_order_direction(order_value)?;
+                    let key = match order_field_map.get(field_name).map(|x| &x.sql_type) {
+                        Some(Some(NodeSQLType::Column(column))) => {
+                            if !keys.iter().any(|k| k.column.name == column.name) {
+                                return Err(GraphQLError::validation(format!(
+                                    "\"{field_name}\" is not a groupBy key"
+                                )));
+                            }
+                            GroupOrderByKey::Column(Arc::clone(column))
+                        }
+                        Some(None) if field_name == aggregate::COUNT => GroupOrderByKey::Count,
+                        _ => return Err(GraphQLError::validation("Order re-validation error 3")),
+                    };
+                    orders.push(GroupOrderByElem { key, direction });
+                }
             }
-        })
-    }
+        }
+        _ => return Err(GraphQLError::validation("OrderBy re-validation errror")),
+    };
 
-    Ok(AggregateBuilder { alias, selections })
+    // Keys break ties so group positions are stable across pages
+    for key in keys {
+        orders.push(GroupOrderByElem {
+            key: GroupOrderByKey::Column(Arc::clone(&key.column)),
+            direction: OrderDirection::AscNullsLast,
+        });
+    }
+    Ok(orders)
 }
 
 fn to_aggregate_column_builders<'a, T>(
@@ -1805,7 +2898,7 @@ where
     T::Value: Hash,
 {
     let type_ = field.type_().unmodified_type();
-    let __Type::AggregateNumeric(_) = type_ else {
+    let (__Type::AggregateNumeric(_) | __Type::AggregateGroupKey(_)) = type_ else {
         return Err(GraphQLError::internal(
             "Internal Error: Expected AggregateNumericType",
         ));
@@ -1829,7 +2922,7 @@ where
             )
         })?;
 
-        let __Type::Scalar(_) = sub_field.type_().unmodified_type() else {
+        let (__Type::Scalar(_) | __Type::Enum(_)) = sub_field.type_().unmodified_type() else {
             return Err(GraphQLError::internal(format!(
                 "Field \"{}\" on type \"{}\" is not a scalar column",
                 col_name, type_name
@@ -1852,6 +2945,53 @@ where
     Ok(column_builers)
 }
 
+/// Selects a column, or only part of a json/b document when a "path" is provided
+fn to_column_selection<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    alias: String,
+    column: &Arc<Column>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<NodeSelection>
+where
+    T: Text<'a> + Eq + AsRef<str>,
+{
+    if field.get_arg(args::PATH).is_none() {
+        return Ok(NodeSelection::Column(ColumnBuilder {
+            alias,
+            column: Arc::clone(column),
+        }));
+    }
+
+    let path: gson::Value = read_argument(
+        args::PATH,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )?;
+
+    Ok(match path {
+        gson::Value::Absent | gson::Value::Null => NodeSelection::Column(ColumnBuilder {
+            alias,
+            column: Arc::clone(column),
+        }),
+        gson::Value::Array(elems) => NodeSelection::JsonPath(JsonPathBuilder {
+            alias,
+            column: Arc::clone(column),
+            path: elems
+                .iter()
+                .map(|elem| match elem {
+                    gson::Value::String(key) => Ok(key.clone()),
+                    _ => Err(GraphQLError::validation("Invalid value for \"path\"")),
+                })
+                .collect::<GraphQLResult<Vec<String>>>()?,
+        }),
+        _ => return Err(GraphQLError::validation("Invalid value for \"path\"")),
+    })
+}
+
 fn to_page_info_builder<'a, T>(
     field: &__Field,
     query_field: &graphql_parser::query::Field<'a, T>,
@@ -1969,6 +3109,9 @@ where
                             connection::CURSOR => EdgeSelection::Cursor {
                                 alias: alias_or_name(&selection_field),
                             },
+                            connection::DEPTH => EdgeSelection::Depth {
+                                alias: alias_or_name(&selection_field),
+                            },
                             introspection::TYPENAME => EdgeSelection::Typename {
                                 alias: alias_or_name(&selection_field),
                                 typename: xtype.name().expect("edge type should have a name"),
@@ -2007,19 +3150,26 @@ where
 {
     let type_ = field.type_().unmodified_type();
 
-    let alias = alias_or_name(query_field);
-
     let xtype: NodeType = match type_.return_type() {
         __Type::Node(xtype) => {
             restrict_allowed_arguments(extra_allowed_args, query_field)?;
             xtype.clone()
         }
         __Type::NodeInterface(node_interface) => {
-            restrict_allowed_arguments(&[args::NODE_ID], query_field)?;
+            let mut allowed_args = vec![args::NODE_ID];
+            if field.get_arg(args::INCLUDE_DELETED).is_some() {
+                allowed_args.push(args::INCLUDE_DELETED);
+            }
+            restrict_allowed_arguments(&allowed_args, query_field)?;
             // The nodeId argument is only valid on the entrypoint field for Node
             // relationships to "node" e.g. within edges, do not have any arguments
-            let node_id: NodeIdInstance =
-                read_argument_node_id(field, query_field, variables, variable_definitions)?;
+            let node_id: NodeIdInstance = read_argument_node_id(
+                field,
+                query_field,
+                variables,
+                variable_definitions,
+                &node_interface.schema,
+            )?;
 
             let possible_types: Vec<__Type> = node_interface.possible_types().unwrap_or(vec![]);
             let xtype = possible_types.iter().find_map(|x| match x {
@@ -2048,6 +3198,166 @@ where
         }
     };
 
+    to_node_builder_for_type(
+        field,
+        query_field,
+        &xtype,
+        fragment_definitions,
+        variables,
+        extra_allowed_args,
+        variable_definitions,
+    )
+}
+
+pub fn to_nodes_builder<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    fragment_definitions: &Vec<FragmentDefinition<'a, T>>,
+    variables: &serde_json::Value,
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<NodesBuilder>
+where
+    T: Text<'a> + Eq + AsRef<str> + Clone,
+    T::Value: Hash,
+{
+    let node_interface = match field.type_().unmodified_type() {
+        __Type::NodeInterface(node_interface) => node_interface,
+        _ => {
+            return Err(GraphQLError::validation(
+                "can not build query for non-node type",
+            ));
+        }
+    };
+
+    let mut allowed_args = vec![args::IDS];
+    if field.get_arg(args::INCLUDE_DELETED).is_some() {
+        allowed_args.push(args::INCLUDE_DELETED);
+    }
+    restrict_allowed_arguments(&allowed_args, query_field)?;
+
+    let encoded_ids = match read_argument(
+        args::IDS,
+        field,
+        query_field,
+        variables,
+        variable_definitions,
+    )? {
+        gson::Value::Array(x) => x,
+        _ => {
+            return Err(GraphQLError::internal(
+                "Internal Error: failed to parse validated ids",
+            ));
+        }
+    };
+
+    let possible_types: Vec<__Type> = node_interface.possible_types().unwrap_or(vec![]);
+
+    let mut lookups: Vec<NodesLookup> = vec![];
+    for (ix, encoded) in encoded_ids.iter().enumerate() {
+        // Ids that can not be decoded, or reference a collection the user can not see,
+        // resolve to null rather than failing the whole request
+        let node_id = match parse_node_id(encoded.clone(), &node_interface.schema) {
+            Ok(node_id) => node_id,
+            Err(_) => continue,
+        };
+
+        let xtype = possible_types.iter().find_map(|x| match x {
+            __Type::Node(node_type)
+                if node_type.table.schema == node_id.schema_name
+                    && node_type.table.name == node_id.table_name =>
+            {
+                Some(node_type)
+            }
+            _ => None,
+        });
+
+        let xtype = match xtype {
+            Some(xtype)
+                if xtype
+                    .table
+                    .primary_key()
+                    .map(|pkey| pkey.column_names.len())
+                    == Some(node_id.values.len()) =>
+            {
+                xtype
+            }
+            _ => continue,
+        };
+
+        // Values are cast to the primary key's types in SQL, where a mismatch would fail
+        // the whole request
+        let is_castable = xtype
+            .table
+            .primary_key_columns()
+            .iter()
+            .zip(&node_id.values)
+            .all(|(column, value)| node_id_value_is_castable(column, value));
+        if !is_castable {
+            continue;
+        }
+
+        match lookups
+            .iter_mut()
+            .find(|lookup| lookup.node.table.oid == xtype.table.oid)
+        {
+            Some(lookup) => lookup.node_ids.push((ix, node_id)),
+            None => lookups.push(NodesLookup {
+                node: to_node_builder_for_type(
+                    field,
+                    query_field,
+                    xtype,
+                    fragment_definitions,
+                    variables,
+                    &[args::IDS],
+                    variable_definitions,
+                )?,
+                node_ids: vec![(ix, node_id)],
+            }),
+        }
+    }
+
+    Ok(NodesBuilder {
+        ids_count: encoded_ids.len(),
+        lookups,
+    })
+}
+
+/// Can a value decoded from a nodeId be cast to the type of its primary key column
+fn node_id_value_is_castable(column: &Column, value: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    match value {
+        Value::Null | Value::Array(_) | Value::Object(_) => false,
+        _ => match column.type_name.as_str() {
+            "smallint" => value.as_i64().is_some_and(|x| i16::try_from(x).is_ok()),
+            "integer" => value.as_i64().is_some_and(|x| i32::try_from(x).is_ok()),
+            "bigint" => value.as_i64().is_some(),
+            // Postgres does not accept the urn form
+            "uuid" => value
+                .as_str()
+                .is_some_and(|x| !x.starts_with("urn:") && uuid::Uuid::parse_str(x).is_ok()),
+            // Other types, mostly text, are cast as is
+            _ => true,
+        },
+    }
+}
+
+/// Builds the selection of a node once its concrete type is known
+fn to_node_builder_for_type<'a, T>(
+    field: &__Field,
+    query_field: &graphql_parser::query::Field<'a, T>,
+    xtype: &NodeType,
+    fragment_definitions: &Vec<FragmentDefinition<'a, T>>,
+    variables: &serde_json::Value,
+    extra_allowed_args: &[&str],
+    variable_definitions: &Vec<VariableDefinition<'a, T>>,
+) -> GraphQLResult<NodeBuilder>
+where
+    T: Text<'a> + Eq + AsRef<str> + Clone,
+    T::Value: Hash,
+{
+    let alias = alias_or_name(query_field);
+
     let type_name = xtype
         .name()
         .ok_or("Encountered type without name in node builder")?;
@@ -2056,6 +3366,9 @@ where
 
     let mut builder_fields = vec![];
     let mut allowed_args = vec![args::NODE_ID];
+    if field.get_arg(args::INCLUDE_DELETED).is_some() {
+        allowed_args.push(args::INCLUDE_DELETED);
+    }
     allowed_args.extend(extra_allowed_args);
     restrict_allowed_arguments(&allowed_args, query_field)?;
 
@@ -2067,14 +3380,39 @@ where
             query_field,
             variables,
             variable_definitions,
+            &xtype.schema,
         )?),
         false => None,
     };
+    let include_deleted =
+        read_argument_include_deleted(field, query_field, variables, variable_definitions)?;
 
-    let selection_fields = normalize_selection_set(
+    // Fragments on the interfaces the type implements, or the union it is returned through
+    let mut abstract_type_names: Vec<String> = xtype
+        .interfaces()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|x| x.name())
+        .collect();
+    let mut sibling_type_names: Vec<String> = vec![];
+    let return_type = field.type_().return_type().unmodified_type();
+    if let __Type::Union(_) | __Type::Interface(_) = &return_type {
+        abstract_type_names.extend(return_type.name());
+        sibling_type_names = return_type
+            .possible_types()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|x| x.name())
+            .filter(|x| x != &type_name)
+            .collect();
+    }
+
+    let selection_fields = normalize_selection_set_of_type(
         &query_field.selection_set,
         fragment_definitions,
         &type_name,
+        &abstract_type_names,
+        &sibling_type_names,
         variables,
     )?;
 
@@ -2092,10 +3430,14 @@ where
 
                 let node_selection = match &f.sql_type {
                     Some(node_sql_type) => match node_sql_type {
-                        NodeSQLType::Column(col) => NodeSelection::Column(ColumnBuilder {
+                        NodeSQLType::Column(col) => to_column_selection(
+                            f,
+                            &selection_field,
                             alias,
-                            column: Arc::clone(col),
-                        }),
+                            col,
+                            variables,
+                            variable_definitions,
+                        )?,
                         NodeSQLType::Function(func) => {
                             let function_selection = match &f.type_() {
                                 __Type::Scalar(_) => FunctionSelection::ScalarSelf,
@@ -2140,10 +3482,18 @@ where
                             NodeSelection::NodeId(NodeIdBuilder {
                                 alias,
                                 columns: pkey_columns.clone(), // interior is arc
-                                table_name: xtype.table.name.clone(),
-                                schema_name: xtype.table.schema.clone(),
+                                prefix: xtype.schema.node_id_prefix(&xtype.table),
                             })
                         }
+                        NodeSQLType::Version(source) => NodeSelection::Version(VersionBuilder {
+                            alias,
+                            source: source.clone(),
+                        }),
+                        NodeSQLType::Relation(_) => {
+                            return Err(GraphQLError::internal(
+                                "Relationship sql type is only valid on filter fields",
+                            ));
+                        }
                     },
                     _ => match f.name().as_ref() {
                         introspection::TYPENAME => NodeSelection::Typename {
@@ -2188,6 +3538,7 @@ where
     }
     Ok(NodeBuilder {
         node_id,
+        include_deleted,
         alias,
         table: Arc::clone(&xtype.table),
         fkey: xtype.fkey.clone(),
@@ -2253,15 +3604,27 @@ where
         }
     }
 
-    // Need values for all primary key columns
-    if pk_values.len() != pkey.column_names.len() {
-        let missing_cols: Vec<_> = pkey
-            .column_names
+    // Need values for all key columns, either the primary key or a unique index
+    let key_column_names: Vec<String> = field
+        .args()
+        .iter()
+        .filter_map(|arg| match &arg.sql_type {
+            Some(NodeSQLType::Column(col)) => Some(col.name.clone()),
+            _ => None,
+        })
+        .collect();
+
+    if pk_values.len() != key_column_names.len() {
+        let missing_cols: Vec<_> = key_column_names
             .iter()
             .filter(|col| !pk_values.contains_key(*col))
             .collect();
         return Err(GraphQLError::argument(format!(
-            "Missing primary key column(s): {}",
+            "Missing {} column(s): {}",
+            match key_column_names == pkey.column_names {
+                true => "primary key",
+                false => "unique key",
+            },
             missing_cols
                 .iter()
                 .map(|s| s.as_str())
@@ -2291,10 +3654,14 @@ where
 
                 let node_selection = match &f.sql_type {
                     Some(node_sql_type) => match node_sql_type {
-                        NodeSQLType::Column(col) => NodeSelection::Column(ColumnBuilder {
+                        NodeSQLType::Column(col) => to_column_selection(
+                            f,
+                            &selection_field,
                             alias,
-                            column: Arc::clone(col),
-                        }),
+                            col,
+                            variables,
+                            variable_definitions,
+                        )?,
                         NodeSQLType::Function(func) => {
                             let function_selection = match &f.type_() {
                                 __Type::Scalar(_) => FunctionSelection::ScalarSelf,
@@ -2337,9 +3704,17 @@ where
                         NodeSQLType::NodeId(pkey_columns) => NodeSelection::NodeId(NodeIdBuilder {
                             alias,
                             columns: pkey_columns.clone(),
-                            table_name: xtype.table.name.clone(),
-                            schema_name: xtype.table.schema.clone(),
+                            prefix: xtype.schema.node_id_prefix(&xtype.table),
+                        }),
+                        NodeSQLType::Version(source) => NodeSelection::Version(VersionBuilder {
+                            alias,
+                            source: source.clone(),
                         }),
+                        NodeSQLType::Relation(_) => {
+                            return Err(GraphQLError::internal(
+                                "Relationship sql type is only valid on filter fields",
+                            ));
+                        }
                     },
                     _ => match f.name().as_ref() {
                         "__typename" => NodeSelection::Typename {
@@ -2383,8 +3758,12 @@ where
         }
     }
 
+    let include_deleted =
+        read_argument_include_deleted(field, query_field, variables, variable_definitions)?;
+
     Ok(NodeByPkBuilder {
         pk_values,
+        include_deleted,
         table: Arc::clone(&xtype.table),
         selections: builder_fields,
     })
@@ -2671,6 +4050,8 @@ impl __Schema {
         T: Text<'a> + Eq + AsRef<str> + Clone,
         T::Value: Hash,
     {
+        let field_map = field_map(&__Type::__Field(__FieldType));
+
         let selection_fields = normalize_selection_set(
             &query_field.selection_set,
             fragment_definitions,
@@ -2687,8 +4068,21 @@ impl __Schema {
                 "name" => __FieldField::Name,
                 "description" => __FieldField::Description,
                 "args" => {
+                    let args_field = field_map
+                        .get("args")
+                        .ok_or_else(|| GraphQLError::internal("__Field args not found"))?;
+                    let include_deprecated = read_argument_include_deprecated(
+                        args_field,
+                        &selection_field,
+                        variables,
+                        variable_definitions,
+                    )?;
+
                     let mut f_builders: Vec<__InputValueBuilder> = vec![];
-                    let args = field.args();
+                    let args = field
+                        .args()
+                        .into_iter()
+                        .filter(|x| include_deprecated || !x.is_deprecated());
 
                     for arg in args {
                         let f_builder = self.to_input_value_builder(
@@ -2848,8 +4242,13 @@ impl __Schema {
                         "name" => __TypeField::Name,
                         "description" => __TypeField::Description,
                         "fields" => {
-                            // TODO read "include_deprecated" arg.
-                            let type_fields = type_.fields(true);
+                            let include_deprecated = read_argument_include_deprecated(
+                                f,
+                                &selection_field,
+                                variables,
+                                variable_definitions,
+                            )?;
+                            let type_fields = type_.fields(include_deprecated);
                             match type_fields {
                                 None => __TypeField::Fields(None),
                                 Some(vec_fields) => {
@@ -2888,13 +4287,22 @@ impl __Schema {
                             }
                         }
                         "inputFields" => {
+                            let include_deprecated = read_argument_include_deprecated(
+                                f,
+                                &selection_field,
+                                variables,
+                                variable_definitions,
+                            )?;
                             let type_inputs = type_.input_fields();
                             match type_inputs {
                                 None => __TypeField::InputFields(None),
                                 Some(vec_fields) => {
                                     let mut f_builders: Vec<__InputValueBuilder> = vec![];
 
-                                    for vec_field in vec_fields {
+                                    for vec_field in vec_fields
+                                        .into_iter()
+                                        .filter(|x| include_deprecated || !x.is_deprecated())
+                                    {
                                         let f_builder = self.to_input_value_builder(
                                             &vec_field,
                                             &selection_field,
@@ -2931,7 +4339,13 @@ impl __Schema {
                             }
                         }
                         "enumValues" => {
-                            let enum_value_builders = match type_.enum_values(true) {
+                            let include_deprecated = read_argument_include_deprecated(
+                                f,
+                                &selection_field,
+                                variables,
+                                variable_definitions,
+                            )?;
+                            let enum_value_builders = match type_.enum_values(include_deprecated) {
                                 Some(enum_values) => {
                                     let mut f_builders: Vec<__EnumValueBuilder> = vec![];
                                     for enum_value in &enum_values {
@@ -12,9 +12,12 @@ pub mod introspection {
 pub mod connection {
     pub const EDGES: &str = "edges";
     pub const NODE: &str = "node";
+    pub const NODES: &str = "nodes";
     pub const PAGE_INFO: &str = "pageInfo";
     pub const TOTAL_COUNT: &str = "totalCount";
+    pub const TOTAL_COUNT_ESTIMATE: &str = "totalCountEstimate";
     pub const CURSOR: &str = "cursor";
+    pub const DEPTH: &str = "depth";
 }
 
 /// Mutation result field names
@@ -36,11 +39,26 @@ pub mod pagination {
 pub mod args {
     pub const FILTER: &str = "filter";
     pub const ORDER_BY: &str = "orderBy";
+    pub const DISTINCT_ON: &str = "distinctOn";
+    pub const KEYS: &str = "keys";
+    pub const FRACTION: &str = "fraction";
+    pub const PATH: &str = "path";
     pub const OBJECTS: &str = "objects";
     pub const SET: &str = "set";
     pub const AT_MOST: &str = "atMost";
     pub const NODE_ID: &str = "nodeId";
+    pub const IDS: &str = "ids";
     pub const NAME: &str = "name";
+    pub const EXPECTED_VERSION: &str = "expectedVersion";
+    pub const INCLUDE_DELETED: &str = "includeDeleted";
+    pub const MAX_DEPTH: &str = "maxDepth";
+}
+
+/// Node field names
+pub mod node {
+    pub const VERSION: &str = "version";
+    pub const ANCESTORS: &str = "ancestors";
+    pub const DESCENDANTS: &str = "descendants";
 }
 
 /// Aggregate function field names
@@ -50,6 +68,17 @@ pub mod aggregate {
     pub const AVG: &str = "avg";
     pub const MIN: &str = "min";
     pub const MAX: &str = "max";
+    pub const COUNT_DISTINCT: &str = "countDistinct";
+    pub const STDDEV: &str = "stddev";
+    pub const VARIANCE: &str = "variance";
+    pub const BOOL_AND: &str = "boolAnd";
+    pub const BOOL_OR: &str = "boolOr";
+    pub const ARRAY_AGG: &str = "arrayAgg";
+    pub const PERCENTILE_CONT: &str = "percentileCont";
+    pub const GROUP_BY: &str = "groupBy";
+    pub const KEY: &str = "key";
+    pub const FIELD: &str = "field";
+    pub const TRUNCATE: &str = "truncate";
 }
 
 /// PageInfo field names
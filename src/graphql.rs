@@ -1,22 +1,28 @@
 use crate::constants::{
-    aggregate, args, connection, introspection, mutation, page_info, pagination,
+    aggregate, args, connection, introspection, mutation, node, page_info, pagination,
 };
 use crate::sql_types::*;
 use cached::SizedCache;
 use cached::proc_macro::cached;
 use itertools::Itertools;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::ops::Deref;
 use std::sync::Arc;
 
-fn is_valid_graphql_name(name: &str) -> bool {
+pub fn is_valid_graphql_name(name: &str) -> bool {
     !name.is_empty()
         && name.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic())
         && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric())
 }
 
+/// Enum values are names other than `true`, `false` and `null`
+pub fn is_valid_graphql_enum_value_name(name: &str) -> bool {
+    is_valid_graphql_name(name) && !["true", "false", "null"].contains(&name)
+}
+
 fn to_base_type_name(name: &str, name_override: &Option<String>, inflect_names: bool) -> String {
     if let Some(name) = name_override {
         return name.to_string();
@@ -51,6 +57,14 @@ fn lowercase_first_letter(token: &str) -> String {
     }
 }
 
+fn uppercase_first_letter(token: &str) -> String {
+    let first_char = token.chars().next();
+    match first_char {
+        Some(c) => format!("{}{}", c.to_uppercase(), &token[c.len_utf8()..]),
+        None => token.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -78,6 +92,48 @@ impl __Schema {
             .any(|s| s.directives.introspection_enabled)
     }
 
+    /// Leading elements of a table's nodeIds, identifying the collection they belong to
+    pub fn node_id_prefix(&self, table: &Table) -> Vec<String> {
+        node_id_prefixes(self)
+            .get(&table.oid)
+            .cloned()
+            .unwrap_or_else(|| vec![table.schema.clone(), table.name.clone()])
+    }
+
+    /// The prefix requested by the schema's `node_id_format` and the table's `node_id_alias`
+    fn configured_node_id_prefix(&self, table: &Table) -> Vec<String> {
+        let format = self
+            .context
+            .schemas
+            .get(&table.schema_oid)
+            .map(|s| s.directives.node_id_format)
+            .unwrap_or(NodeIdFormat::Table);
+
+        // A stable alias survives renaming the table or its type
+        let alias = || {
+            table
+                .directives
+                .node_id_alias
+                .clone()
+                .unwrap_or_else(|| self.graphql_table_base_type_name(table))
+        };
+
+        match format {
+            NodeIdFormat::Table => vec![table.schema.clone(), table.name.clone()],
+            NodeIdFormat::Type => vec![alias()],
+            NodeIdFormat::Opaque => {
+                let digest = Sha256::digest(alias().as_bytes());
+                let mut bytes = [0u8; 16];
+                bytes.copy_from_slice(&digest[..16]);
+                vec![
+                    uuid::Builder::from_custom_bytes(bytes)
+                        .into_uuid()
+                        .to_string(),
+                ]
+            }
+        }
+    }
+
     pub fn is_schema_introspection_enabled(&self, schema_oid: u32) -> bool {
         self.context
             .schemas
@@ -86,7 +142,7 @@ impl __Schema {
             .unwrap_or(false)
     }
 
-    fn graphql_column_field_name(&self, column: &Column) -> String {
+    pub fn graphql_column_field_name(&self, column: &Column) -> String {
         if let Some(override_name) = &column.directives.name {
             return override_name.clone();
         }
@@ -104,7 +160,7 @@ impl __Schema {
         }
     }
 
-    fn graphql_function_field_name(&self, function: &Function) -> String {
+    pub fn graphql_function_field_name(&self, function: &Function) -> String {
         if let Some(override_name) = &function.directives.name {
             return override_name.clone();
         }
@@ -130,7 +186,7 @@ impl __Schema {
         to_base_type_name(&enum_.name, &enum_.directives.name, inflect_names)
     }
 
-    fn graphql_table_base_type_name(&self, table: &Table) -> String {
+    pub fn graphql_table_base_type_name(&self, table: &Table) -> String {
         to_base_type_name(
             &table.name,
             &table.directives.name,
@@ -138,7 +194,11 @@ impl __Schema {
         )
     }
 
-    fn graphql_foreign_key_field_name(&self, fkey: &ForeignKey, reverse_reference: bool) -> String {
+    pub fn graphql_foreign_key_field_name(
+        &self,
+        fkey: &ForeignKey,
+        reverse_reference: bool,
+    ) -> String {
         let mut table_ref: &ForeignKeyTableInfo = &fkey.referenced_table_meta;
         let mut name_override: &Option<String> = &fkey.directives.foreign_name;
         let mut is_unique: bool = true;
@@ -197,31 +257,242 @@ impl __Schema {
         }
     }
 
-    fn graphql_table_select_types_are_valid(&self, table: &Table) -> bool {
+    fn graphql_mutation_field_name(&self, table: &Table, operation: MutationOperation) -> String {
+        match mutation_name_overrides(self).get(&(table.oid, operation)) {
+            Some(name) => name.clone(),
+            None => self.graphql_default_mutation_field_name(table, operation),
+        }
+    }
+
+    fn graphql_default_mutation_field_name(
+        &self,
+        table: &Table,
+        operation: MutationOperation,
+    ) -> String {
+        let table_base_type_name = self.graphql_table_base_type_name(table);
+        match operation {
+            MutationOperation::Insert => format!("insertInto{}Collection", table_base_type_name),
+            MutationOperation::Update => format!("update{}Collection", table_base_type_name),
+            MutationOperation::Delete => format!("deleteFrom{}Collection", table_base_type_name),
+        }
+    }
+
+    pub fn graphql_table_select_types_are_valid(&self, table: &Table) -> bool {
         let check1 = is_valid_graphql_name(&self.graphql_table_base_type_name(table));
         let check2 = table.primary_key().is_some();
         // Any column is selectable
         let check3 = table.is_any_column_selectable();
+        let check4 = !table.directives.omit;
+        // Lookup tables with the enum directive are exposed as enums instead
+        let check5 = !self.graphql_table_enum_types_are_valid(table);
 
-        check1 && check2 && check3
+        check1 && check2 && check3 && check4 && check5
+    }
+
+    pub fn graphql_table_enum_types_are_valid(&self, table: &Table) -> bool {
+        let check1 = is_valid_graphql_name(&self.graphql_table_base_type_name(table));
+        let check2 = table.enum_values.is_some();
+        let check3 = !table.directives.omit;
+        let check4 = self.context.schemas.contains_key(&table.schema_oid);
+        // Columns return the raw key, so every key must be a value of the enum
+        let check5 = table
+            .enum_values
+            .iter()
+            .flatten()
+            .all(|x| is_valid_graphql_enum_value_name(&x.value));
+
+        check1 && check2 && check3 && check4 && check5
+    }
+
+    /// Lookup table with the enum directive whose values type the column
+    fn graphql_column_enum_table(&self, column: &Column) -> Option<Arc<Table>> {
+        let table = self.context.tables.get(&column.enum_table_oid?)?;
+        self.graphql_table_enum_types_are_valid(table)
+            .then(|| Arc::clone(table))
+    }
+
+    /// The foreign key used for `ancestors` and `descendants` fields
+    pub fn graphql_table_hierarchy_foreign_key(&self, table: &Table) -> Option<Arc<ForeignKey>> {
+        self.context.hierarchy_foreign_keys.get(&table.oid).cloned()
+    }
+
+    /// Foreign keys of a junction table linking two tables many-to-many. The junction table
+    /// is read when resolving the relationship, so it must be selectable and not omitted
+    pub fn graphql_table_junction_foreign_keys(
+        &self,
+        table: &Table,
+    ) -> Option<(Arc<ForeignKey>, Arc<ForeignKey>)> {
+        if !table.permissions.is_selectable || table.directives.omit {
+            return None;
+        }
+        self.context.junctions.get(&table.oid).cloned()
+    }
+
+    /// Tables implementing the interface `name` through their `interfaces` directive
+    fn graphql_interface_tables(&self, name: &str) -> Vec<Arc<Table>> {
+        self.context
+            .tables
+            .values()
+            .filter(|x| self.graphql_table_select_types_are_valid(x))
+            .filter(|x| {
+                x.directives
+                    .interfaces
+                    .as_ref()
+                    .is_some_and(|names| names.iter().any(|n| n == name))
+            })
+            .sorted_by_key(|x| x.oid)
+            .cloned()
+            .collect()
+    }
+
+    /// Whether an interface or union declared by a comment directive may be named `name`
+    fn graphql_abstract_type_name_is_valid(&self, name: &str) -> bool {
+        is_valid_graphql_name(name) && !concrete_type_names(self).contains(name)
+    }
+
+    /// A union name declared by more than one function could refer to either's members,
+    /// so none of them use it
+    fn graphql_union_name_is_unique(&self, func: &Function, name: &str) -> bool {
+        let function_name_to_count = Function::function_names_to_count(&self.context.functions);
+        !self.context.functions.iter().any(|x| {
+            x.oid != func.oid
+                && x.is_supported(&self.context, &function_name_to_count)
+                && x.directives.union.as_ref().is_some_and(|u| u.name == name)
+        })
+    }
+
+    /// Names of the interfaces declared by any table
+    fn graphql_interface_names(&self) -> Vec<String> {
+        self.context
+            .tables
+            .values()
+            .filter(|x| self.graphql_table_select_types_are_valid(x))
+            .flat_map(|x| x.directives.interfaces.clone().unwrap_or_default())
+            .filter(|x| self.graphql_abstract_type_name_is_valid(x))
+            .unique()
+            .sorted()
+            .collect()
+    }
+
+    /// Tables a polymorphic function's rows may identify. Each must have one primary key
+    /// column for every returned column after the table name
+    pub fn graphql_function_member_tables(&self, func: &Function) -> Vec<Arc<Table>> {
+        let tables: Vec<Arc<Table>> = match (&func.directives.union, &func.directives.interface) {
+            (Some(union), _) => union
+                .types
+                .iter()
+                .filter_map(|x| self.context.get_table_by_name(&func.schema_name, x))
+                .filter(|x| self.graphql_table_select_types_are_valid(x))
+                .cloned()
+                .collect(),
+            (None, Some(interface)) => self.graphql_interface_tables(interface),
+            (None, None) => vec![],
+        };
+
+        let num_key_columns = func
+            .return_columns
+            .as_ref()
+            .map(|x| x.len().saturating_sub(1))
+            .unwrap_or(0);
+
+        tables
+            .into_iter()
+            .filter(|x| x.primary_key_columns().len() == num_key_columns)
+            .collect()
     }
 
     fn graphql_table_insert_types_are_valid(&self, table: &Table) -> bool {
         let check1 = self.graphql_table_select_types_are_valid(table);
         let check2 = table.is_any_column_insertable();
-        check1 && check2
+        let check3 = table.is_mutation_enabled(MutationOperation::Insert);
+        check1 && check2 && check3
     }
 
     fn graphql_table_update_types_are_valid(&self, table: &Table) -> bool {
         let check1 = self.graphql_table_select_types_are_valid(table);
         let check2 = table.is_any_column_updatable();
-        check1 && check2
+        let check3 = table.is_mutation_enabled(MutationOperation::Update);
+        check1 && check2 && check3
     }
 
     fn graphql_table_delete_types_are_valid(&self, table: &Table) -> bool {
         let check1 = self.graphql_table_select_types_are_valid(table);
-        let check2 = table.permissions.is_deletable;
-        check1 && check2
+        // Soft deleted tables mark records as deleted rather than removing them
+        let check2 = match table.soft_delete_column() {
+            Some(column) => column.permissions.is_updatable,
+            None => table.permissions.is_deletable,
+        };
+        let check3 = table.is_mutation_enabled(MutationOperation::Delete);
+        check1 && check2 && check3
+    }
+
+    /// Relationships from `table` to tables with valid select types, along with the related table
+    fn graphql_table_relations(&self, table: &Table) -> Vec<(ForeignKeyReversible, Arc<Table>)> {
+        let mut relations = vec![];
+        for fkey in self.context.foreign_keys().iter() {
+            for reverse_reference in [false, true] {
+                let (local_oid, foreign_oid) = match reverse_reference {
+                    false => (fkey.local_table_meta.oid, fkey.referenced_table_meta.oid),
+                    true => (fkey.referenced_table_meta.oid, fkey.local_table_meta.oid),
+                };
+                if local_oid != table.oid {
+                    continue;
+                }
+                let foreign_table = match self.context.get_table_by_oid(foreign_oid) {
+                    Some(foreign_table) => foreign_table,
+                    None => continue,
+                };
+                if !self.graphql_table_select_types_are_valid(foreign_table) {
+                    continue;
+                }
+                relations.push((
+                    ForeignKeyReversible {
+                        fkey: Arc::clone(fkey),
+                        reverse_reference,
+                    },
+                    Arc::clone(foreign_table),
+                ));
+            }
+        }
+        relations
+    }
+
+    /// Columns of `table` that may be used as sort keys
+    fn graphql_table_orderable_columns(&self, table: &Table) -> Vec<Arc<Column>> {
+        table
+            .columns
+            .iter()
+            .filter(|x| x.permissions.is_selectable)
+            .filter(|x| !x.is_omitted(OmitOperation::Select))
+            .filter(|x| !x.is_omitted(OmitOperation::Order))
+            // No ordering by arrays
+            .filter(|x| !x.type_name.ends_with("[]"))
+            // No ordering by composites
+            .filter(|x| !self.context.is_composite(x.type_oid))
+            // No ordering by json/b. they do not support = or <>
+            .filter(|x| !["json", "jsonb"].contains(&x.type_name.as_ref()))
+            .filter(|x| is_valid_graphql_name(&self.graphql_column_field_name(x)))
+            .map(Arc::clone)
+            .collect()
+    }
+
+    fn graphql_relation_is_to_many(&self, relation: &ForeignKeyReversible) -> bool {
+        relation.reverse_reference && !self.context.fkey_is_locally_unique(&relation.fkey)
+    }
+
+    /// Whether `table` is exposed through a to-many relationship, which requires
+    /// its `RelationFilter` and `AggregateOrderBy` types
+    fn graphql_table_is_to_many_related(&self, table: &Table) -> bool {
+        self.context
+            .foreign_keys()
+            .iter()
+            .filter(|x| x.local_table_meta.oid == table.oid)
+            .filter(|x| !self.context.fkey_is_locally_unique(x))
+            .any(|x| {
+                self.context
+                    .get_table_by_oid(x.referenced_table_meta.oid)
+                    .is_some_and(|t| self.graphql_table_select_types_are_valid(t))
+            })
     }
 }
 
@@ -355,7 +626,7 @@ pub trait ___Field {
 
     // isDeprecated: Boolean!
     fn is_deprecated(&self) -> bool {
-        self.deprecation_reason().is_none()
+        self.deprecation_reason().is_some()
     }
 
     // deprecationReason: String
@@ -369,6 +640,8 @@ pub enum NodeSQLType {
     Column(Arc<Column>),
     NodeId(Vec<Arc<Column>>),
     Function(Arc<Function>),
+    Version(VersionSource),
+    Relation(ForeignKeyReversible),
 }
 
 #[derive(Clone, Debug)]
@@ -463,8 +736,13 @@ impl __InputValue {
     }
 
     // deprecationReason: String
+    /// Inputs backed by a deprecated column are deprecated, unless they are required
     pub fn deprecation_reason(&self) -> Option<String> {
-        None
+        match (&self.sql_type, &self.type_) {
+            (_, __Type::NonNull(_)) => None,
+            (Some(NodeSQLType::Column(col)), _) => col.directives.deprecated.clone(),
+            _ => None,
+        }
     }
 }
 
@@ -505,7 +783,7 @@ impl __EnumValue {
 
     // deprecationReason: String
     pub fn deprecation_reason(&self) -> Option<String> {
-        None
+        self.deprecation_reason.clone()
     }
 }
 
@@ -520,6 +798,8 @@ pub enum __Type {
     Node(NodeType),
     Enum(EnumType),
     NodeInterface(NodeInterfaceType),
+    Interface(InterfaceType),
+    Union(UnionType),
     // Mutation
     Mutation(MutationType),
     InsertInput(InsertInputType),
@@ -529,9 +809,12 @@ pub enum __Type {
     DeleteResponse(DeleteResponseType),
     FuncCallResponse(FuncCallResponseType),
     OrderBy(OrderByType),
+    SearchRankOrderBy(SearchRankOrderByType),
     OrderByEntity(OrderByEntityType),
+    AggregateOrderBy(AggregateOrderByType),
     FilterType(FilterTypeType),
     FilterEntity(FilterEntityType),
+    RelationFilter(RelationFilterType),
 
     // Constant
     PageInfo(PageInfoType),
@@ -551,6 +834,10 @@ pub enum __Type {
     NonNull(NonNullType),
     Aggregate(AggregateType),
     AggregateNumeric(AggregateNumericType),
+    AggregateGroup(AggregateGroupType),
+    AggregateGroupKey(AggregateGroupKeyType),
+    GroupByKey(GroupByKeyType),
+    AggregateGroupOrderBy(AggregateGroupOrderByType),
 }
 
 #[cached(
@@ -604,6 +891,8 @@ impl ___Type for __Type {
             Self::Edge(x) => x.kind(),
             Self::Node(x) => x.kind(),
             Self::NodeInterface(x) => x.kind(),
+            Self::Interface(x) => x.kind(),
+            Self::Union(x) => x.kind(),
             Self::InsertInput(x) => x.kind(),
             Self::InsertResponse(x) => x.kind(),
             Self::UpdateInput(x) => x.kind(),
@@ -612,8 +901,11 @@ impl ___Type for __Type {
             Self::FuncCallResponse(x) => x.kind(),
             Self::FilterType(x) => x.kind(),
             Self::FilterEntity(x) => x.kind(),
+            Self::RelationFilter(x) => x.kind(),
             Self::OrderBy(x) => x.kind(),
+            Self::SearchRankOrderBy(x) => x.kind(),
             Self::OrderByEntity(x) => x.kind(),
+            Self::AggregateOrderBy(x) => x.kind(),
             Self::PageInfo(x) => x.kind(),
             Self::__TypeKind(x) => x.kind(),
             Self::__Schema(x) => x.kind(),
@@ -627,6 +919,10 @@ impl ___Type for __Type {
             Self::NonNull(x) => x.kind(),
             Self::Aggregate(x) => x.kind(),
             Self::AggregateNumeric(x) => x.kind(),
+            Self::AggregateGroup(x) => x.kind(),
+            Self::AggregateGroupKey(x) => x.kind(),
+            Self::GroupByKey(x) => x.kind(),
+            Self::AggregateGroupOrderBy(x) => x.kind(),
         }
     }
 
@@ -641,6 +937,8 @@ impl ___Type for __Type {
             Self::Edge(x) => x.name(),
             Self::Node(x) => x.name(),
             Self::NodeInterface(x) => x.name(),
+            Self::Interface(x) => x.name(),
+            Self::Union(x) => x.name(),
             Self::InsertInput(x) => x.name(),
             Self::InsertResponse(x) => x.name(),
             Self::UpdateInput(x) => x.name(),
@@ -649,8 +947,11 @@ impl ___Type for __Type {
             Self::FuncCallResponse(x) => x.name(),
             Self::FilterType(x) => x.name(),
             Self::FilterEntity(x) => x.name(),
+            Self::RelationFilter(x) => x.name(),
             Self::OrderBy(x) => x.name(),
+            Self::SearchRankOrderBy(x) => x.name(),
             Self::OrderByEntity(x) => x.name(),
+            Self::AggregateOrderBy(x) => x.name(),
             Self::PageInfo(x) => x.name(),
             Self::__TypeKind(x) => x.name(),
             Self::__Schema(x) => x.name(),
@@ -664,6 +965,10 @@ impl ___Type for __Type {
             Self::NonNull(x) => x.name(),
             Self::Aggregate(x) => x.name(),
             Self::AggregateNumeric(x) => x.name(),
+            Self::AggregateGroup(x) => x.name(),
+            Self::AggregateGroupKey(x) => x.name(),
+            Self::GroupByKey(x) => x.name(),
+            Self::AggregateGroupOrderBy(x) => x.name(),
         }
     }
 
@@ -678,6 +983,8 @@ impl ___Type for __Type {
             Self::Edge(x) => x.description(),
             Self::Node(x) => x.description(),
             Self::NodeInterface(x) => x.description(),
+            Self::Interface(x) => x.description(),
+            Self::Union(x) => x.description(),
             Self::InsertInput(x) => x.description(),
             Self::InsertResponse(x) => x.description(),
             Self::UpdateInput(x) => x.description(),
@@ -686,8 +993,11 @@ impl ___Type for __Type {
             Self::FuncCallResponse(x) => x.description(),
             Self::FilterType(x) => x.description(),
             Self::FilterEntity(x) => x.description(),
+            Self::RelationFilter(x) => x.description(),
             Self::OrderBy(x) => x.description(),
+            Self::SearchRankOrderBy(x) => x.description(),
             Self::OrderByEntity(x) => x.description(),
+            Self::AggregateOrderBy(x) => x.description(),
             Self::PageInfo(x) => x.description(),
             Self::__TypeKind(x) => x.description(),
             Self::__Schema(x) => x.description(),
@@ -701,45 +1011,65 @@ impl ___Type for __Type {
             Self::NonNull(x) => x.description(),
             Self::Aggregate(x) => x.description(),
             Self::AggregateNumeric(x) => x.description(),
+            Self::AggregateGroup(x) => x.description(),
+            Self::AggregateGroupKey(x) => x.description(),
+            Self::GroupByKey(x) => x.description(),
+            Self::AggregateGroupOrderBy(x) => x.description(),
         }
     }
 
     // # OBJECT and INTERFACE only
     // fields(includeDeprecated: Boolean = false): [__Field!]
-    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
-        match self {
-            Self::Scalar(x) => x.fields(_include_deprecated),
-            Self::Enum(x) => x.fields(_include_deprecated),
-            Self::Query(x) => x.fields(_include_deprecated),
-            Self::Mutation(x) => x.fields(_include_deprecated),
-            Self::Connection(x) => x.fields(_include_deprecated),
-            Self::Edge(x) => x.fields(_include_deprecated),
-            Self::Node(x) => x.fields(_include_deprecated),
-            Self::NodeInterface(x) => x.fields(_include_deprecated),
-            Self::InsertInput(x) => x.fields(_include_deprecated),
-            Self::InsertResponse(x) => x.fields(_include_deprecated),
-            Self::UpdateInput(x) => x.fields(_include_deprecated),
-            Self::UpdateResponse(x) => x.fields(_include_deprecated),
-            Self::DeleteResponse(x) => x.fields(_include_deprecated),
-            Self::FuncCallResponse(x) => x.fields(_include_deprecated),
-            Self::FilterType(x) => x.fields(_include_deprecated),
-            Self::FilterEntity(x) => x.fields(_include_deprecated),
-            Self::OrderBy(x) => x.fields(_include_deprecated),
-            Self::OrderByEntity(x) => x.fields(_include_deprecated),
-            Self::PageInfo(x) => x.fields(_include_deprecated),
-            Self::__TypeKind(x) => x.fields(_include_deprecated),
-            Self::__Schema(x) => x.fields(_include_deprecated),
-            Self::__Type(x) => x.fields(_include_deprecated),
-            Self::__Field(x) => x.fields(_include_deprecated),
-            Self::__InputValue(x) => x.fields(_include_deprecated),
-            Self::__EnumValue(x) => x.fields(_include_deprecated),
-            Self::__DirectiveLocation(x) => x.fields(_include_deprecated),
-            Self::__Directive(x) => x.fields(_include_deprecated),
-            Self::List(x) => x.fields(_include_deprecated),
-            Self::NonNull(x) => x.fields(_include_deprecated),
-            Self::Aggregate(x) => x.fields(_include_deprecated),
-            Self::AggregateNumeric(x) => x.fields(_include_deprecated),
-        }
+    fn fields(&self, include_deprecated: bool) -> Option<Vec<__Field>> {
+        let fields = match self {
+            Self::Scalar(x) => x.fields(include_deprecated),
+            Self::Enum(x) => x.fields(include_deprecated),
+            Self::Query(x) => x.fields(include_deprecated),
+            Self::Mutation(x) => x.fields(include_deprecated),
+            Self::Connection(x) => x.fields(include_deprecated),
+            Self::Edge(x) => x.fields(include_deprecated),
+            Self::Node(x) => x.fields(include_deprecated),
+            Self::NodeInterface(x) => x.fields(include_deprecated),
+            Self::Interface(x) => x.fields(include_deprecated),
+            Self::Union(x) => x.fields(include_deprecated),
+            Self::InsertInput(x) => x.fields(include_deprecated),
+            Self::InsertResponse(x) => x.fields(include_deprecated),
+            Self::UpdateInput(x) => x.fields(include_deprecated),
+            Self::UpdateResponse(x) => x.fields(include_deprecated),
+            Self::DeleteResponse(x) => x.fields(include_deprecated),
+            Self::FuncCallResponse(x) => x.fields(include_deprecated),
+            Self::FilterType(x) => x.fields(include_deprecated),
+            Self::FilterEntity(x) => x.fields(include_deprecated),
+            Self::RelationFilter(x) => x.fields(include_deprecated),
+            Self::OrderBy(x) => x.fields(include_deprecated),
+            Self::SearchRankOrderBy(x) => x.fields(include_deprecated),
+            Self::OrderByEntity(x) => x.fields(include_deprecated),
+            Self::AggregateOrderBy(x) => x.fields(include_deprecated),
+            Self::PageInfo(x) => x.fields(include_deprecated),
+            Self::__TypeKind(x) => x.fields(include_deprecated),
+            Self::__Schema(x) => x.fields(include_deprecated),
+            Self::__Type(x) => x.fields(include_deprecated),
+            Self::__Field(x) => x.fields(include_deprecated),
+            Self::__InputValue(x) => x.fields(include_deprecated),
+            Self::__EnumValue(x) => x.fields(include_deprecated),
+            Self::__DirectiveLocation(x) => x.fields(include_deprecated),
+            Self::__Directive(x) => x.fields(include_deprecated),
+            Self::List(x) => x.fields(include_deprecated),
+            Self::NonNull(x) => x.fields(include_deprecated),
+            Self::Aggregate(x) => x.fields(include_deprecated),
+            Self::AggregateNumeric(x) => x.fields(include_deprecated),
+            Self::AggregateGroup(x) => x.fields(include_deprecated),
+            Self::AggregateGroupKey(x) => x.fields(include_deprecated),
+            Self::GroupByKey(x) => x.fields(include_deprecated),
+            Self::AggregateGroupOrderBy(x) => x.fields(include_deprecated),
+        };
+        // Deprecated fields are only listed when requested
+        fields.map(|fields| {
+            fields
+                .into_iter()
+                .filter(|x| include_deprecated || !x.is_deprecated())
+                .collect()
+        })
     }
 
     // # OBJECT only
@@ -754,6 +1084,8 @@ impl ___Type for __Type {
             Self::Edge(x) => x.interfaces(),
             Self::Node(x) => x.interfaces(),
             Self::NodeInterface(x) => x.interfaces(),
+            Self::Interface(x) => x.interfaces(),
+            Self::Union(x) => x.interfaces(),
             Self::InsertInput(x) => x.interfaces(),
             Self::InsertResponse(x) => x.interfaces(),
             Self::UpdateInput(x) => x.interfaces(),
@@ -762,8 +1094,11 @@ impl ___Type for __Type {
             Self::FuncCallResponse(x) => x.interfaces(),
             Self::FilterType(x) => x.interfaces(),
             Self::FilterEntity(x) => x.interfaces(),
+            Self::RelationFilter(x) => x.interfaces(),
             Self::OrderBy(x) => x.interfaces(),
+            Self::SearchRankOrderBy(x) => x.interfaces(),
             Self::OrderByEntity(x) => x.interfaces(),
+            Self::AggregateOrderBy(x) => x.interfaces(),
             Self::PageInfo(x) => x.interfaces(),
             Self::__TypeKind(x) => x.interfaces(),
             Self::__Schema(x) => x.interfaces(),
@@ -777,6 +1112,10 @@ impl ___Type for __Type {
             Self::NonNull(x) => x.interfaces(),
             Self::Aggregate(x) => x.interfaces(),
             Self::AggregateNumeric(x) => x.interfaces(),
+            Self::AggregateGroup(x) => x.interfaces(),
+            Self::AggregateGroupKey(x) => x.interfaces(),
+            Self::GroupByKey(x) => x.interfaces(),
+            Self::AggregateGroupOrderBy(x) => x.interfaces(),
         }
     }
 
@@ -785,46 +1124,64 @@ impl ___Type for __Type {
     fn possible_types(&self) -> Option<Vec<__Type>> {
         match self {
             Self::NodeInterface(x) => x.possible_types(),
+            Self::Interface(x) => x.possible_types(),
+            Self::Union(x) => x.possible_types(),
             _ => None,
         }
     }
 
     // # ENUM only
     // enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
-    fn enum_values(&self, _include_deprecated: bool) -> Option<Vec<__EnumValue>> {
-        match self {
-            Self::Scalar(x) => x.enum_values(_include_deprecated),
-            Self::Enum(x) => x.enum_values(_include_deprecated),
-            Self::Query(x) => x.enum_values(_include_deprecated),
-            Self::Mutation(x) => x.enum_values(_include_deprecated),
-            Self::Connection(x) => x.enum_values(_include_deprecated),
-            Self::Edge(x) => x.enum_values(_include_deprecated),
-            Self::Node(x) => x.enum_values(_include_deprecated),
-            Self::NodeInterface(x) => x.enum_values(_include_deprecated),
-            Self::InsertInput(x) => x.enum_values(_include_deprecated),
-            Self::InsertResponse(x) => x.enum_values(_include_deprecated),
-            Self::UpdateInput(x) => x.enum_values(_include_deprecated),
-            Self::UpdateResponse(x) => x.enum_values(_include_deprecated),
-            Self::DeleteResponse(x) => x.enum_values(_include_deprecated),
-            Self::FuncCallResponse(x) => x.enum_values(_include_deprecated),
-            Self::FilterType(x) => x.enum_values(_include_deprecated),
-            Self::FilterEntity(x) => x.enum_values(_include_deprecated),
-            Self::OrderBy(x) => x.enum_values(_include_deprecated),
-            Self::OrderByEntity(x) => x.enum_values(_include_deprecated),
-            Self::PageInfo(x) => x.enum_values(_include_deprecated),
-            Self::__TypeKind(x) => x.enum_values(_include_deprecated),
-            Self::__Schema(x) => x.enum_values(_include_deprecated),
-            Self::__Type(x) => x.enum_values(_include_deprecated),
-            Self::__Field(x) => x.enum_values(_include_deprecated),
-            Self::__InputValue(x) => x.enum_values(_include_deprecated),
-            Self::__EnumValue(x) => x.enum_values(_include_deprecated),
-            Self::__DirectiveLocation(x) => x.enum_values(_include_deprecated),
-            Self::__Directive(x) => x.enum_values(_include_deprecated),
-            Self::List(x) => x.enum_values(_include_deprecated),
-            Self::NonNull(x) => x.enum_values(_include_deprecated),
-            Self::Aggregate(x) => x.enum_values(_include_deprecated),
-            Self::AggregateNumeric(x) => x.enum_values(_include_deprecated),
-        }
+    fn enum_values(&self, include_deprecated: bool) -> Option<Vec<__EnumValue>> {
+        let enum_values = match self {
+            Self::Scalar(x) => x.enum_values(include_deprecated),
+            Self::Enum(x) => x.enum_values(include_deprecated),
+            Self::Query(x) => x.enum_values(include_deprecated),
+            Self::Mutation(x) => x.enum_values(include_deprecated),
+            Self::Connection(x) => x.enum_values(include_deprecated),
+            Self::Edge(x) => x.enum_values(include_deprecated),
+            Self::Node(x) => x.enum_values(include_deprecated),
+            Self::NodeInterface(x) => x.enum_values(include_deprecated),
+            Self::Interface(x) => x.enum_values(include_deprecated),
+            Self::Union(x) => x.enum_values(include_deprecated),
+            Self::InsertInput(x) => x.enum_values(include_deprecated),
+            Self::InsertResponse(x) => x.enum_values(include_deprecated),
+            Self::UpdateInput(x) => x.enum_values(include_deprecated),
+            Self::UpdateResponse(x) => x.enum_values(include_deprecated),
+            Self::DeleteResponse(x) => x.enum_values(include_deprecated),
+            Self::FuncCallResponse(x) => x.enum_values(include_deprecated),
+            Self::FilterType(x) => x.enum_values(include_deprecated),
+            Self::FilterEntity(x) => x.enum_values(include_deprecated),
+            Self::RelationFilter(x) => x.enum_values(include_deprecated),
+            Self::OrderBy(x) => x.enum_values(include_deprecated),
+            Self::SearchRankOrderBy(x) => x.enum_values(include_deprecated),
+            Self::OrderByEntity(x) => x.enum_values(include_deprecated),
+            Self::AggregateOrderBy(x) => x.enum_values(include_deprecated),
+            Self::PageInfo(x) => x.enum_values(include_deprecated),
+            Self::__TypeKind(x) => x.enum_values(include_deprecated),
+            Self::__Schema(x) => x.enum_values(include_deprecated),
+            Self::__Type(x) => x.enum_values(include_deprecated),
+            Self::__Field(x) => x.enum_values(include_deprecated),
+            Self::__InputValue(x) => x.enum_values(include_deprecated),
+            Self::__EnumValue(x) => x.enum_values(include_deprecated),
+            Self::__DirectiveLocation(x) => x.enum_values(include_deprecated),
+            Self::__Directive(x) => x.enum_values(include_deprecated),
+            Self::List(x) => x.enum_values(include_deprecated),
+            Self::NonNull(x) => x.enum_values(include_deprecated),
+            Self::Aggregate(x) => x.enum_values(include_deprecated),
+            Self::AggregateNumeric(x) => x.enum_values(include_deprecated),
+            Self::AggregateGroup(x) => x.enum_values(include_deprecated),
+            Self::AggregateGroupKey(x) => x.enum_values(include_deprecated),
+            Self::GroupByKey(x) => x.enum_values(include_deprecated),
+            Self::AggregateGroupOrderBy(x) => x.enum_values(include_deprecated),
+        };
+        // Deprecated enum values are only listed when requested
+        enum_values.map(|enum_values| {
+            enum_values
+                .into_iter()
+                .filter(|x| include_deprecated || !x.is_deprecated())
+                .collect()
+        })
     }
 
     // # INPUT_OBJECT only
@@ -839,6 +1196,8 @@ impl ___Type for __Type {
             Self::Edge(x) => x.input_fields(),
             Self::Node(x) => x.input_fields(),
             Self::NodeInterface(x) => x.input_fields(),
+            Self::Interface(x) => x.input_fields(),
+            Self::Union(x) => x.input_fields(),
             Self::InsertInput(x) => x.input_fields(),
             Self::InsertResponse(x) => x.input_fields(),
             Self::UpdateInput(x) => x.input_fields(),
@@ -847,8 +1206,11 @@ impl ___Type for __Type {
             Self::FuncCallResponse(x) => x.input_fields(),
             Self::FilterType(x) => x.input_fields(),
             Self::FilterEntity(x) => x.input_fields(),
+            Self::RelationFilter(x) => x.input_fields(),
             Self::OrderBy(x) => x.input_fields(),
+            Self::SearchRankOrderBy(x) => x.input_fields(),
             Self::OrderByEntity(x) => x.input_fields(),
+            Self::AggregateOrderBy(x) => x.input_fields(),
             Self::PageInfo(x) => x.input_fields(),
             Self::__TypeKind(x) => x.input_fields(),
             Self::__Schema(x) => x.input_fields(),
@@ -862,6 +1224,10 @@ impl ___Type for __Type {
             Self::NonNull(x) => x.input_fields(),
             Self::Aggregate(x) => x.input_fields(),
             Self::AggregateNumeric(x) => x.input_fields(),
+            Self::AggregateGroup(x) => x.input_fields(),
+            Self::AggregateGroupKey(x) => x.input_fields(),
+            Self::GroupByKey(x) => x.input_fields(),
+            Self::AggregateGroupOrderBy(x) => x.input_fields(),
         }
     }
 
@@ -913,14 +1279,24 @@ impl __Type {
             __Type::UpdateResponse(t) => Some(t.table.schema_oid),
             __Type::DeleteResponse(t) => Some(t.table.schema_oid),
             __Type::FilterEntity(t) => Some(t.table.schema_oid),
+            __Type::RelationFilter(t) => Some(t.table.schema_oid),
             __Type::OrderByEntity(t) => Some(t.table.schema_oid),
+            __Type::AggregateOrderBy(t) => Some(t.table.schema_oid),
             __Type::Enum(t) => match &t.enum_ {
                 EnumSource::Enum(e) => Some(e.schema_oid),
                 EnumSource::FilterIs => None,
+                EnumSource::TableField(t) => Some(t.schema_oid),
+                EnumSource::DateTruncUnit => None,
+                EnumSource::Table(t) => Some(t.schema_oid),
             },
             __Type::FuncCallResponse(t) => Some(t.function.schema_oid),
+            __Type::Union(t) => Some(t.function.schema_oid),
             __Type::Aggregate(t) => Some(t.table.schema_oid),
             __Type::AggregateNumeric(t) => Some(t.table.schema_oid),
+            __Type::AggregateGroup(t) => Some(t.table.schema_oid),
+            __Type::AggregateGroupKey(t) => Some(t.table.schema_oid),
+            __Type::GroupByKey(t) => Some(t.table.schema_oid),
+            __Type::AggregateGroupOrderBy(t) => Some(t.table.schema_oid),
             _ => None,
         }
     }
@@ -1051,10 +1427,27 @@ pub struct ForeignKeyReversible {
     pub reverse_reference: bool,
 }
 
+/// A table linking two others through a foreign key to each. The connection's records are
+/// those referenced by `foreign_fkey` from rows referencing the parent record by `local_fkey`
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct JunctionReference {
+    pub table: Arc<Table>,
+    pub local_fkey: Arc<ForeignKey>,
+    pub foreign_fkey: Arc<ForeignKey>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum HierarchyDirection {
+    Ancestors,
+    Descendants,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct ConnectionType {
     pub table: Arc<Table>,
     pub fkey: Option<ForeignKeyReversible>,
+    pub hierarchy: Option<HierarchyDirection>,
+    pub junction: Option<JunctionReference>,
 
     pub schema: Arc<__Schema>,
 }
@@ -1062,7 +1455,7 @@ pub struct ConnectionType {
 impl ConnectionType {
     // default arguments for all connections
     fn get_connection_input_args(&self) -> Vec<__InputValue> {
-        vec![
+        let mut args = vec![
             __InputValue {
                 name_: pagination::FIRST.to_string(),
                 type_: __Type::Scalar(Scalar::Int),
@@ -1131,7 +1524,32 @@ impl ConnectionType {
                 default_value: None,
                 sql_type: None,
             },
-        ]
+        ];
+        if !self
+            .schema
+            .graphql_table_orderable_columns(&self.table)
+            .is_empty()
+        {
+            args.push(__InputValue {
+                name_: args::DISTINCT_ON.to_string(),
+                type_: __Type::List(ListType {
+                    type_: Box::new(__Type::NonNull(NonNullType {
+                        type_: Box::new(__Type::Enum(EnumType {
+                            enum_: EnumSource::TableField(Arc::clone(&self.table)),
+                            schema: self.schema.clone(),
+                        })),
+                    })),
+                }),
+                description: Some(
+                    "Return only the first record for each distinct combination of the provided fields"
+                        .to_string(),
+                ),
+                default_value: None,
+                sql_type: None,
+            });
+        }
+        args.extend(include_deleted_arg(&self.table));
+        args
     }
 }
 
@@ -1139,6 +1557,10 @@ impl ConnectionType {
 pub enum EnumSource {
     Enum(Arc<Enum>),
     FilterIs,
+    TableField(Arc<Table>),
+    DateTruncUnit,
+    // Lookup table with the enum directive
+    Table(Arc<Table>),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -1147,6 +1569,20 @@ pub struct EnumType {
     pub schema: Arc<__Schema>,
 }
 
+impl EnumType {
+    /// Column referenced by a value of a `<Table>Field` enum
+    pub fn table_field_column(&self, value: &str) -> Option<Arc<Column>> {
+        match &self.enum_ {
+            EnumSource::TableField(table) => self
+                .schema
+                .graphql_table_orderable_columns(table)
+                .into_iter()
+                .find(|col| self.schema.graphql_column_field_name(col) == value),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct OrderByType {}
 
@@ -1161,6 +1597,10 @@ pub enum FilterableType {
     Scalar(Scalar),
     Enum(EnumType),
     List(ListType),
+    // Full text search on tsvector columns
+    Search,
+    // String comparisons plus full text search on text columns with a `search_config`
+    StringSearch,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -1181,6 +1621,8 @@ impl FilterTypeType {
                     .name()
                     .expect("inner list type name should exist")
             ),
+            FilterableType::Search => "Search".to_string(),
+            FilterableType::StringSearch => "StringSearch".to_string(),
         }
     }
 }
@@ -1191,9 +1633,31 @@ pub struct FilterEntityType {
     pub schema: Arc<__Schema>,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SearchRankOrderByType {}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AggregateOrderByType {
+    pub table: Arc<Table>,
+    pub schema: Arc<__Schema>,
+}
+
+/// Precisions accepted by `date_trunc` when grouping aggregates
+pub const DATE_TRUNC_UNITS: [&str; 8] = [
+    "SECOND", "MINUTE", "HOUR", "DAY", "WEEK", "MONTH", "QUARTER", "YEAR",
+];
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RelationFilterType {
+    pub table: Arc<Table>,
+    pub schema: Arc<__Schema>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct EdgeType {
     pub table: Arc<Table>,
+    // Edges of ancestors/descendants connections expose their `depth`
+    pub hierarchy: bool,
     pub schema: Arc<__Schema>,
 }
 
@@ -1214,6 +1678,20 @@ pub struct NodeInterfaceType {
     pub schema: Arc<__Schema>,
 }
 
+/// Interface declared by the `interfaces` directive of the tables implementing it
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct InterfaceType {
+    pub name: String,
+    pub schema: Arc<__Schema>,
+}
+
+/// Union of the tables listed by a function's `union` directive
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct UnionType {
+    pub function: Arc<Function>,
+    pub schema: Arc<__Schema>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct PageInfoType;
 
@@ -1232,26 +1710,73 @@ impl ___Type for QueryType {
 
     fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
         let mut f = Vec::new();
+
+        // Only offered when soft deletable records could be returned
+        let node_include_deleted_arg = self
+            .schema
+            .context
+            .tables
+            .values()
+            .filter(|table| self.schema.graphql_table_select_types_are_valid(table))
+            .find_map(include_deleted_arg);
+
+        let mut node_args = vec![__InputValue {
+            name_: args::NODE_ID.to_string(),
+            type_: __Type::NonNull(NonNullType {
+                type_: Box::new(__Type::Scalar(Scalar::ID)),
+            }),
+            description: Some("The record's `ID`".to_string()),
+            default_value: None,
+            sql_type: None,
+        }];
+        node_args.extend(node_include_deleted_arg.clone());
+
         let single_entrypoint = __Field {
             name_: connection::NODE.to_string(),
             type_: __Type::NodeInterface(NodeInterfaceType {
                 schema: Arc::clone(&self.schema),
             }),
-            args: vec![__InputValue {
-                name_: args::NODE_ID.to_string(),
-                type_: __Type::NonNull(NonNullType {
-                    type_: Box::new(__Type::Scalar(Scalar::ID)),
-                }),
-                description: Some("The record's `ID`".to_string()),
-                default_value: None,
-                sql_type: None,
-            }],
+            args: node_args,
             description: Some("Retrieve a record by its `ID`".to_string()),
             deprecation_reason: None,
             sql_type: None,
         };
         f.push(single_entrypoint);
 
+        let mut nodes_args = vec![__InputValue {
+            name_: args::IDS.to_string(),
+            type_: __Type::NonNull(NonNullType {
+                type_: Box::new(__Type::List(ListType {
+                    type_: Box::new(__Type::NonNull(NonNullType {
+                        type_: Box::new(__Type::Scalar(Scalar::ID)),
+                    })),
+                })),
+            }),
+            description: Some("The records' `ID`s".to_string()),
+            default_value: None,
+            sql_type: None,
+        }];
+        nodes_args.extend(node_include_deleted_arg);
+
+        let batch_entrypoint = __Field {
+            name_: connection::NODES.to_string(),
+            type_: __Type::NonNull(NonNullType {
+                type_: Box::new(__Type::List(ListType {
+                    type_: Box::new(__Type::NodeInterface(NodeInterfaceType {
+                        schema: Arc::clone(&self.schema),
+                    })),
+                })),
+            }),
+            args: nodes_args,
+            description: Some(
+                "Retrieve records by their `ID`s, in the order requested. Ids that do not match a record resolve to null"
+                    .to_string(),
+            ),
+            deprecation_reason: None,
+            sql_type: None,
+        };
+        f.push(batch_entrypoint);
+
         for table in self
             .schema
             .context
@@ -1265,6 +1790,8 @@ impl ___Type for QueryType {
                 let connection_type = ConnectionType {
                     table: Arc::clone(table),
                     fkey: None,
+                    hierarchy: None,
+                    junction: None,
                     schema: Arc::clone(&self.schema),
                 };
 
@@ -1286,21 +1813,17 @@ impl ___Type for QueryType {
 
                 f.push(collection_entrypoint);
 
-                // Add single record query by primary key if the table has a primary key
-                // and the primary key types are supported (int, bigint, uuid, string)
-                if let Some(primary_key) = table.primary_key()
-                    && table.has_supported_pk_types_for_by_pk()
-                {
-                    let node_type = NodeType {
-                        table: Arc::clone(table),
-                        fkey: None,
-                        reverse_reference: None,
-                        schema: Arc::clone(&self.schema),
-                    };
+                let node_type = NodeType {
+                    table: Arc::clone(table),
+                    fkey: None,
+                    reverse_reference: None,
+                    schema: Arc::clone(&self.schema),
+                };
 
-                    // Create arguments for each primary key column
-                    let mut pk_args = Vec::new();
-                    for col_name in &primary_key.column_names {
+                // Create a required argument for each column identifying the record
+                let lookup_args = |column_names: &[String]| {
+                    let mut args = Vec::new();
+                    for col_name in column_names {
                         if let Some(col) = table.columns.iter().find(|c| &c.name == col_name) {
                             let col_type = sql_column_to_graphql_type(col, &self.schema)
                                 .ok_or_else(|| {
@@ -1320,7 +1843,7 @@ impl ___Type for QueryType {
                                 __Type::NonNull(_) => col_type,
                                 t => __Type::NonNull(NonNullType { type_: Box::new(t) }),
                             };
-                            pk_args.push(__InputValue {
+                            args.push(__InputValue {
                                 name_: arg_name,
                                 type_: non_null_col_type,
                                 description: Some(format!("The record's `{}` value", col_name)),
@@ -1329,11 +1852,19 @@ impl ___Type for QueryType {
                             });
                         }
                     }
+                    args.extend(include_deleted_arg(table));
+                    args
+                };
 
+                // Add single record query by primary key if the table has a primary key
+                // and the primary key types are supported (int, bigint, uuid, string)
+                if let Some(primary_key) = table.primary_key()
+                    && table.has_supported_pk_types_for_by_pk()
+                {
                     let pk_entrypoint = __Field {
                         name_: format!("{}ByPk", lowercase_first_letter(table_base_type_name)),
-                        type_: __Type::Node(node_type),
-                        args: pk_args,
+                        type_: __Type::Node(node_type.clone()),
+                        args: lookup_args(&primary_key.column_names),
                         description: Some(format!(
                             "Retrieve a record of type `{}` by its primary key",
                             table_base_type_name
@@ -1344,6 +1875,46 @@ impl ___Type for QueryType {
 
                     f.push(pk_entrypoint);
                 }
+
+                // Add single record queries by unique index e.g. accountByEmail
+                for index in table.unique_lookup_indexes() {
+                    let columns: Vec<&Arc<Column>> = index
+                        .column_names
+                        .iter()
+                        .filter_map(|name| table.columns.iter().find(|c| &c.name == name))
+                        .collect();
+
+                    let field_name = format!(
+                        "{}By{}",
+                        lowercase_first_letter(table_base_type_name),
+                        columns
+                            .iter()
+                            .map(|c| uppercase_first_letter(
+                                &self.schema.graphql_column_field_name(c)
+                            ))
+                            .join("And")
+                    );
+                    // Redundant unique indexes produce the same field
+                    if f.iter().any(|x| x.name_ == field_name) {
+                        continue;
+                    }
+
+                    f.push(__Field {
+                        name_: field_name,
+                        type_: __Type::Node(node_type.clone()),
+                        args: lookup_args(&index.column_names),
+                        description: Some(format!(
+                            "Retrieve a record of type `{}` by its unique {}",
+                            table_base_type_name,
+                            columns
+                                .iter()
+                                .map(|c| format!("`{}`", c.name))
+                                .join(" and ")
+                        )),
+                        deprecation_reason: None,
+                        sql_type: None,
+                    });
+                }
             }
         }
 
@@ -1406,6 +1977,30 @@ fn function_fields(schema: &Arc<__Schema>, volatilities: &[FunctionVolatility])
         .filter(|func| func.is_supported(&schema.context, &function_name_to_count))
         .filter(|func| volatilities.contains(&func.volatility))
         .filter_map(|func| match sql_types.get(&func.type_oid) {
+            _ if func.is_polymorphic() => {
+                let mut args = function_args(schema, func);
+                // Offered when any member table soft deletes records
+                if !args.iter().any(|x| x.name_ == args::INCLUDE_DELETED) {
+                    args.extend(
+                        schema
+                            .graphql_function_member_tables(func)
+                            .iter()
+                            .find_map(include_deleted_arg),
+                    );
+                }
+                polymorphic_function_return_type(schema, func).map(|return_type| __Field {
+                    name_: schema.graphql_function_field_name(func),
+                    type_: __Type::FuncCallResponse(FuncCallResponseType {
+                        function: Arc::clone(func),
+                        schema: Arc::clone(schema),
+                        return_type: Box::new(return_type),
+                    }),
+                    args,
+                    description: func.directives.description.clone(),
+                    deprecation_reason: func.directives.deprecated.clone(),
+                    sql_type: Some(NodeSQLType::Function(Arc::clone(func))),
+                })
+            }
             None => None,
             Some(sql_type) => {
                 if let Some(return_type) = sql_type.to_graphql_type(None, func.is_set_of, schema) {
@@ -1444,7 +2039,7 @@ fn function_fields(schema: &Arc<__Schema>, volatilities: &[FunctionVolatility])
                         }),
                         args: gql_args,
                         description: func.directives.description.clone(),
-                        deprecation_reason: None,
+                        deprecation_reason: func.directives.deprecated.clone(),
                         sql_type: Some(NodeSQLType::Function(Arc::clone(func))),
                     })
                 } else {
@@ -1456,8 +2051,47 @@ fn function_fields(schema: &Arc<__Schema>, volatilities: &[FunctionVolatility])
         .collect()
 }
 
-fn function_args(schema: &Arc<__Schema>, func: &Arc<Function>) -> Vec<__InputValue> {
-    let sql_types = &schema.context.types;
+/// `[<Union or Interface>!]!` for functions returning rows of several tables
+fn polymorphic_function_return_type(
+    schema: &Arc<__Schema>,
+    func: &Arc<Function>,
+) -> Option<__Type> {
+    if schema.graphql_function_member_tables(func).is_empty() {
+        return None;
+    }
+
+    // Names must not collide with other types, including interfaces declared by tables
+    let type_ = match (&func.directives.union, &func.directives.interface) {
+        (Some(union), _)
+            if schema.graphql_abstract_type_name_is_valid(&union.name)
+                && !schema.graphql_interface_names().contains(&union.name)
+                && schema.graphql_union_name_is_unique(func, &union.name) =>
+        {
+            __Type::Union(UnionType {
+                function: Arc::clone(func),
+                schema: Arc::clone(schema),
+            })
+        }
+        (None, Some(interface)) if schema.graphql_interface_names().contains(interface) => {
+            __Type::Interface(InterfaceType {
+                name: interface.clone(),
+                schema: Arc::clone(schema),
+            })
+        }
+        _ => return None,
+    };
+
+    Some(__Type::NonNull(NonNullType {
+        type_: Box::new(__Type::List(ListType {
+            type_: Box::new(__Type::NonNull(NonNullType {
+                type_: Box::new(type_),
+            })),
+        })),
+    }))
+}
+
+fn function_args(schema: &Arc<__Schema>, func: &Arc<Function>) -> Vec<__InputValue> {
+    let sql_types = &schema.context.types;
     func.args()
         .filter(|(_, _, arg_name, _)| !arg_name.is_none())
         .filter_map(
@@ -1507,6 +2141,33 @@ fn function_args(schema: &Arc<__Schema>, func: &Arc<Function>) -> Vec<__InputVal
         .collect()
 }
 
+/// The optional `expectedVersion` argument accepted by update and delete mutations
+/// on tables with the `version` directive enabled
+fn expected_version_arg(table: &Arc<Table>) -> Option<__InputValue> {
+    table.version_source().map(|_| __InputValue {
+        name_: args::EXPECTED_VERSION.to_string(),
+        type_: __Type::Scalar(Scalar::String(None)),
+        description: Some(
+            "Fails with a conflict if any record matching the `filter` is not at this version"
+                .to_string(),
+        ),
+        default_value: None,
+        sql_type: None,
+    })
+}
+
+/// The optional `includeDeleted` argument accepted by queries on tables with the
+/// `soft_delete` directive
+fn include_deleted_arg(table: &Arc<Table>) -> Option<__InputValue> {
+    table.soft_delete_column().map(|_| __InputValue {
+        name_: args::INCLUDE_DELETED.to_string(),
+        type_: __Type::Scalar(Scalar::Boolean),
+        description: Some("Include records that have been soft deleted".to_string()),
+        default_value: Some("false".to_string()),
+        sql_type: None,
+    })
+}
+
 impl ___Type for MutationType {
     fn kind(&self) -> __TypeKind {
         __TypeKind::OBJECT
@@ -1529,7 +2190,9 @@ impl ___Type for MutationType {
 
             if self.schema.graphql_table_insert_types_are_valid(table) {
                 f.push(__Field {
-                    name_: format!("insertInto{}Collection", table_base_type_name),
+                    name_: self
+                        .schema
+                        .graphql_mutation_field_name(table, MutationOperation::Insert),
                     type_: __Type::InsertResponse(InsertResponseType {
                         table: Arc::clone(table),
                         schema: Arc::clone(&self.schema),
@@ -1561,7 +2224,10 @@ impl ___Type for MutationType {
 
             if self.schema.graphql_table_update_types_are_valid(table) {
                 f.push(__Field {
-                    name_: format!("update{}Collection", table_base_type_name),
+                    name_: self.schema.graphql_mutation_field_name(
+                        table,
+                        MutationOperation::Update,
+                    ),
                     type_: __Type::NonNull(NonNullType {
                         type_: Box::new(__Type::UpdateResponse(UpdateResponseType {
                             table: Arc::clone(table),
@@ -1600,7 +2266,10 @@ impl ___Type for MutationType {
                             default_value: Some("1".to_string()),
                             sql_type: None,
                         },
-                    ],
+                    ]
+                    .into_iter()
+                    .chain(expected_version_arg(table))
+                    .collect(),
                     description: Some(format!(
                         "Updates zero or more records in the `{}` collection",
                         table_base_type_name
@@ -1612,7 +2281,10 @@ impl ___Type for MutationType {
 
             if self.schema.graphql_table_delete_types_are_valid(table) {
                 f.push(__Field {
-                    name_: format!("deleteFrom{}Collection", table_base_type_name),
+                    name_: self.schema.graphql_mutation_field_name(
+                        table,
+                        MutationOperation::Delete,
+                    ),
                     type_: __Type::NonNull(NonNullType {
                         type_: Box::new(__Type::DeleteResponse(DeleteResponseType {
                             table: Arc::clone(table),
@@ -1642,7 +2314,10 @@ impl ___Type for MutationType {
                             default_value: Some("1".to_string()),
                             sql_type: None,
                         },
-                    ],
+                    ]
+                    .into_iter()
+                    .chain(expected_version_arg(table))
+                    .collect(),
                     description: Some(format!(
                         "Deletes zero or more records from the `{}` collection",
                         table_base_type_name
@@ -1737,6 +2412,12 @@ impl ___Type for EnumType {
                 )
             }
             EnumSource::FilterIs => Some("FilterIs".to_string()),
+            EnumSource::TableField(table) => Some(format!(
+                "{}Field",
+                self.schema.graphql_table_base_type_name(table)
+            )),
+            EnumSource::DateTruncUnit => Some("DateTruncUnit".to_string()),
+            EnumSource::Table(table) => Some(self.schema.graphql_table_base_type_name(table)),
         }
     }
 
@@ -1758,7 +2439,11 @@ impl ___Type for EnumType {
                         .and_then(|mappings| mappings.get_by_left(&x.name).cloned())
                         .unwrap_or_else(|| x.name.clone()),
                     description: None,
-                    deprecation_reason: None,
+                    deprecation_reason: enum_
+                        .directives
+                        .deprecated
+                        .as_ref()
+                        .and_then(|deprecated| deprecated.get(&x.name).cloned()),
                 })
                 .collect(),
             EnumSource::FilterIs => {
@@ -1775,6 +2460,34 @@ impl ___Type for EnumType {
                     },
                 ]
             }
+            EnumSource::TableField(table) => self
+                .schema
+                .graphql_table_orderable_columns(table)
+                .iter()
+                .map(|col| __EnumValue {
+                    name: self.schema.graphql_column_field_name(col),
+                    description: None,
+                    deprecation_reason: None,
+                })
+                .collect(),
+            EnumSource::DateTruncUnit => DATE_TRUNC_UNITS
+                .iter()
+                .map(|unit| __EnumValue {
+                    name: unit.to_string(),
+                    description: None,
+                    deprecation_reason: None,
+                })
+                .collect(),
+            EnumSource::Table(table) => table
+                .enum_values
+                .iter()
+                .flatten()
+                .map(|x| __EnumValue {
+                    name: x.value.clone(),
+                    description: x.description.clone(),
+                    deprecation_reason: None,
+                })
+                .collect(),
         })
     }
 }
@@ -1786,8 +2499,12 @@ impl ___Type for ConnectionType {
 
     fn name(&self) -> Option<String> {
         Some(format!(
-            "{}Connection",
-            self.schema.graphql_table_base_type_name(&self.table)
+            "{}{}Connection",
+            self.schema.graphql_table_base_type_name(&self.table),
+            match self.hierarchy {
+                Some(_) => "Hierarchy",
+                None => "",
+            }
         ))
     }
 
@@ -1795,6 +2512,7 @@ impl ___Type for ConnectionType {
         let table_base_type_name = &self.schema.graphql_table_base_type_name(&self.table);
         let edge_type = __Type::Edge(EdgeType {
             table: Arc::clone(&self.table),
+            hierarchy: self.hierarchy.is_some(),
             schema: self.schema.clone(),
         });
 
@@ -1845,6 +2563,27 @@ impl ___Type for ConnectionType {
             fields.push(total_count);
         }
 
+        // Conditionally add totalCountEstimate based on the directive
+        if let Some(total_count_estimate_directive) =
+            self.table.directives.total_count_estimate.as_ref()
+            && total_count_estimate_directive.enabled
+        {
+            let total_count_estimate = __Field {
+                name_: connection::TOTAL_COUNT_ESTIMATE.to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::Scalar(Scalar::Int)),
+                }),
+                args: vec![],
+                description: Some(
+                    "An approximate number of records matching the `filter` criteria, estimated by the query planner from table statistics. May differ from the exact count"
+                        .to_string(),
+                ),
+                deprecation_reason: None,
+                sql_type: None,
+            };
+            fields.push(total_count_estimate);
+        }
+
         // Conditionally add aggregate based on the directive
         if let Some(aggregate_directive) = self.table.directives.aggregate.as_ref()
             && aggregate_directive.enabled
@@ -1855,7 +2594,19 @@ impl ___Type for ConnectionType {
                     table: Arc::clone(&self.table),
                     schema: self.schema.clone(),
                 }),
-                args: vec![],
+                args: vec![__InputValue {
+                    name_: args::FILTER.to_string(),
+                    type_: __Type::FilterEntity(FilterEntityType {
+                        table: Arc::clone(&self.table),
+                        schema: self.schema.clone(),
+                    }),
+                    description: Some(
+                        "Filters to apply to the aggregated records in addition to the collection's `filter`"
+                            .to_string(),
+                    ),
+                    default_value: None,
+                    sql_type: None,
+                }],
                 description: Some(format!(
                     "Aggregate functions calculated on the collection of `{table_base_type_name}`"
                 )),
@@ -1869,6 +2620,111 @@ impl ___Type for ConnectionType {
     }
 }
 
+impl ___Type for InterfaceType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::INTERFACE
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(self.name.clone())
+    }
+
+    fn possible_types(&self) -> Option<Vec<__Type>> {
+        Some(
+            self.schema
+                .graphql_interface_tables(&self.name)
+                .into_iter()
+                .map(|table| {
+                    __Type::Node(NodeType {
+                        table,
+                        fkey: None,
+                        reverse_reference: None,
+                        schema: Arc::clone(&self.schema),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    // The `nodeId` and column fields with the same name and type on every implementing table
+    fn fields(&self, include_deprecated: bool) -> Option<Vec<__Field>> {
+        let field_sets: Vec<Vec<__Field>> = self
+            .possible_types()
+            .unwrap_or_default()
+            .iter()
+            .map(|type_| {
+                type_
+                    .fields(include_deprecated)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|x| {
+                        matches!(
+                            x.sql_type,
+                            Some(NodeSQLType::NodeId(_)) | Some(NodeSQLType::Column(_))
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let signature = |field: &__Field| (field.name(), type_signature(&field.type_));
+
+        let Some((first, rest)) = field_sets.split_first() else {
+            return Some(vec![]);
+        };
+        Some(
+            first
+                .iter()
+                .filter(|field| {
+                    rest.iter()
+                        .all(|fields| fields.iter().any(|x| signature(x) == signature(field)))
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl ___Type for UnionType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::UNION
+    }
+
+    fn name(&self) -> Option<String> {
+        self.function
+            .directives
+            .union
+            .as_ref()
+            .map(|x| x.name.clone())
+    }
+
+    fn possible_types(&self) -> Option<Vec<__Type>> {
+        Some(
+            self.schema
+                .graphql_function_member_tables(&self.function)
+                .into_iter()
+                .map(|table| {
+                    __Type::Node(NodeType {
+                        table,
+                        fkey: None,
+                        reverse_reference: None,
+                        schema: Arc::clone(&self.schema),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The type as written in SDL e.g. `[String!]!`
+fn type_signature(type_: &__Type) -> String {
+    match type_ {
+        __Type::NonNull(x) => format!("{}!", type_signature(&x.type_)),
+        __Type::List(x) => format!("[{}]", type_signature(&x.type_)),
+        _ => type_.name().unwrap_or_default(),
+    }
+}
+
 impl ___Type for NodeInterfaceType {
     fn kind(&self) -> __TypeKind {
         __TypeKind::INTERFACE
@@ -1922,13 +2778,17 @@ impl ___Type for EdgeType {
 
     fn name(&self) -> Option<String> {
         Some(format!(
-            "{}Edge",
-            self.schema.graphql_table_base_type_name(&self.table)
+            "{}{}Edge",
+            self.schema.graphql_table_base_type_name(&self.table),
+            match self.hierarchy {
+                true => "Hierarchy",
+                false => "",
+            }
         ))
     }
 
     fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
-        Some(vec![
+        let mut fields = vec![
             __Field {
                 name_: connection::CURSOR.to_string(),
                 type_: __Type::NonNull(NonNullType {
@@ -1954,12 +2814,30 @@ impl ___Type for EdgeType {
                 deprecation_reason: None,
                 sql_type: None,
             },
-        ])
+        ];
+
+        if self.hierarchy {
+            fields.push(__Field {
+                name_: connection::DEPTH.to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::Scalar(Scalar::Int)),
+                }),
+                args: vec![],
+                description: Some(
+                    "Number of foreign key hops between the record and the parent record"
+                        .to_string(),
+                ),
+                deprecation_reason: None,
+                sql_type: None,
+            });
+        }
+
+        Some(fields)
     }
 }
 
 impl Type {
-    fn to_graphql_type(
+    pub fn to_graphql_type(
         &self,
         max_characters: Option<i32>,
         is_set_of: bool,
@@ -2051,6 +2929,8 @@ impl Type {
                             true => Some(__Type::Connection(ConnectionType {
                                 table: Arc::clone(table),
                                 fkey: None,
+                                hierarchy: None,
+                                junction: None,
                                 schema: Arc::clone(schema),
                             })),
                             false => Some(__Type::Node(NodeType {
@@ -2073,7 +2953,13 @@ impl Type {
 
 pub fn sql_column_to_graphql_type(col: &Column, schema: &Arc<__Schema>) -> Option<__Type> {
     let sql_type = schema.context.types.get(&col.type_oid)?;
-    let maybe_type_w_list_mod = sql_type.to_graphql_type(col.max_characters, false, schema);
+    let maybe_type_w_list_mod = match schema.graphql_column_enum_table(col) {
+        Some(table) => Some(__Type::Enum(EnumType {
+            enum_: EnumSource::Table(table),
+            schema: Arc::clone(schema),
+        })),
+        None => sql_type.to_graphql_type(col.max_characters, false, schema),
+    };
     match maybe_type_w_list_mod {
         None => None,
         Some(type_with_list_mod) => match col.is_not_null {
@@ -2092,6 +2978,9 @@ impl NodeType {
         type_: __Type,
         is_reverse_reference: bool,
     ) -> __Type {
+        // Soft deleted records are not returned, so the relationship may be null
+        let is_soft_deleted =
+            matches!(&type_, __Type::Node(x) if x.table.soft_delete_column().is_some());
         if fkey.local_table_meta.column_names.iter().any(|colname| {
             self.table
                 .columns
@@ -2099,6 +2988,7 @@ impl NodeType {
                 .any(|c| &c.name == colname && c.is_not_null)
                 && !fkey.referenced_table_meta.is_rls_enabled
                 && !is_reverse_reference
+                && !is_soft_deleted
         }) {
             __Type::NonNull(NonNullType {
                 type_: Box::new(type_),
@@ -2131,6 +3021,19 @@ impl ___Type for NodeType {
             }))
         }
 
+        if let Some(names) = &self.table.directives.interfaces {
+            for name in names
+                .iter()
+                .filter(|x| self.schema.graphql_abstract_type_name_is_valid(x))
+                .unique()
+            {
+                interfaces.push(__Type::Interface(InterfaceType {
+                    name: name.clone(),
+                    schema: Arc::clone(&self.schema),
+                }))
+            }
+        }
+
         match interfaces.is_empty() {
             false => Some(interfaces),
             true => None,
@@ -2138,19 +3041,36 @@ impl ___Type for NodeType {
     }
 
     fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
-        let column_fields = self
+        let column_fields: Vec<__Field> = self
             .table
             .columns
             .iter()
             .filter(|x| x.permissions.is_selectable)
+            .filter(|x| !x.is_omitted(OmitOperation::Select))
             .filter(|x| !self.schema.context.is_composite(x.type_oid))
             .filter_map(|col| {
                 sql_column_to_graphql_type(col, &self.schema).map(|utype| __Field {
                     name_: self.schema.graphql_column_field_name(col),
                     type_: utype,
-                    args: vec![],
+                    args: match col.is_json() {
+                        true => vec![__InputValue {
+                            name_: args::PATH.to_string(),
+                            type_: __Type::List(ListType {
+                                type_: Box::new(__Type::NonNull(NonNullType {
+                                    type_: Box::new(__Type::Scalar(Scalar::String(None))),
+                                })),
+                            }),
+                            description: Some(
+                                "Keys and array indexes locating the part of the document to return"
+                                    .to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        }],
+                        false => vec![],
+                    },
                     description: col.directives.description.clone(),
-                    deprecation_reason: None,
+                    deprecation_reason: col.directives.deprecated.clone(),
                     sql_type: Some(NodeSQLType::Column(Arc::clone(col))),
                 })
             })
@@ -2180,6 +3100,37 @@ impl ___Type for NodeType {
             node_id_field.push(node_id);
         };
 
+        // version field, used for optimistic concurrency control
+        let mut version_field: Vec<__Field> = vec![];
+
+        if let Some(version_source) = self.table.version_source() {
+            let is_not_null = match &version_source {
+                VersionSource::Column(col) => col.is_not_null,
+                VersionSource::Xmin => true,
+            };
+            let version_type = __Type::Scalar(Scalar::String(None));
+            let version = __Field {
+                name_: node::VERSION.to_string(),
+                type_: match is_not_null {
+                    true => __Type::NonNull(NonNullType {
+                        type_: Box::new(version_type),
+                    }),
+                    false => version_type,
+                },
+                args: vec![],
+                description: Some(
+                    "Version of the record. Pass as `expectedVersion` to guard updates and deletes against concurrent changes"
+                        .to_string(),
+                ),
+                deprecation_reason: None,
+                sql_type: Some(NodeSQLType::Version(version_source)),
+            };
+            // A column named "version" takes precedence
+            if !column_fields.iter().any(|f| f.name_ == version.name_) {
+                version_field.push(version);
+            }
+        };
+
         let sql_types = &self.schema.context.types;
         // Functions require selecting an entire row. the whole table must be selectable
         // for functions to work
@@ -2190,6 +3141,7 @@ impl ___Type for NodeType {
                 .functions
                 .iter()
                 .filter(|x| x.permissions.is_executable)
+                .filter(|x| !x.directives.omit)
                 .filter(|func| {
                     // TODO: remove in favor of making `to_sql_type` return an Option
                     // so we can optionally remove inappropriate types
@@ -2219,7 +3171,7 @@ impl ___Type for NodeType {
                                 type_: gql_ret_type,
                                 args: gql_args,
                                 description: func.directives.description.clone(),
-                                deprecation_reason: None,
+                                deprecation_reason: func.directives.deprecated.clone(),
                                 sql_type: Some(NodeSQLType::Function(Arc::clone(func))),
                             })
                         } else {
@@ -2277,7 +3229,7 @@ impl ___Type for NodeType {
                 type_,
                 args: vec![],
                 description: None,
-                deprecation_reason: None,
+                deprecation_reason: fkey.directives.deprecated.clone(),
                 sql_type: None,
             };
             relation_fields.push(relation_field);
@@ -2317,6 +3269,8 @@ impl ___Type for NodeType {
                             fkey: Arc::clone(fkey),
                             reverse_reference,
                         }),
+                        hierarchy: None,
+                        junction: None,
                         schema: Arc::clone(&self.schema),
                     };
                     let connection_args = connection_type.get_connection_input_args();
@@ -2334,7 +3288,7 @@ impl ___Type for NodeType {
                         type_,
                         args: connection_args,
                         description: None,
-                        deprecation_reason: None,
+                        deprecation_reason: fkey.directives.deprecated.clone(),
                         sql_type: None,
                     }
                 }
@@ -2357,7 +3311,7 @@ impl ___Type for NodeType {
                         type_,
                         args: vec![],
                         description: None,
-                        deprecation_reason: None,
+                        deprecation_reason: fkey.directives.deprecated.clone(),
                         sql_type: None,
                     }
                 }
@@ -2365,10 +3319,141 @@ impl ___Type for NodeType {
             relation_fields.push(relation_field);
         }
 
+        // Many-to-many relationships through junction tables
+        for junction_table in self.schema.context.tables.values().sorted_by_key(|x| x.oid) {
+            let Some((a, b)) = self
+                .schema
+                .graphql_table_junction_foreign_keys(junction_table)
+            else {
+                continue;
+            };
+
+            // Self referencing junctions produce a field for each direction
+            for (local_fkey, foreign_fkey) in [(&a, &b), (&b, &a)] {
+                if local_fkey.referenced_table_meta.oid != self.table.oid {
+                    continue;
+                }
+                let Some(foreign_table) = self
+                    .schema
+                    .context
+                    .get_table_by_oid(foreign_fkey.referenced_table_meta.oid)
+                else {
+                    continue;
+                };
+                if !self
+                    .schema
+                    .graphql_table_select_types_are_valid(foreign_table)
+                {
+                    continue;
+                }
+
+                // "tagCollection" for a junction's `tag_id` foreign key
+                let field_name = format!(
+                    "{}Collection",
+                    self.schema
+                        .graphql_foreign_key_field_name(foreign_fkey, false)
+                );
+
+                // Don't shadow a column or relationship with the same name
+                if column_fields
+                    .iter()
+                    .chain(relation_fields.iter())
+                    .any(|x| x.name_ == field_name)
+                {
+                    continue;
+                }
+
+                let connection_type = ConnectionType {
+                    table: Arc::clone(foreign_table),
+                    fkey: None,
+                    hierarchy: None,
+                    junction: Some(JunctionReference {
+                        table: Arc::clone(junction_table),
+                        local_fkey: Arc::clone(local_fkey),
+                        foreign_fkey: Arc::clone(foreign_fkey),
+                    }),
+                    schema: Arc::clone(&self.schema),
+                };
+                let connection_args = connection_type.get_connection_input_args();
+
+                relation_fields.push(__Field {
+                    name_: field_name,
+                    type_: __Type::NonNull(NonNullType {
+                        type_: Box::new(__Type::Connection(connection_type)),
+                    }),
+                    args: connection_args,
+                    description: None,
+                    deprecation_reason: None,
+                    sql_type: None,
+                });
+            }
+        }
+
+        if let Some(fkey) = self.schema.graphql_table_hierarchy_foreign_key(&self.table) {
+            let hierarchy_fields = [
+                (
+                    node::ANCESTORS,
+                    HierarchyDirection::Ancestors,
+                    false,
+                    "Records reachable by following the self referencing foreign key towards the root",
+                ),
+                (
+                    node::DESCENDANTS,
+                    HierarchyDirection::Descendants,
+                    true,
+                    "Records reachable by following the self referencing foreign key towards the leaves",
+                ),
+            ];
+
+            for (field_name, direction, reverse_reference, description) in hierarchy_fields {
+                // Don't shadow a column or relationship with the same name
+                if column_fields
+                    .iter()
+                    .chain(relation_fields.iter())
+                    .any(|x| x.name_ == field_name)
+                {
+                    continue;
+                }
+
+                let connection_type = ConnectionType {
+                    table: Arc::clone(&self.table),
+                    fkey: Some(ForeignKeyReversible {
+                        fkey: Arc::clone(&fkey),
+                        reverse_reference,
+                    }),
+                    hierarchy: Some(direction),
+                    junction: None,
+                    schema: Arc::clone(&self.schema),
+                };
+                let mut connection_args = connection_type.get_connection_input_args();
+                connection_args.push(__InputValue {
+                    name_: args::MAX_DEPTH.to_string(),
+                    type_: __Type::Scalar(Scalar::Int),
+                    description: Some(
+                        "Maximum number of foreign key hops to follow from the record".to_string(),
+                    ),
+                    default_value: None,
+                    sql_type: None,
+                });
+
+                relation_fields.push(__Field {
+                    name_: field_name.to_string(),
+                    type_: __Type::NonNull(NonNullType {
+                        type_: Box::new(__Type::Connection(connection_type)),
+                    }),
+                    args: connection_args,
+                    description: Some(description.to_string()),
+                    deprecation_reason: None,
+                    sql_type: None,
+                });
+            }
+        }
+
         Some(
             vec![
                 node_id_field,
                 column_fields,
+                version_field,
                 relation_fields,
                 function_fields,
             ]
@@ -3227,6 +4312,7 @@ impl ___Type for InsertInputType {
                 .columns
                 .iter()
                 .filter(|x| x.permissions.is_insertable)
+                .filter(|x| !x.is_omitted(OmitOperation::Insert))
                 .filter(|x| !x.is_generated)
                 .filter(|x| !x.is_serial)
                 .filter(|x| !self.schema.context.is_composite(x.type_oid))
@@ -3315,6 +4401,7 @@ impl ___Type for UpdateInputType {
                 .columns
                 .iter()
                 .filter(|x| x.permissions.is_updatable)
+                .filter(|x| !x.is_omitted(OmitOperation::Update))
                 .filter(|x| !x.is_generated)
                 .filter(|x| !x.is_serial)
                 .filter(|x| !self.schema.context.is_composite(x.type_oid))
@@ -3477,15 +4564,35 @@ pub enum FilterOp {
     GreaterThan,
     GreaterThanEqualTo,
     In,
+    NotIn,
+    Between,
     Is,
+    DistinctFrom,
+    NotDistinctFrom,
+    IEqual,
     StartsWith,
+    IStartsWith,
+    EndsWith,
     Like,
+    NotLike,
     ILike,
+    NotILike,
     RegEx,
     IRegEx,
     Contains,
     ContainedBy,
     Overlap,
+    Matches,
+    HasKey,
+    HasAnyKeys,
+    HasAllKeys,
+    JsonPath,
+    AnyEqual,
+    AnyIn,
+    AnyStartsWith,
+    AnyILike,
+    Length,
+    IsEmpty,
 }
 
 impl Display for FilterOp {
@@ -3498,15 +4605,35 @@ impl Display for FilterOp {
             Self::GreaterThan => "gt",
             Self::GreaterThanEqualTo => "gte",
             Self::In => "in",
+            Self::NotIn => "nin",
+            Self::Between => "between",
             Self::Is => "is",
+            Self::DistinctFrom => "distinctFrom",
+            Self::NotDistinctFrom => "notDistinctFrom",
+            Self::IEqual => "ieq",
             Self::StartsWith => "startsWith",
+            Self::IStartsWith => "istartsWith",
+            Self::EndsWith => "endsWith",
             Self::Like => "like",
+            Self::NotLike => "notLike",
             Self::ILike => "ilike",
+            Self::NotILike => "notIlike",
             Self::RegEx => "regex",
             Self::IRegEx => "iregex",
             Self::Contains => "contains",
             Self::ContainedBy => "containedBy",
             Self::Overlap => "overlaps",
+            Self::Matches => "matches",
+            Self::HasKey => "hasKey",
+            Self::HasAnyKeys => "hasAnyKeys",
+            Self::HasAllKeys => "hasAllKeys",
+            Self::JsonPath => "jsonPath",
+            Self::AnyEqual => "anyEq",
+            Self::AnyIn => "anyIn",
+            Self::AnyStartsWith => "anyStartsWith",
+            Self::AnyILike => "anyIlike",
+            Self::Length => "length",
+            Self::IsEmpty => "isEmpty",
         };
         write!(f, "{res}")
     }
@@ -3524,15 +4651,35 @@ impl FromStr for FilterOp {
             "gt" => Ok(Self::GreaterThan),
             "gte" => Ok(Self::GreaterThanEqualTo),
             "in" => Ok(Self::In),
+            "nin" => Ok(Self::NotIn),
+            "between" => Ok(Self::Between),
             "is" => Ok(Self::Is),
+            "distinctFrom" => Ok(Self::DistinctFrom),
+            "notDistinctFrom" => Ok(Self::NotDistinctFrom),
+            "ieq" => Ok(Self::IEqual),
             "startsWith" => Ok(Self::StartsWith),
+            "istartsWith" => Ok(Self::IStartsWith),
+            "endsWith" => Ok(Self::EndsWith),
             "like" => Ok(Self::Like),
+            "notLike" => Ok(Self::NotLike),
             "ilike" => Ok(Self::ILike),
+            "notIlike" => Ok(Self::NotILike),
             "regex" => Ok(Self::RegEx),
             "iregex" => Ok(Self::IRegEx),
             "contains" => Ok(Self::Contains),
             "containedBy" => Ok(Self::ContainedBy),
             "overlaps" => Ok(Self::Overlap),
+            "matches" => Ok(Self::Matches),
+            "hasKey" => Ok(Self::HasKey),
+            "hasAnyKeys" => Ok(Self::HasAnyKeys),
+            "hasAllKeys" => Ok(Self::HasAllKeys),
+            "jsonPath" => Ok(Self::JsonPath),
+            "anyEq" => Ok(Self::AnyEqual),
+            "anyIn" => Ok(Self::AnyIn),
+            "anyStartsWith" => Ok(Self::AnyStartsWith),
+            "anyIlike" => Ok(Self::AnyILike),
+            "length" => Ok(Self::Length),
+            "isEmpty" => Ok(Self::IsEmpty),
             other => Err(format!("Invalid filter operation: {}", other)),
         }
     }
@@ -3570,10 +4717,18 @@ impl ___Type for FilterTypeType {
                             FilterOp::Equal,
                             FilterOp::NotEqual,
                             FilterOp::In,
+                            FilterOp::NotIn,
                             FilterOp::Is,
+                            FilterOp::DistinctFrom,
+                            FilterOp::NotDistinctFrom,
                         ]
                     }
-                    Scalar::Boolean => vec![FilterOp::Equal, FilterOp::Is],
+                    Scalar::Boolean => vec![
+                        FilterOp::Equal,
+                        FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
+                    ],
                     Scalar::Int => vec![
                         FilterOp::Equal,
                         FilterOp::NotEqual,
@@ -3582,7 +4737,11 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
                     ],
                     Scalar::Float => vec![
                         FilterOp::Equal,
@@ -3592,7 +4751,11 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
                     ],
                     Scalar::String(_) => vec![
                         FilterOp::Equal,
@@ -3602,10 +4765,19 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
+                        FilterOp::IEqual,
                         FilterOp::StartsWith,
+                        FilterOp::IStartsWith,
+                        FilterOp::EndsWith,
                         FilterOp::Like,
+                        FilterOp::NotLike,
                         FilterOp::ILike,
+                        FilterOp::NotILike,
                         FilterOp::RegEx,
                         FilterOp::IRegEx,
                     ],
@@ -3617,7 +4789,11 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
                     ],
                     Scalar::Date => vec![
                         FilterOp::Equal,
@@ -3627,7 +4803,11 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
                     ],
                     Scalar::Time => vec![
                         FilterOp::Equal,
@@ -3637,7 +4817,11 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
                     ],
                     Scalar::Datetime => vec![
                         FilterOp::Equal,
@@ -3647,7 +4831,11 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
                     ],
                     Scalar::BigFloat => vec![
                         FilterOp::Equal,
@@ -3657,10 +4845,23 @@ impl ___Type for FilterTypeType {
                         FilterOp::GreaterThan,
                         FilterOp::GreaterThanEqualTo,
                         FilterOp::In,
+                        FilterOp::NotIn,
+                        FilterOp::Between,
                         FilterOp::Is,
+                        FilterOp::DistinctFrom,
+                        FilterOp::NotDistinctFrom,
                     ],
                     Scalar::Opaque => vec![FilterOp::Equal, FilterOp::Is],
-                    Scalar::JSON => vec![],   // unreachable, not in schema
+                    // json/b do not support = or <>
+                    Scalar::JSON => vec![
+                        FilterOp::Contains,
+                        FilterOp::ContainedBy,
+                        FilterOp::HasKey,
+                        FilterOp::HasAnyKeys,
+                        FilterOp::HasAllKeys,
+                        FilterOp::JsonPath,
+                        FilterOp::Is,
+                    ],
                     Scalar::Cursor => vec![], // unreachable, not in schema
                 };
 
@@ -3673,9 +4874,16 @@ impl ___Type for FilterTypeType {
                         | FilterOp::GreaterThanEqualTo
                         | FilterOp::LessThan
                         | FilterOp::LessThanEqualTo
+                        | FilterOp::DistinctFrom
+                        | FilterOp::NotDistinctFrom
+                        | FilterOp::IEqual
                         | FilterOp::StartsWith
+                        | FilterOp::IStartsWith
+                        | FilterOp::EndsWith
                         | FilterOp::Like
+                        | FilterOp::NotLike
                         | FilterOp::ILike
+                        | FilterOp::NotILike
                         | FilterOp::RegEx
                         | FilterOp::IRegEx => Some(__InputValue {
                             name_: op.to_string(),
@@ -3684,7 +4892,7 @@ impl ___Type for FilterTypeType {
                             default_value: None,
                             sql_type: None,
                         }),
-                        FilterOp::In => Some(__InputValue {
+                        FilterOp::In | FilterOp::NotIn => Some(__InputValue {
                             name_: op.to_string(),
                             type_: __Type::List(ListType {
                                 type_: Box::new(__Type::NonNull(NonNullType {
@@ -3695,18 +4903,93 @@ impl ___Type for FilterTypeType {
                             default_value: None,
                             sql_type: None,
                         }),
-                        FilterOp::Is => Some(__InputValue {
-                            name_: "is".to_string(),
-                            type_: __Type::Enum(EnumType {
-                                enum_: EnumSource::FilterIs,
-                                schema: Arc::clone(&self.schema),
+                        FilterOp::Between => Some(__InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::List(ListType {
+                                type_: Box::new(__Type::NonNull(NonNullType {
+                                    type_: Box::new(__Type::Scalar(scalar.clone())),
+                                })),
+                            }),
+                            description: Some(
+                                "Lower and upper bound, both inclusive".to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        }),
+                        FilterOp::Is => Some(__InputValue {
+                            name_: "is".to_string(),
+                            type_: __Type::Enum(EnumType {
+                                enum_: EnumSource::FilterIs,
+                                schema: Arc::clone(&self.schema),
                             }),
                             description: None,
                             default_value: None,
                             sql_type: None,
                         }),
+                        FilterOp::Contains => Some(__InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::Scalar(scalar.clone()),
+                            description: Some(
+                                "Matches documents containing the given document".to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        }),
+                        FilterOp::ContainedBy => Some(__InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::Scalar(scalar.clone()),
+                            description: Some(
+                                "Matches documents contained by the given document".to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        }),
+                        FilterOp::HasKey => Some(__InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::Scalar(Scalar::String(None)),
+                            description: Some(
+                                "Matches documents with the given top-level key or array element"
+                                    .to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        }),
+                        FilterOp::HasAnyKeys | FilterOp::HasAllKeys => Some(__InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::List(ListType {
+                                type_: Box::new(__Type::NonNull(NonNullType {
+                                    type_: Box::new(__Type::Scalar(Scalar::String(None))),
+                                })),
+                            }),
+                            description: Some(format!(
+                                "Matches documents with {} of the given top-level keys or array elements",
+                                match op {
+                                    FilterOp::HasAnyKeys => "any",
+                                    _ => "all",
+                                }
+                            )),
+                            default_value: None,
+                            sql_type: None,
+                        }),
+                        FilterOp::JsonPath => Some(__InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::Scalar(Scalar::String(None)),
+                            description: Some(
+                                "Matches documents for which the SQL/JSON path returns any item"
+                                    .to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        }),
                         // shouldn't happen since we've covered all cases in supported_ops
-                        FilterOp::Contains | FilterOp::ContainedBy | FilterOp::Overlap => None,
+                        FilterOp::Overlap
+                        | FilterOp::Matches
+                        | FilterOp::AnyEqual
+                        | FilterOp::AnyIn
+                        | FilterOp::AnyStartsWith
+                        | FilterOp::AnyILike
+                        | FilterOp::Length
+                        | FilterOp::IsEmpty => None,
                     })
                     .collect()
             }
@@ -3750,17 +5033,86 @@ impl ___Type for FilterTypeType {
                 ]
             }
             FilterableType::List(list_type) => {
-                let supported_ops = [
+                let element_type = *list_type.type_.clone();
+                let mut supported_ops = vec![
                     FilterOp::Contains,
                     FilterOp::ContainedBy,
                     FilterOp::Equal,
                     FilterOp::Overlap,
+                    FilterOp::AnyEqual,
+                    FilterOp::AnyIn,
+                    FilterOp::Length,
+                    FilterOp::IsEmpty,
                     FilterOp::Is,
                 ];
+                // Pattern matching on elements is only meaningful for text arrays
+                if matches!(element_type, __Type::Scalar(Scalar::String(_))) {
+                    supported_ops.extend([FilterOp::AnyStartsWith, FilterOp::AnyILike]);
+                }
 
                 supported_ops
                     .iter()
                     .map(|op| match op {
+                        FilterOp::AnyEqual => __InputValue {
+                            name_: op.to_string(),
+                            type_: element_type.clone(),
+                            description: Some(
+                                "Matches arrays with any element equal to the value".to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        },
+                        FilterOp::AnyStartsWith | FilterOp::AnyILike => __InputValue {
+                            name_: op.to_string(),
+                            type_: element_type.clone(),
+                            description: Some(
+                                match op {
+                                    FilterOp::AnyStartsWith => {
+                                        "Matches arrays with any element starting with the prefix"
+                                    }
+                                    _ => {
+                                        "Matches arrays with any element matching the pattern. Case Insensitive"
+                                    }
+                                }
+                                .to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        },
+                        FilterOp::AnyIn => __InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::List(ListType {
+                                type_: Box::new(__Type::NonNull(NonNullType {
+                                    type_: Box::new(element_type.clone()),
+                                })),
+                            }),
+                            description: Some(
+                                "Matches arrays with any element contained by the value list"
+                                    .to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        },
+                        FilterOp::Length => __InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::FilterType(FilterTypeType {
+                                entity: FilterableType::Scalar(Scalar::Int),
+                                schema: Arc::clone(&self.schema),
+                            }),
+                            description: Some("Compares the number of elements".to_string()),
+                            default_value: None,
+                            sql_type: None,
+                        },
+                        FilterOp::IsEmpty => __InputValue {
+                            name_: op.to_string(),
+                            type_: __Type::Scalar(Scalar::Boolean),
+                            description: Some(
+                                "Matches arrays with (true) or without (false) elements"
+                                    .to_string(),
+                            ),
+                            default_value: None,
+                            sql_type: None,
+                        },
                         FilterOp::Is => __InputValue {
                             name_: "is".to_string(),
                             type_: __Type::Enum(EnumType {
@@ -3775,7 +5127,7 @@ impl ___Type for FilterTypeType {
                             name_: op.to_string(),
                             type_: __Type::List(ListType {
                                 type_: Box::new(__Type::NonNull(NonNullType {
-                                    type_: Box::new(*list_type.type_.clone()),
+                                    type_: Box::new(element_type.clone()),
                                 })),
                             }),
                             description: None,
@@ -3785,6 +5137,31 @@ impl ___Type for FilterTypeType {
                     })
                     .collect()
             }
+            FilterableType::Search => {
+                vec![
+                    search_matches_input_value(),
+                    __InputValue {
+                        name_: "is".to_string(),
+                        type_: __Type::Enum(EnumType {
+                            enum_: EnumSource::FilterIs,
+                            schema: Arc::clone(&self.schema),
+                        }),
+                        description: None,
+                        default_value: None,
+                        sql_type: None,
+                    },
+                ]
+            }
+            FilterableType::StringSearch => {
+                let mut string_fields = FilterTypeType {
+                    entity: FilterableType::Scalar(Scalar::String(None)),
+                    schema: Arc::clone(&self.schema),
+                }
+                .input_fields()
+                .unwrap_or_default();
+                string_fields.push(search_matches_input_value());
+                string_fields
+            }
         };
 
         infields.sort_by_key(|a| a.name());
@@ -3792,6 +5169,18 @@ impl ___Type for FilterTypeType {
     }
 }
 
+fn search_matches_input_value() -> __InputValue {
+    __InputValue {
+        name_: FilterOp::Matches.to_string(),
+        type_: __Type::Scalar(Scalar::String(None)),
+        description: Some(
+            "Matches records using web search syntax via `websearch_to_tsquery`".to_string(),
+        ),
+        default_value: None,
+        sql_type: None,
+    }
+}
+
 pub(crate) const AND_FILTER_NAME: &str = "and";
 pub(crate) const OR_FILTER_NAME: &str = "or";
 pub(crate) const NOT_FILTER_NAME: &str = "not";
@@ -3822,10 +5211,10 @@ impl ___Type for FilterEntityType {
             .columns
             .iter()
             .filter(|x| x.permissions.is_selectable)
+            .filter(|x| !x.is_omitted(OmitOperation::Select))
+            .filter(|x| !x.is_omitted(OmitOperation::Filter))
             // No filtering on composites
             .filter(|x| !self.schema.context.is_composite(x.type_oid))
-            // No filtering on json/b. they do not support = or <>
-            .filter(|x| !["json", "jsonb"].contains(&x.type_name.as_ref()))
             .filter_map(|col| {
                 if let Some(utype) = sql_column_to_graphql_type(col, &self.schema) {
                     let column_graphql_name = self.schema.graphql_column_field_name(col);
@@ -3840,6 +5229,24 @@ impl ___Type for FilterEntityType {
                         not_column_exists = true;
                     }
 
+                    if col.is_searchable() {
+                        // Text columns keep their string operators alongside `matches`
+                        let entity = match col.type_name.as_str() {
+                            "tsvector" => FilterableType::Search,
+                            _ => FilterableType::StringSearch,
+                        };
+                        return Some(__InputValue {
+                            name_: column_graphql_name,
+                            type_: __Type::FilterType(FilterTypeType {
+                                entity,
+                                schema: Arc::clone(&self.schema),
+                            }),
+                            description: None,
+                            default_value: None,
+                            sql_type: Some(NodeSQLType::Column(Arc::clone(col))),
+                        });
+                    }
+
                     match utype.nullable_type() {
                         __Type::Scalar(s) => Some(__InputValue {
                             name_: column_graphql_name,
@@ -3915,6 +5322,8 @@ impl ___Type for FilterEntityType {
             });
         }
 
+        f.extend(self.relation_input_fields(&f));
+
         // If there is a column named `and` (and inflection is disabled) and
         // we were to add the `and` filter entry in this list there would be two
         // entries named `and` in the list returned by this method. Then
@@ -3987,6 +5396,107 @@ impl ___Type for FilterEntityType {
     }
 }
 
+impl FilterEntityType {
+    /// Filter fields for related tables. To-one relationships accept the related table's
+    /// filter directly while to-many relationships accept a `RelationFilter`
+    fn relation_input_fields(&self, existing: &[__InputValue]) -> Vec<__InputValue> {
+        let mut f: Vec<__InputValue> = vec![];
+
+        for (relation, foreign_table) in self.schema.graphql_table_relations(&self.table) {
+            let name_ = self
+                .schema
+                .graphql_foreign_key_field_name(&relation.fkey, relation.reverse_reference);
+
+            // Columns and other relationships take precedence on name collisions
+            if existing.iter().chain(f.iter()).any(|x| x.name_ == name_)
+                || [AND_FILTER_NAME, OR_FILTER_NAME, NOT_FILTER_NAME].contains(&name_.as_str())
+            {
+                continue;
+            }
+
+            let type_ = match self.schema.graphql_relation_is_to_many(&relation) {
+                true => __Type::RelationFilter(RelationFilterType {
+                    table: foreign_table,
+                    schema: Arc::clone(&self.schema),
+                }),
+                false => __Type::FilterEntity(FilterEntityType {
+                    table: foreign_table,
+                    schema: Arc::clone(&self.schema),
+                }),
+            };
+
+            f.push(__InputValue {
+                name_,
+                type_,
+                description: None,
+                default_value: None,
+                sql_type: Some(NodeSQLType::Relation(relation)),
+            });
+        }
+        f.into_iter()
+            .filter(|x| is_valid_graphql_name(&x.name_))
+            .collect()
+    }
+}
+
+pub(crate) const SOME_FILTER_NAME: &str = "some";
+pub(crate) const EVERY_FILTER_NAME: &str = "every";
+pub(crate) const NONE_FILTER_NAME: &str = "none";
+
+impl ___Type for RelationFilterType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::INPUT_OBJECT
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(format!(
+            "{}RelationFilter",
+            self.schema.graphql_table_base_type_name(&self.table)
+        ))
+    }
+
+    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
+        None
+    }
+
+    fn input_fields(&self) -> Option<Vec<__InputValue>> {
+        let filter_type = __Type::FilterEntity(FilterEntityType {
+            table: Arc::clone(&self.table),
+            schema: Arc::clone(&self.schema),
+        });
+
+        Some(vec![
+            __InputValue {
+                name_: SOME_FILTER_NAME.to_string(),
+                type_: filter_type.clone(),
+                description: Some(
+                    "Returns true if at least one related record matches the filter".to_string(),
+                ),
+                default_value: None,
+                sql_type: None,
+            },
+            __InputValue {
+                name_: EVERY_FILTER_NAME.to_string(),
+                type_: filter_type.clone(),
+                description: Some(
+                    "Returns true if all related records match the filter".to_string(),
+                ),
+                default_value: None,
+                sql_type: None,
+            },
+            __InputValue {
+                name_: NONE_FILTER_NAME.to_string(),
+                type_: filter_type,
+                description: Some(
+                    "Returns true if no related records match the filter".to_string(),
+                ),
+                default_value: None,
+                sql_type: None,
+            },
+        ])
+    }
+}
+
 impl ___Type for OrderByType {
     fn kind(&self) -> __TypeKind {
         __TypeKind::ENUM
@@ -4047,55 +5557,188 @@ impl ___Type for OrderByEntityType {
     }
 
     fn input_fields(&self) -> Option<Vec<__InputValue>> {
-        Some(
-            self.table
-                .columns
-                .iter()
-                .filter(|x| x.permissions.is_selectable)
-                // No ordering by arrays
-                .filter(|x| !x.type_name.ends_with("[]"))
-                // No ordering by composites
-                .filter(|x| !self.schema.context.is_composite(x.type_oid))
-                // No ordering by json/b. they do not support = or <>
-                .filter(|x| !["json", "jsonb"].contains(&x.type_name.as_ref()))
-                .map(|col| __InputValue {
-                    name_: self.schema.graphql_column_field_name(col),
-                    type_: __Type::OrderBy(OrderByType {}),
-                    description: None,
-                    default_value: None,
-                    sql_type: Some(NodeSQLType::Column(Arc::clone(col))),
-                })
-                .filter(|x| is_valid_graphql_name(&x.name_))
-                .collect(),
-        )
-    }
-}
+        let mut f: Vec<__InputValue> = self
+            .schema
+            .graphql_table_orderable_columns(&self.table)
+            .into_iter()
+            .map(|col| __InputValue {
+                name_: self.schema.graphql_column_field_name(&col),
+                type_: __Type::OrderBy(OrderByType {}),
+                description: None,
+                default_value: None,
+                sql_type: Some(NodeSQLType::Column(col)),
+            })
+            .collect();
 
-#[derive(Serialize)]
-pub struct ErrorMessage {
-    pub message: String,
-}
+        // Relevance of full text search matches
+        let rank_fields: Vec<__InputValue> = self
+            .table
+            .columns
+            .iter()
+            .filter(|x| x.permissions.is_selectable)
+            .filter(|x| !x.is_omitted(OmitOperation::Select))
+            .filter(|x| !x.is_omitted(OmitOperation::Order))
+            .filter(|x| x.is_searchable())
+            .map(|col| __InputValue {
+                name_: format!("{}Rank", self.schema.graphql_column_field_name(col)),
+                type_: __Type::SearchRankOrderBy(SearchRankOrderByType {}),
+                description: None,
+                default_value: None,
+                sql_type: Some(NodeSQLType::Column(Arc::clone(col))),
+            })
+            .filter(|x| is_valid_graphql_name(&x.name_))
+            .filter(|x| !f.iter().any(|y| y.name_ == x.name_))
+            .collect();
+        f.extend(rank_fields);
 
-use super::omit::Omit;
+        for (relation, foreign_table) in self.schema.graphql_table_relations(&self.table) {
+            // Join columns must be selectable to compute sort keys for cursors
+            let join_column_names = match relation.reverse_reference {
+                true => &relation.fkey.referenced_table_meta.column_names,
+                false => &relation.fkey.local_table_meta.column_names,
+            };
+            let join_columns_selectable = join_column_names.iter().all(|name| {
+                self.table
+                    .columns
+                    .iter()
+                    .any(|col| &col.name == name && col.permissions.is_selectable)
+            });
+            if !join_columns_selectable {
+                continue;
+            }
 
-#[derive(Serialize)]
-pub struct GraphQLResponse {
-    #[serde(skip_serializing_if = "Omit::is_omit")]
-    pub data: Omit<serde_json::Value>,
+            let relation_name = self
+                .schema
+                .graphql_foreign_key_field_name(&relation.fkey, relation.reverse_reference);
 
-    #[serde(skip_serializing_if = "Omit::is_omit")]
-    pub errors: Omit<Vec<ErrorMessage>>,
-}
+            let (name_, type_) = match self.schema.graphql_relation_is_to_many(&relation) {
+                true => (
+                    format!("{relation_name}Aggregate"),
+                    __Type::AggregateOrderBy(AggregateOrderByType {
+                        table: foreign_table,
+                        schema: Arc::clone(&self.schema),
+                    }),
+                ),
+                false => (
+                    relation_name,
+                    __Type::OrderByEntity(OrderByEntityType {
+                        table: foreign_table,
+                        schema: Arc::clone(&self.schema),
+                    }),
+                ),
+            };
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct __Schema {
-    pub context: Arc<Context>,
-}
+            // Columns take precedence on name collisions
+            if !is_valid_graphql_name(&name_) || f.iter().any(|x| x.name_ == name_) {
+                continue;
+            }
 
-#[cached(
-    type = "SizedCache<String, HashMap<String, __Type>>",
-    create = "{ SizedCache::with_size(200) }",
-    convert = r#"{ serde_json::ser::to_string(&schema.context.config).expect("schema config should be a string") }"#
+            f.push(__InputValue {
+                name_,
+                type_,
+                description: None,
+                default_value: None,
+                sql_type: Some(NodeSQLType::Relation(relation)),
+            });
+        }
+
+        Some(f)
+    }
+}
+
+impl ___Type for SearchRankOrderByType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::INPUT_OBJECT
+    }
+
+    fn name(&self) -> Option<String> {
+        Some("SearchRankOrderBy".to_string())
+    }
+
+    fn description(&self) -> Option<String> {
+        Some("Orders records by the relevance of a full text search using `ts_rank`".to_string())
+    }
+
+    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
+        None
+    }
+
+    fn input_fields(&self) -> Option<Vec<__InputValue>> {
+        Some(vec![
+            __InputValue {
+                name_: "query".to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::Scalar(Scalar::String(None))),
+                }),
+                description: Some("Search terms in web search syntax".to_string()),
+                default_value: None,
+                sql_type: None,
+            },
+            __InputValue {
+                name_: "direction".to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::OrderBy(OrderByType {})),
+                }),
+                description: None,
+                default_value: None,
+                sql_type: None,
+            },
+        ])
+    }
+}
+
+impl ___Type for AggregateOrderByType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::INPUT_OBJECT
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(format!(
+            "{}AggregateOrderBy",
+            self.schema.graphql_table_base_type_name(&self.table)
+        ))
+    }
+
+    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
+        None
+    }
+
+    fn input_fields(&self) -> Option<Vec<__InputValue>> {
+        Some(vec![__InputValue {
+            name_: aggregate::COUNT.to_string(),
+            type_: __Type::OrderBy(OrderByType {}),
+            description: Some("Order by the number of related records".to_string()),
+            default_value: None,
+            sql_type: None,
+        }])
+    }
+}
+
+#[derive(Serialize)]
+pub struct ErrorMessage {
+    pub message: String,
+}
+
+use super::omit::Omit;
+
+#[derive(Serialize)]
+pub struct GraphQLResponse {
+    #[serde(skip_serializing_if = "Omit::is_omit")]
+    pub data: Omit<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Omit::is_omit")]
+    pub errors: Omit<Vec<ErrorMessage>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct __Schema {
+    pub context: Arc<Context>,
+}
+
+#[cached(
+    type = "SizedCache<String, HashMap<String, __Type>>",
+    create = "{ SizedCache::with_size(200) }",
+    convert = r#"{ serde_json::ser::to_string(&schema.context.config).expect("schema config should be a string") }"#
 )]
 pub fn type_map(schema: &__Schema) -> HashMap<String, __Type> {
     let tmap: HashMap<String, __Type> = schema
@@ -4107,12 +5750,166 @@ pub fn type_map(schema: &__Schema) -> HashMap<String, __Type> {
     tmap
 }
 
+/// The nodeId prefix of each table with a primary key, keyed by the table's oid. A configured
+/// prefix matching or leading another table's prefix, for example from a duplicate
+/// `node_id_alias`, could not be told apart when parsing, so those tables fall back to the
+/// [schema, table] prefix
+#[cached(
+    type = "SizedCache<String, Arc<HashMap<u32, Vec<String>>>>",
+    create = "{ SizedCache::with_size(200) }",
+    convert = r#"{ serde_json::ser::to_string(&schema.context.config).expect("schema config should be a string") }"#
+)]
+pub fn node_id_prefixes(schema: &__Schema) -> Arc<HashMap<u32, Vec<String>>> {
+    let tables: Vec<&Arc<Table>> = schema
+        .context
+        .tables
+        .values()
+        .filter(|t| t.primary_key().is_some())
+        .collect();
+
+    let configured: Vec<(u32, Vec<String>)> = tables
+        .iter()
+        .map(|t| (t.oid, schema.configured_node_id_prefix(t)))
+        .collect();
+    let legacy: Vec<(u32, Vec<String>)> = tables
+        .iter()
+        .map(|t| (t.oid, vec![t.schema.clone(), t.name.clone()]))
+        .collect();
+
+    let overlaps = |a: &[String], b: &[String]| a.starts_with(b) || b.starts_with(a);
+
+    Arc::new(
+        configured
+            .iter()
+            .zip(&legacy)
+            .map(|((oid, prefix), (_, legacy_prefix))| {
+                let is_ambiguous = configured
+                    .iter()
+                    .chain(&legacy)
+                    .any(|(other_oid, other)| other_oid != oid && overlaps(prefix, other));
+                match is_ambiguous {
+                    true => (*oid, legacy_prefix.clone()),
+                    false => (*oid, prefix.clone()),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Mutation names from the `mutations` directive, keyed by table oid and operation. Names that
+/// are not valid GraphQL names, or that match another mutation's default or overridden name or a
+/// volatile function's field name, are left out so those mutations keep their default names
+#[cached(
+    type = "SizedCache<String, Arc<HashMap<(u32, MutationOperation), String>>>",
+    create = "{ SizedCache::with_size(200) }",
+    convert = r#"{ serde_json::ser::to_string(&schema.context.config).expect("schema config should be a string") }"#
+)]
+fn mutation_name_overrides(schema: &__Schema) -> Arc<HashMap<(u32, MutationOperation), String>> {
+    let mut operations: Vec<(&Arc<Table>, MutationOperation)> = vec![];
+    for table in schema.context.tables.values() {
+        if schema.graphql_table_insert_types_are_valid(table) {
+            operations.push((table, MutationOperation::Insert));
+        }
+        if schema.graphql_table_update_types_are_valid(table) {
+            operations.push((table, MutationOperation::Update));
+        }
+        if schema.graphql_table_delete_types_are_valid(table) {
+            operations.push((table, MutationOperation::Delete));
+        }
+    }
+
+    let default_names: Vec<String> = operations
+        .iter()
+        .map(|(table, operation)| schema.graphql_default_mutation_field_name(table, *operation))
+        .collect();
+
+    let function_name_to_count = Function::function_names_to_count(&schema.context.functions);
+    let function_names: HashSet<String> = schema
+        .context
+        .functions
+        .iter()
+        .filter(|func| func.is_supported(&schema.context, &function_name_to_count))
+        .filter(|func| func.volatility == FunctionVolatility::Volatile)
+        .map(|func| schema.graphql_function_field_name(func))
+        .collect();
+
+    let mut override_name_to_count: HashMap<&String, u32> = HashMap::new();
+    for (table, operation) in &operations {
+        if let Some(name) = table.mutation_name(*operation) {
+            *override_name_to_count.entry(name).or_insert(0) += 1;
+        }
+    }
+
+    Arc::new(
+        operations
+            .iter()
+            .zip(&default_names)
+            .filter_map(|((table, operation), default_name)| {
+                let name = table.mutation_name(*operation)?;
+                let collides = override_name_to_count[name] > 1
+                    || function_names.contains(name)
+                    || default_names
+                        .iter()
+                        .any(|other| other != default_name && other == name);
+                match is_valid_graphql_name(name) && !collides {
+                    true => Some(((table.oid, *operation), name.clone())),
+                    false => None,
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Names of every type other than the interfaces and unions declared by comment directives,
+/// which may not reuse them
+#[cached(
+    type = "SizedCache<String, HashSet<String>>",
+    create = "{ SizedCache::with_size(200) }",
+    convert = r#"{ serde_json::ser::to_string(&schema.context.config).expect("schema config should be a string") }"#
+)]
+fn concrete_type_names(schema: &__Schema) -> HashSet<String> {
+    schema
+        .concrete_types()
+        .into_iter()
+        .filter_map(|x| x.name())
+        .collect()
+}
+
 impl __Schema {
     // types: [__Type!]!
     pub fn types(&self) -> Vec<__Type> {
         // This is is lightweight because context is Rc
         let schema_rc = Arc::new(self.clone());
 
+        let mut types_ = self.concrete_types();
+
+        for name in self.graphql_interface_names() {
+            types_.push(__Type::Interface(InterfaceType {
+                name,
+                schema: Arc::clone(&schema_rc),
+            }));
+        }
+
+        let function_name_to_count = Function::function_names_to_count(&self.context.functions);
+        for func in self.context.functions.iter().filter(|x| {
+            x.is_supported(&self.context, &function_name_to_count)
+                && x.is_polymorphic()
+                && x.directives.union.is_some()
+        }) {
+            if let Some(return_type) = polymorphic_function_return_type(&schema_rc, func) {
+                types_.push(return_type.unmodified_type());
+            }
+        }
+
+        types_.sort_by_key(|a| a.name());
+        types_
+    }
+
+    /// Every type except the interfaces and unions declared by comment directives
+    fn concrete_types(&self) -> Vec<__Type> {
+        // This is is lightweight because context is Rc
+        let schema_rc = Arc::new(self.clone());
+
         let mut types_: Vec<__Type> = vec![
             __Type::__TypeKind(__TypeKindType),
             __Type::__Schema(__SchemaType),
@@ -4258,12 +6055,64 @@ impl __Schema {
             }),
         ];
 
+        // Full text search types are only needed when a searchable column is exposed
+        let searchable_columns: Vec<&Arc<Column>> = self
+            .context
+            .tables
+            .values()
+            .filter(|x| self.graphql_table_select_types_are_valid(x))
+            .flat_map(|x| x.columns.iter())
+            .filter(|x| {
+                x.is_searchable()
+                    && x.permissions.is_selectable
+                    && !x.is_omitted(OmitOperation::Select)
+            })
+            .collect();
+        if searchable_columns.iter().any(|x| x.type_name == "tsvector") {
+            types_.push(__Type::FilterType(FilterTypeType {
+                entity: FilterableType::Search,
+                schema: Arc::clone(&schema_rc),
+            }));
+        }
+        if searchable_columns.iter().any(|x| x.type_name != "tsvector") {
+            types_.push(__Type::FilterType(FilterTypeType {
+                entity: FilterableType::StringSearch,
+                schema: Arc::clone(&schema_rc),
+            }));
+        }
+        if !searchable_columns.is_empty() {
+            types_.push(__Type::SearchRankOrderBy(SearchRankOrderByType {}));
+        }
+
+        // `JSONFilter` is only needed when a json/b column is exposed
+        let json_exists = self
+            .context
+            .tables
+            .values()
+            .filter(|x| self.graphql_table_select_types_are_valid(x))
+            .flat_map(|x| x.columns.iter())
+            .any(|x| {
+                x.is_json()
+                    && x.permissions.is_selectable
+                    && !x.is_omitted(OmitOperation::Select)
+                    && !x.is_omitted(OmitOperation::Filter)
+            });
+        if json_exists {
+            types_.push(__Type::FilterType(FilterTypeType {
+                entity: FilterableType::Scalar(Scalar::JSON),
+                schema: Arc::clone(&schema_rc),
+            }));
+        }
+
         if self.mutations_exist() {
             types_.push(__Type::Mutation(MutationType {
                 schema: Arc::clone(&schema_rc),
             }));
         }
 
+        // `DateTruncUnit` is only needed when some table can group its aggregates
+        let mut group_by_exists = false;
+
         for table in self
             .context
             .tables
@@ -4278,14 +6127,32 @@ impl __Schema {
             }));
             types_.push(__Type::Edge(EdgeType {
                 table: Arc::clone(table),
+                hierarchy: false,
                 schema: Arc::clone(&schema_rc),
             }));
             types_.push(__Type::Connection(ConnectionType {
                 table: Arc::clone(table),
                 fkey: None,
+                hierarchy: None,
+                junction: None,
                 schema: Arc::clone(&schema_rc),
             }));
 
+            if self.graphql_table_hierarchy_foreign_key(table).is_some() {
+                types_.push(__Type::Edge(EdgeType {
+                    table: Arc::clone(table),
+                    hierarchy: true,
+                    schema: Arc::clone(&schema_rc),
+                }));
+                types_.push(__Type::Connection(ConnectionType {
+                    table: Arc::clone(table),
+                    fkey: None,
+                    hierarchy: Some(HierarchyDirection::Descendants),
+                    junction: None,
+                    schema: Arc::clone(&schema_rc),
+                }));
+            }
+
             types_.push(__Type::FilterEntity(FilterEntityType {
                 table: Arc::clone(table),
                 schema: Arc::clone(&schema_rc),
@@ -4296,6 +6163,24 @@ impl __Schema {
                 schema: Arc::clone(&schema_rc),
             }));
 
+            if !self.graphql_table_orderable_columns(table).is_empty() {
+                types_.push(__Type::Enum(EnumType {
+                    enum_: EnumSource::TableField(Arc::clone(table)),
+                    schema: Arc::clone(&schema_rc),
+                }));
+            }
+
+            if self.graphql_table_is_to_many_related(table) {
+                types_.push(__Type::RelationFilter(RelationFilterType {
+                    table: Arc::clone(table),
+                    schema: Arc::clone(&schema_rc),
+                }));
+                types_.push(__Type::AggregateOrderBy(AggregateOrderByType {
+                    table: Arc::clone(table),
+                    schema: Arc::clone(&schema_rc),
+                }));
+            }
+
             if self.graphql_table_insert_types_are_valid(table) {
                 types_.push(__Type::InsertInput(InsertInputType {
                     table: Arc::clone(table),
@@ -4335,44 +6220,45 @@ impl __Schema {
                         table: Arc::clone(table),
                         schema: Arc::clone(&schema_rc),
                     }));
-                    // Check if there are any columns aggregatable by sum/avg
-                    if table
-                        .columns
-                        .iter()
-                        .any(|c| is_aggregatable(c, &AggregateOperation::Sum))
-                    {
-                        types_.push(__Type::AggregateNumeric(AggregateNumericType {
+                    if !self.graphql_table_orderable_columns(table).is_empty() {
+                        types_.push(__Type::AggregateGroup(AggregateGroupType {
                             table: Arc::clone(table),
                             schema: Arc::clone(&schema_rc),
-                            aggregate_op: AggregateOperation::Sum,
                         }));
-                        types_.push(__Type::AggregateNumeric(AggregateNumericType {
+                        types_.push(__Type::AggregateGroupKey(AggregateGroupKeyType {
                             table: Arc::clone(table),
                             schema: Arc::clone(&schema_rc),
-                            aggregate_op: AggregateOperation::Avg,
                         }));
-                    }
-                    // Check if there are any columns aggregatable by min/max
-                    if table
-                        .columns
-                        .iter()
-                        .any(|c| is_aggregatable(c, &AggregateOperation::Min))
-                    {
-                        types_.push(__Type::AggregateNumeric(AggregateNumericType {
+                        types_.push(__Type::GroupByKey(GroupByKeyType {
                             table: Arc::clone(table),
                             schema: Arc::clone(&schema_rc),
-                            aggregate_op: AggregateOperation::Min,
                         }));
-                        types_.push(__Type::AggregateNumeric(AggregateNumericType {
+                        types_.push(__Type::AggregateGroupOrderBy(AggregateGroupOrderByType {
                             table: Arc::clone(table),
                             schema: Arc::clone(&schema_rc),
-                            aggregate_op: AggregateOperation::Max,
                         }));
+                        group_by_exists = true;
+                    }
+                    for op in AggregateOperation::ALL {
+                        if table.columns.iter().any(|c| is_aggregatable(c, &op)) {
+                            types_.push(__Type::AggregateNumeric(AggregateNumericType {
+                                table: Arc::clone(table),
+                                schema: Arc::clone(&schema_rc),
+                                aggregate_op: op,
+                            }));
+                        }
                     }
                 }
             }
         }
 
+        if group_by_exists {
+            types_.push(__Type::Enum(EnumType {
+                enum_: EnumSource::DateTruncUnit,
+                schema: Arc::clone(&schema_rc),
+            }));
+        }
+
         for (_, enum_) in self
             .context
             .enums
@@ -4394,7 +6280,27 @@ impl __Schema {
             types_.push(enum_filter);
         }
 
-        types_.sort_by_key(|a| a.name());
+        for table in self
+            .context
+            .tables
+            .values()
+            .filter(|x| self.graphql_table_enum_types_are_valid(x))
+            .sorted_by_key(|x| x.oid)
+        {
+            let enum_type = EnumType {
+                enum_: EnumSource::Table(Arc::clone(table)),
+                schema: Arc::clone(&schema_rc),
+            };
+
+            let enum_filter = __Type::FilterType(FilterTypeType {
+                entity: FilterableType::Enum(enum_type.clone()),
+                schema: Arc::clone(&schema_rc),
+            });
+
+            types_.push(__Type::Enum(enum_type));
+            types_.push(enum_filter);
+        }
+
         types_
     }
 
@@ -4498,6 +6404,26 @@ impl __Schema {
                 }],
                 is_repeatable: false,
             },
+            __Directive {
+                name: "deprecated".to_string(),
+                description: Some(
+                    "Marks an element of a GraphQL schema as no longer supported.".to_string(),
+                ),
+                locations: vec![
+                    __DirectiveLocation::FieldDefinition,
+                    __DirectiveLocation::ArgumentDefinition,
+                    __DirectiveLocation::InputFieldDefinition,
+                    __DirectiveLocation::EnumValue,
+                ],
+                args: vec![__InputValue {
+                    name_: "reason".to_string(),
+                    type_: __Type::Scalar(Scalar::String(None)),
+                    description: Some("Explains why the element is deprecated".to_string()),
+                    default_value: Some("\"No longer supported\"".to_string()),
+                    sql_type: None,
+                }],
+                is_repeatable: false,
+            },
         ]
     }
 }
@@ -4516,15 +6442,129 @@ pub struct AggregateNumericType {
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub enum AggregateOperation {
-    Sum,
-    Avg,
-    Min,
+pub struct AggregateGroupType {
+    pub table: Arc<Table>,
+    pub schema: Arc<__Schema>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AggregateGroupKeyType {
+    pub table: Arc<Table>,
+    pub schema: Arc<__Schema>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GroupByKeyType {
+    pub table: Arc<Table>,
+    pub schema: Arc<__Schema>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AggregateGroupOrderByType {
+    pub table: Arc<Table>,
+    pub schema: Arc<__Schema>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AggregateOperation {
+    Sum,
+    Avg,
+    Min,
     Max,
+    CountDistinct,
+    Stddev,
+    Variance,
+    BoolAnd,
+    BoolOr,
+    ArrayAgg,
+    PercentileCont,
     // Count is handled directly in AggregateType
 }
 
 impl AggregateOperation {
+    /// Every per-column operation, in the order its field appears on `<Table>Aggregate`
+    pub const ALL: [AggregateOperation; 11] = [
+        AggregateOperation::Sum,
+        AggregateOperation::Avg,
+        AggregateOperation::Min,
+        AggregateOperation::Max,
+        AggregateOperation::CountDistinct,
+        AggregateOperation::Stddev,
+        AggregateOperation::Variance,
+        AggregateOperation::BoolAnd,
+        AggregateOperation::BoolOr,
+        AggregateOperation::ArrayAgg,
+        AggregateOperation::PercentileCont,
+    ];
+
+    // Name of the field exposing the operation on `<Table>Aggregate`
+    fn field_name(&self) -> &str {
+        match self {
+            AggregateOperation::Sum => aggregate::SUM,
+            AggregateOperation::Avg => aggregate::AVG,
+            AggregateOperation::Min => aggregate::MIN,
+            AggregateOperation::Max => aggregate::MAX,
+            AggregateOperation::CountDistinct => aggregate::COUNT_DISTINCT,
+            AggregateOperation::Stddev => aggregate::STDDEV,
+            AggregateOperation::Variance => aggregate::VARIANCE,
+            AggregateOperation::BoolAnd => aggregate::BOOL_AND,
+            AggregateOperation::BoolOr => aggregate::BOOL_OR,
+            AggregateOperation::ArrayAgg => aggregate::ARRAY_AGG,
+            AggregateOperation::PercentileCont => aggregate::PERCENTILE_CONT,
+        }
+    }
+
+    // Description of the field exposing the operation on `<Table>Aggregate`
+    fn field_description(&self) -> &str {
+        match self {
+            AggregateOperation::Sum => "Summation aggregates for numeric fields",
+            AggregateOperation::Avg => "Average aggregates for numeric fields",
+            AggregateOperation::Min => "Minimum aggregates for comparable fields",
+            AggregateOperation::Max => "Maximum aggregates for comparable fields",
+            AggregateOperation::CountDistinct => "Number of distinct non-null values per field",
+            AggregateOperation::Stddev => "Sample standard deviation aggregates for numeric fields",
+            AggregateOperation::Variance => "Sample variance aggregates for numeric fields",
+            AggregateOperation::BoolAnd => {
+                "True when every non-null value of a boolean field is true"
+            }
+            AggregateOperation::BoolOr => "True when any non-null value of a boolean field is true",
+            AggregateOperation::ArrayAgg => "Values of scalar fields collected into ordered lists",
+            AggregateOperation::PercentileCont => {
+                "Interpolated percentile aggregates for numeric fields"
+            }
+        }
+    }
+
+    // Arguments accepted by the field exposing the operation on `<Table>Aggregate`
+    fn field_args(&self, table: &Arc<Table>, schema: &Arc<__Schema>) -> Vec<__InputValue> {
+        match self {
+            AggregateOperation::ArrayAgg => vec![__InputValue {
+                name_: args::ORDER_BY.to_string(),
+                type_: __Type::List(ListType {
+                    type_: Box::new(__Type::NonNull(NonNullType {
+                        type_: Box::new(__Type::OrderByEntity(OrderByEntityType {
+                            table: Arc::clone(table),
+                            schema: Arc::clone(schema),
+                        })),
+                    })),
+                }),
+                description: Some("Sort order of the values within each list".to_string()),
+                default_value: None,
+                sql_type: None,
+            }],
+            AggregateOperation::PercentileCont => vec![__InputValue {
+                name_: args::FRACTION.to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::Scalar(Scalar::Float)),
+                }),
+                description: Some("The percentile to compute, between 0 and 1".to_string()),
+                default_value: None,
+                sql_type: None,
+            }],
+            _ => vec![],
+        }
+    }
+
     // Helper for descriptive terms used in descriptions
     fn descriptive_term(&self) -> &str {
         match self {
@@ -4532,6 +6572,13 @@ impl AggregateOperation {
             AggregateOperation::Avg => "average",
             AggregateOperation::Min => "minimum",
             AggregateOperation::Max => "maximum",
+            AggregateOperation::CountDistinct => "distinct count",
+            AggregateOperation::Stddev => "standard deviation",
+            AggregateOperation::Variance => "variance",
+            AggregateOperation::BoolAnd => "boolean and",
+            AggregateOperation::BoolOr => "boolean or",
+            AggregateOperation::ArrayAgg => "array",
+            AggregateOperation::PercentileCont => "continuous percentile",
         }
     }
 
@@ -4542,6 +6589,13 @@ impl AggregateOperation {
             AggregateOperation::Avg => "Average",
             AggregateOperation::Min => "Minimum",
             AggregateOperation::Max => "Maximum",
+            AggregateOperation::CountDistinct => "Distinct count",
+            AggregateOperation::Stddev => "Sample standard deviation",
+            AggregateOperation::Variance => "Sample variance",
+            AggregateOperation::BoolAnd => "Boolean and",
+            AggregateOperation::BoolOr => "Boolean or",
+            AggregateOperation::ArrayAgg => "List",
+            AggregateOperation::PercentileCont => "Continuous percentile",
         }
     }
 }
@@ -4553,6 +6607,13 @@ impl std::fmt::Display for AggregateOperation {
             AggregateOperation::Avg => write!(f, "Avg"), // GraphQL schema uses "Avg" for the type name part
             AggregateOperation::Min => write!(f, "Min"),
             AggregateOperation::Max => write!(f, "Max"),
+            AggregateOperation::CountDistinct => write!(f, "CountDistinct"),
+            AggregateOperation::Stddev => write!(f, "Stddev"),
+            AggregateOperation::Variance => write!(f, "Variance"),
+            AggregateOperation::BoolAnd => write!(f, "BoolAnd"),
+            AggregateOperation::BoolOr => write!(f, "BoolOr"),
+            AggregateOperation::ArrayAgg => write!(f, "ArrayAgg"),
+            AggregateOperation::PercentileCont => write!(f, "PercentileCont"),
         }
     }
 }
@@ -4563,11 +6624,19 @@ fn is_aggregatable(column: &Column, op: &AggregateOperation) -> bool {
         return false;
     };
 
+    if column.is_omitted(OmitOperation::Select) {
+        return false;
+    }
+
     // Removed duplicated closures, will use helper functions below
 
     match op {
         // Sum/Avg only make sense for numeric types
-        AggregateOperation::Sum | AggregateOperation::Avg => {
+        AggregateOperation::Sum
+        | AggregateOperation::Avg
+        | AggregateOperation::Stddev
+        | AggregateOperation::Variance
+        | AggregateOperation::PercentileCont => {
             // Check category first for arrays/enums, then check name for base types
             match type_.category {
                 TypeCategory::Other => is_pg_numeric_type(&type_.name),
@@ -4586,6 +6655,21 @@ fn is_aggregatable(column: &Column, op: &AggregateOperation) -> bool {
                 _ => false, // Don't allow min/max on composites, arrays, tables, pseudo
             }
         }
+        // Distinct counts and lists only need equality, so uuids are allowed too
+        AggregateOperation::CountDistinct | AggregateOperation::ArrayAgg => match type_.category {
+            TypeCategory::Other => {
+                is_pg_numeric_type(&type_.name)
+                    || is_pg_string_type(&type_.name)
+                    || is_pg_datetime_type(&type_.name)
+                    || is_pg_boolean_type(&type_.name)
+                    || is_pg_uuid_type(&type_.name)
+            }
+            _ => false,
+        },
+        AggregateOperation::BoolAnd | AggregateOperation::BoolOr => match type_.category {
+            TypeCategory::Other => is_pg_boolean_type(&type_.name),
+            _ => false,
+        },
     }
 }
 
@@ -4606,14 +6690,32 @@ fn aggregate_result_type(column: &Column, op: &AggregateOperation) -> Option<Sca
                 None
             }
         }
-        AggregateOperation::Avg => {
+        AggregateOperation::Avg | AggregateOperation::Stddev | AggregateOperation::Variance => {
             if is_pg_numeric_type(&type_.name) {
                 Some(Scalar::BigFloat)
             } else {
                 None
             }
         }
-        AggregateOperation::Min | AggregateOperation::Max => {
+        // percentile_cont interpolates in double precision
+        AggregateOperation::PercentileCont => {
+            if is_pg_numeric_type(&type_.name) {
+                Some(Scalar::Float)
+            } else {
+                None
+            }
+        }
+        AggregateOperation::CountDistinct => Some(Scalar::Int),
+        AggregateOperation::BoolAnd | AggregateOperation::BoolOr => {
+            if is_pg_boolean_type(&type_.name) {
+                Some(Scalar::Boolean)
+            } else {
+                None
+            }
+        }
+        // Element type of the list
+        AggregateOperation::ArrayAgg if is_pg_uuid_type(&type_.name) => Some(Scalar::UUID),
+        AggregateOperation::Min | AggregateOperation::Max | AggregateOperation::ArrayAgg => {
             if is_pg_numeric_type(&type_.name) {
                 sql_type_to_scalar(&type_.name, column.max_characters)
             } else if is_pg_string_type(&type_.name) {
@@ -4629,6 +6731,42 @@ fn aggregate_result_type(column: &Column, op: &AggregateOperation) -> Option<Sca
     }
 }
 
+/// Aggregate fields shared by `<Table>Aggregate` and its `<Table>AggregateGroup` buckets
+fn aggregate_fields(table: &Arc<Table>, schema: &Arc<__Schema>) -> Vec<__Field> {
+    let mut fields = Vec::new();
+
+    // Count field (always present)
+    fields.push(__Field {
+        name_: aggregate::COUNT.to_string(),
+        type_: __Type::NonNull(NonNullType {
+            type_: Box::new(__Type::Scalar(Scalar::Int)),
+        }),
+        args: vec![],
+        description: Some("The number of records matching the query".to_string()),
+        deprecation_reason: None,
+        sql_type: None,
+    });
+
+    // Add a field per operation that has at least one aggregatable column
+    for op in AggregateOperation::ALL {
+        if table.columns.iter().any(|c| is_aggregatable(c, &op)) {
+            fields.push(__Field {
+                name_: op.field_name().to_string(),
+                args: op.field_args(table, schema),
+                description: Some(op.field_description().to_string()),
+                type_: __Type::AggregateNumeric(AggregateNumericType {
+                    table: Arc::clone(table),
+                    schema: Arc::clone(schema),
+                    aggregate_op: op,
+                }),
+                deprecation_reason: None,
+                sql_type: None,
+            });
+        }
+    }
+    fields
+}
+
 impl ___Type for AggregateType {
     fn kind(&self) -> __TypeKind {
         __TypeKind::OBJECT
@@ -4645,89 +6783,253 @@ impl ___Type for AggregateType {
     }
 
     fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
-        let mut fields = Vec::new();
+        let mut fields = aggregate_fields(&self.table, &self.schema);
 
-        // Count field (always present)
-        fields.push(__Field {
-            name_: aggregate::COUNT.to_string(),
-            type_: __Type::NonNull(NonNullType {
-                type_: Box::new(__Type::Scalar(Scalar::Int)),
-            }),
-            args: vec![],
-            description: Some("The number of records matching the query".to_string()),
-            deprecation_reason: None,
-            sql_type: None,
-        });
-
-        // Add fields for Sum, Avg, Min, Max if there are any aggregatable columns
-        let has_sum_avgable = self
-            .table
-            .columns
-            .iter()
-            .any(|c| is_aggregatable(c, &AggregateOperation::Sum));
-        let has_min_maxable = self
-            .table
-            .columns
-            .iter()
-            .any(|c| is_aggregatable(c, &AggregateOperation::Min));
-
-        if has_sum_avgable {
-            fields.push(__Field {
-                name_: aggregate::SUM.to_string(),
-                type_: __Type::AggregateNumeric(AggregateNumericType {
-                    table: Arc::clone(&self.table),
-                    schema: Arc::clone(&self.schema),
-                    aggregate_op: AggregateOperation::Sum,
-                }),
-                args: vec![],
-                description: Some("Summation aggregates for numeric fields".to_string()),
-                deprecation_reason: None,
-                sql_type: None,
-            });
+        if !self
+            .schema
+            .graphql_table_orderable_columns(&self.table)
+            .is_empty()
+        {
             fields.push(__Field {
-                name_: aggregate::AVG.to_string(),
-                type_: __Type::AggregateNumeric(AggregateNumericType {
-                    table: Arc::clone(&self.table),
-                    schema: Arc::clone(&self.schema),
-                    aggregate_op: AggregateOperation::Avg,
+                name_: aggregate::GROUP_BY.to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::List(ListType {
+                        type_: Box::new(__Type::NonNull(NonNullType {
+                            type_: Box::new(__Type::AggregateGroup(AggregateGroupType {
+                                table: Arc::clone(&self.table),
+                                schema: Arc::clone(&self.schema),
+                            })),
+                        })),
+                    })),
                 }),
-                args: vec![],
-                description: Some("Average aggregates for numeric fields".to_string()),
+                args: vec![
+                    __InputValue {
+                        name_: args::KEYS.to_string(),
+                        type_: __Type::NonNull(NonNullType {
+                            type_: Box::new(__Type::List(ListType {
+                                type_: Box::new(__Type::NonNull(NonNullType {
+                                    type_: Box::new(__Type::GroupByKey(GroupByKeyType {
+                                        table: Arc::clone(&self.table),
+                                        schema: Arc::clone(&self.schema),
+                                    })),
+                                })),
+                            })),
+                        }),
+                        description: Some("Fields to group records by".to_string()),
+                        default_value: None,
+                        sql_type: None,
+                    },
+                    __InputValue {
+                        name_: pagination::FIRST.to_string(),
+                        type_: __Type::Scalar(Scalar::Int),
+                        description: Some("Query the first `n` groups".to_string()),
+                        default_value: None,
+                        sql_type: None,
+                    },
+                    __InputValue {
+                        name_: pagination::AFTER.to_string(),
+                        type_: __Type::Scalar(Scalar::Cursor),
+                        description: Some("Query groups after the provided cursor".to_string()),
+                        default_value: None,
+                        sql_type: None,
+                    },
+                    __InputValue {
+                        name_: args::ORDER_BY.to_string(),
+                        type_: __Type::List(ListType {
+                            type_: Box::new(__Type::NonNull(NonNullType {
+                                type_: Box::new(__Type::AggregateGroupOrderBy(
+                                    AggregateGroupOrderByType {
+                                        table: Arc::clone(&self.table),
+                                        schema: Arc::clone(&self.schema),
+                                    },
+                                )),
+                            })),
+                        }),
+                        description: Some("Sort order to apply to the groups".to_string()),
+                        default_value: None,
+                        sql_type: None,
+                    },
+                ],
+                description: Some(
+                    "Aggregates calculated for each distinct combination of the grouping keys"
+                        .to_string(),
+                ),
                 deprecation_reason: None,
                 sql_type: None,
             });
         }
+        Some(fields)
+    }
+}
 
-        if has_min_maxable {
-            fields.push(__Field {
-                name_: aggregate::MIN.to_string(),
-                type_: __Type::AggregateNumeric(AggregateNumericType {
-                    table: Arc::clone(&self.table),
-                    schema: Arc::clone(&self.schema),
-                    aggregate_op: AggregateOperation::Min,
+impl ___Type for AggregateGroupType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::OBJECT
+    }
+
+    fn name(&self) -> Option<String> {
+        let table_base_type_name = &self.schema.graphql_table_base_type_name(&self.table);
+        Some(format!("{table_base_type_name}AggregateGroup"))
+    }
+
+    fn description(&self) -> Option<String> {
+        let table_base_type_name = &self.schema.graphql_table_base_type_name(&self.table);
+        Some(format!(
+            "Aggregate results for a group of `{table_base_type_name}` records"
+        ))
+    }
+
+    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
+        let mut fields = vec![
+            __Field {
+                name_: connection::CURSOR.to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::Scalar(Scalar::String(None))),
                 }),
                 args: vec![],
-                description: Some("Minimum aggregates for comparable fields".to_string()),
+                description: None,
                 deprecation_reason: None,
                 sql_type: None,
-            });
-            fields.push(__Field {
-                name_: aggregate::MAX.to_string(),
-                type_: __Type::AggregateNumeric(AggregateNumericType {
-                    table: Arc::clone(&self.table),
-                    schema: Arc::clone(&self.schema),
-                    aggregate_op: AggregateOperation::Max,
+            },
+            __Field {
+                name_: aggregate::KEY.to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::AggregateGroupKey(AggregateGroupKeyType {
+                        table: Arc::clone(&self.table),
+                        schema: Arc::clone(&self.schema),
+                    })),
                 }),
                 args: vec![],
-                description: Some("Maximum aggregates for comparable fields".to_string()),
+                description: Some("Values of the grouping keys".to_string()),
                 deprecation_reason: None,
                 sql_type: None,
-            });
-        }
+            },
+        ];
+        fields.extend(aggregate_fields(&self.table, &self.schema));
         Some(fields)
     }
 }
 
+impl ___Type for AggregateGroupKeyType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::OBJECT
+    }
+
+    fn name(&self) -> Option<String> {
+        let table_base_type_name = &self.schema.graphql_table_base_type_name(&self.table);
+        Some(format!("{table_base_type_name}AggregateGroupKey"))
+    }
+
+    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
+        Some(
+            self.schema
+                .graphql_table_orderable_columns(&self.table)
+                .into_iter()
+                .filter_map(|col| {
+                    // Groups may be keyed by null values
+                    let type_ = sql_column_to_graphql_type(&col, &self.schema)?.nullable_type();
+                    Some(__Field {
+                        name_: self.schema.graphql_column_field_name(&col),
+                        type_,
+                        args: vec![],
+                        description: None,
+                        deprecation_reason: None,
+                        sql_type: Some(NodeSQLType::Column(col)),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ___Type for GroupByKeyType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::INPUT_OBJECT
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(format!(
+            "{}GroupByKey",
+            self.schema.graphql_table_base_type_name(&self.table)
+        ))
+    }
+
+    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
+        None
+    }
+
+    fn input_fields(&self) -> Option<Vec<__InputValue>> {
+        Some(vec![
+            __InputValue {
+                name_: aggregate::FIELD.to_string(),
+                type_: __Type::NonNull(NonNullType {
+                    type_: Box::new(__Type::Enum(EnumType {
+                        enum_: EnumSource::TableField(Arc::clone(&self.table)),
+                        schema: Arc::clone(&self.schema),
+                    })),
+                }),
+                description: Some("Field to group records by".to_string()),
+                default_value: None,
+                sql_type: None,
+            },
+            __InputValue {
+                name_: aggregate::TRUNCATE.to_string(),
+                type_: __Type::Enum(EnumType {
+                    enum_: EnumSource::DateTruncUnit,
+                    schema: Arc::clone(&self.schema),
+                }),
+                description: Some(
+                    "Truncate date and datetime values to the given precision before grouping"
+                        .to_string(),
+                ),
+                default_value: None,
+                sql_type: None,
+            },
+        ])
+    }
+}
+
+impl ___Type for AggregateGroupOrderByType {
+    fn kind(&self) -> __TypeKind {
+        __TypeKind::INPUT_OBJECT
+    }
+
+    fn name(&self) -> Option<String> {
+        Some(format!(
+            "{}AggregateGroupOrderBy",
+            self.schema.graphql_table_base_type_name(&self.table)
+        ))
+    }
+
+    fn fields(&self, _include_deprecated: bool) -> Option<Vec<__Field>> {
+        None
+    }
+
+    fn input_fields(&self) -> Option<Vec<__InputValue>> {
+        let mut f = vec![__InputValue {
+            name_: aggregate::COUNT.to_string(),
+            type_: __Type::OrderBy(OrderByType {}),
+            description: Some("Order by the number of records in the group".to_string()),
+            default_value: None,
+            sql_type: None,
+        }];
+        f.extend(
+            self.schema
+                .graphql_table_orderable_columns(&self.table)
+                .into_iter()
+                .map(|col| __InputValue {
+                    name_: self.schema.graphql_column_field_name(&col),
+                    type_: __Type::OrderBy(OrderByType {}),
+                    description: None,
+                    default_value: None,
+                    sql_type: Some(NodeSQLType::Column(col)),
+                })
+                .filter(|x| x.name_ != aggregate::COUNT),
+        );
+        Some(f)
+    }
+}
+
 impl ___Type for AggregateNumericType {
     fn kind(&self) -> __TypeKind {
         __TypeKind::OBJECT
@@ -4758,9 +7060,18 @@ impl ___Type for AggregateNumericType {
                 && let Some(scalar_type) = aggregate_result_type(col, &self.aggregate_op)
             {
                 let field_name = self.schema.graphql_column_field_name(col);
+                let type_ = match self.aggregate_op {
+                    AggregateOperation::CountDistinct => __Type::NonNull(NonNullType {
+                        type_: Box::new(__Type::Scalar(scalar_type)),
+                    }),
+                    AggregateOperation::ArrayAgg => __Type::List(ListType {
+                        type_: Box::new(__Type::Scalar(scalar_type)),
+                    }),
+                    _ => __Type::Scalar(scalar_type),
+                };
                 fields.push(__Field {
                     name_: field_name.clone(),
-                    type_: __Type::Scalar(scalar_type),
+                    type_,
                     args: vec![],
                     description: Some(format!(
                         "{} of {} across all matching records",
@@ -4826,6 +7137,10 @@ fn is_pg_boolean_type(name: &str) -> bool {
     matches!(name, "bool")
 }
 
+fn is_pg_uuid_type(name: &str) -> bool {
+    matches!(name, "uuid")
+}
+
 fn is_pg_small_integer_type(name: &str) -> bool {
     matches!(name, "int2" | "int4" | "int8")
 }
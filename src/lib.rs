@@ -10,10 +10,12 @@ mod constants;
 mod error;
 mod graphql;
 mod gson;
+mod lint;
 mod merge;
 mod omit;
 mod parser_util;
 mod resolve;
+mod signing;
 mod sql_types;
 mod transpile;
 
@@ -22,8 +24,15 @@ pg_module_magic!();
 extension_sql_file!("../sql/schema_version.sql");
 extension_sql_file!("../sql/directives.sql");
 extension_sql_file!("../sql/raise_exception.sql");
+extension_sql_file!("../sql/estimate_row_count.sql");
+extension_sql_file!("../sql/enum_table_values.sql");
 extension_sql_file!("../sql/resolve.sql", requires = [resolve]);
 
+#[pg_guard]
+pub extern "C-unwind" fn _PG_init() {
+    signing::init();
+}
+
 #[allow(non_snake_case, unused_variables)]
 #[pg_extern(name = "_internal_resolve")]
 fn resolve(
@@ -73,6 +82,23 @@ fn resolve(
     pgrx::JsonB(value)
 }
 
+#[pg_extern(name = "lint")]
+fn lint() -> TableIterator<
+    'static,
+    (
+        name!(entity_type, String),
+        name!(entity, String),
+        name!(reason, String),
+        name!(suggestion, String),
+    ),
+> {
+    TableIterator::new(
+        lint::lint()
+            .into_iter()
+            .map(|x| (x.entity_type, x.entity, x.reason, x.suggestion)),
+    )
+}
+
 #[cfg(any(test, feature = "pg_test"))]
 #[pg_schema]
 mod tests {}
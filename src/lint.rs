@@ -0,0 +1,674 @@
+use crate::constants::node;
+use crate::graphql::*;
+use crate::sql_types::*;
+use crate::transpile::quote_ident;
+use itertools::Itertools;
+use pgrx::JsonB;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// An entity left out of, or degraded in, the GraphQL schema
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct LintIssue {
+    pub entity_type: String,
+    pub entity: String,
+    pub reason: String,
+    pub suggestion: String,
+}
+
+impl LintIssue {
+    fn new(
+        entity_type: &str,
+        entity: String,
+        reason: String,
+        suggestion: impl Into<String>,
+    ) -> Self {
+        Self {
+            entity_type: entity_type.to_string(),
+            entity,
+            reason,
+            suggestion: suggestion.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct LintComment {
+    entity_type: String,
+    entity: String,
+    comment: String,
+}
+
+pub fn lint() -> Vec<LintIssue> {
+    let (mut issues, is_loadable) = lint_comment_directives();
+
+    // A directive that is not valid JSON fails the schema load, so it must be fixed first
+    if is_loadable {
+        let config = load_sql_config();
+        match load_sql_context(&config) {
+            Ok(context) => issues.extend(lint_schema(&__Schema { context })),
+            Err(err) => issues.push(LintIssue::new(
+                "schema",
+                config.search_path.join(", "),
+                err.to_string(),
+                "fix the comment directives of the schemas on the search path",
+            )),
+        }
+    }
+
+    issues.sort();
+    issues
+}
+
+/// Comments mentioning `@graphql` that `graphql.comment_directive` can not read.
+/// Also returns whether the schema can be loaded with these comments
+fn lint_comment_directives() -> (Vec<LintIssue>, bool) {
+    let query = include_str!("../sql/load_lint_comments.sql");
+    let sql_result: serde_json::Value = get_one_readonly::<JsonB>(query)
+        .expect("failed to read comments")
+        .expect("comments are missing")
+        .0;
+    let comments: Vec<LintComment> =
+        serde_json::from_value(sql_result).expect("failed to convert comments from json");
+
+    let mut issues = vec![];
+    let mut is_loadable = true;
+
+    for comment in comments {
+        // Matches the `@graphql\((.+)\)` pattern of `graphql.comment_directive`
+        let directive = comment.comment.find("@graphql(").and_then(|start| {
+            let start = start + "@graphql(".len();
+            let end = comment.comment.rfind(')')?;
+            (end > start).then(|| &comment.comment[start..end])
+        });
+
+        let entity_type = match comment.entity_type.strip_suffix(" column") {
+            Some(_) => "column",
+            None => comment.entity_type.as_str(),
+        };
+
+        let (reason, suggestion) = match directive {
+            None => (
+                "comment mentions @graphql without a @graphql(...) directive, so it is ignored"
+                    .to_string(),
+                r#"wrap the directive's JSON in @graphql(...) e.g. @graphql({"name": "Example"})"#,
+            ),
+            Some(directive) => match serde_json::from_str::<serde_json::Value>(directive) {
+                Ok(serde_json::Value::Object(_)) => continue,
+                Ok(_) => (
+                    "comment directive is not a JSON object, so it is ignored".to_string(),
+                    r#"use a JSON object e.g. @graphql({"name": "Example"})"#,
+                ),
+                Err(err) => {
+                    is_loadable = false;
+                    (
+                        format!("comment directive is not valid JSON: {err}"),
+                        "fix the JSON between @graphql( and ). Other checks are skipped until the directive is valid",
+                    )
+                }
+            },
+        };
+        issues.push(LintIssue::new(
+            entity_type,
+            comment.entity,
+            reason,
+            suggestion,
+        ));
+    }
+
+    (issues, is_loadable)
+}
+
+fn table_entity_type(table: &Table) -> &'static str {
+    match table.relkind.as_str() {
+        "v" => "view",
+        "m" => "materialized view",
+        "f" => "foreign table",
+        _ => "table",
+    }
+}
+
+fn table_entity(schema: &str, name: &str) -> String {
+    format!("{}.{}", quote_ident(schema), quote_ident(name))
+}
+
+pub fn lint_schema(schema: &__Schema) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let role = &schema.context.config.role;
+
+    let tables: Vec<&Arc<Table>> = schema
+        .context
+        .tables
+        .values()
+        // Lookup tables with the enum directive are exposed as enums
+        .filter(|x| !schema.graphql_table_enum_types_are_valid(x))
+        .sorted_by_key(|x| x.oid)
+        .collect();
+
+    for table in &tables {
+        let entity_type = table_entity_type(table);
+        let entity = table_entity(&table.schema, &table.name);
+        let type_name = schema.graphql_table_base_type_name(table);
+
+        if table.directives.omit {
+            issues.push(LintIssue::new(
+                entity_type,
+                entity,
+                "omitted by the omit comment directive".to_string(),
+                "remove omit from the comment directive to expose it",
+            ));
+            continue;
+        }
+        if let Some(invalid_value) = table
+            .enum_values
+            .iter()
+            .flatten()
+            .find(|x| !is_valid_graphql_enum_value_name(&x.value))
+        {
+            issues.push(LintIssue::new(
+                entity_type,
+                entity.clone(),
+                format!(
+                    "key {} is not a valid GraphQL enum value, so the enum directive is ignored",
+                    invalid_value.value
+                ),
+                "use keys that are valid GraphQL names other than true, false and null",
+            ));
+        }
+        if !table.is_any_column_selectable() {
+            issues.push(LintIssue::new(
+                entity_type,
+                entity.clone(),
+                format!("role {role} can not select any of its columns"),
+                format!("grant select on {entity} to {}", quote_ident(role)),
+            ));
+        }
+        if table.primary_key().is_none() {
+            issues.push(LintIssue::new(
+                entity_type,
+                entity.clone(),
+                "has no primary key".to_string(),
+                match table.relkind.as_str() {
+                    "r" => "add a primary key",
+                    _ => "declare a primary key with the primary_key_columns comment directive",
+                },
+            ));
+        }
+        if !is_valid_graphql_name(&type_name) {
+            issues.push(LintIssue::new(
+                entity_type,
+                entity.clone(),
+                format!("type name {type_name} is not a valid GraphQL name"),
+                "set a valid name with the name comment directive",
+            ));
+        }
+    }
+
+    let valid_tables: Vec<&Arc<Table>> = tables
+        .into_iter()
+        .filter(|x| schema.graphql_table_select_types_are_valid(x))
+        .collect();
+
+    // Tables whose types would share a name
+    let mut tables_by_type_name: HashMap<String, Vec<&Arc<Table>>> = HashMap::new();
+    for table in &valid_tables {
+        tables_by_type_name
+            .entry(schema.graphql_table_base_type_name(table))
+            .or_default()
+            .push(table);
+    }
+    for (type_name, same_name_tables) in &tables_by_type_name {
+        for table in same_name_tables {
+            for other in same_name_tables.iter().filter(|x| x.oid != table.oid) {
+                issues.push(LintIssue::new(
+                    table_entity_type(table),
+                    table_entity(&table.schema, &table.name),
+                    format!(
+                        "type name {type_name} is also used by {}",
+                        table_entity(&other.schema, &other.name)
+                    ),
+                    "set a different name with the name comment directive",
+                ));
+            }
+        }
+    }
+
+    for table in &valid_tables {
+        let fields = NodeType {
+            table: Arc::clone(table),
+            fkey: None,
+            reverse_reference: None,
+            schema: Arc::new(schema.clone()),
+        }
+        .fields(true)
+        .unwrap_or_default();
+        issues.extend(lint_columns(schema, table));
+        issues.extend(lint_relationship_fields(schema, table, &fields));
+        issues.extend(lint_computed_fields(schema, table, &fields));
+    }
+
+    issues.extend(lint_foreign_keys(schema, &valid_tables));
+    issues.extend(lint_functions(schema));
+    issues
+}
+
+fn lint_columns(schema: &__Schema, table: &Table) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let role = &schema.context.config.role;
+    let table_entity = table_entity(&table.schema, &table.name);
+    let column_entity = |column: &Column| format!("{table_entity}.{}", quote_ident(&column.name));
+    let schema_rc = Arc::new(schema.clone());
+
+    let mut visible_columns = vec![];
+    for column in &table.columns {
+        let entity = column_entity(column);
+        let field_name = schema.graphql_column_field_name(column);
+
+        if matches!(column.directives.omit, Some(ColumnDirectiveOmit::All(true))) {
+            issues.push(LintIssue::new(
+                "column",
+                entity,
+                "omitted by the omit comment directive".to_string(),
+                "remove omit from the comment directive to expose it",
+            ));
+            continue;
+        }
+        let omitted_operations: Vec<&str> = [
+            OmitOperation::Select,
+            OmitOperation::Insert,
+            OmitOperation::Update,
+            OmitOperation::Filter,
+            OmitOperation::Order,
+        ]
+        .into_iter()
+        .filter(|x| column.is_omitted(*x))
+        .map(|x| x.as_str())
+        .collect();
+        if !omitted_operations.is_empty() {
+            issues.push(LintIssue::new(
+                "column",
+                entity.clone(),
+                format!(
+                    "omitted from {} by the omit comment directive",
+                    omitted_operations.join(", ")
+                ),
+                "remove the operations from omit in the comment directive to expose it there",
+            ));
+        }
+        if !column.permissions.is_selectable {
+            issues.push(LintIssue::new(
+                "column",
+                entity,
+                format!("role {role} can not select it"),
+                format!(
+                    "grant select ({}) on {table_entity} to {}",
+                    quote_ident(&column.name),
+                    quote_ident(role)
+                ),
+            ));
+            continue;
+        }
+        if sql_column_to_graphql_type(column, &schema_rc).is_none() {
+            issues.push(LintIssue::new(
+                "column",
+                entity,
+                format!("type {} is not supported", column.type_name),
+                "expose the value through a computed field or view with a supported type",
+            ));
+            continue;
+        }
+        if !is_valid_graphql_name(&field_name) {
+            issues.push(LintIssue::new(
+                "column",
+                entity,
+                format!("field name {field_name} is not a valid GraphQL name"),
+                "set a valid name with the name comment directive",
+            ));
+            continue;
+        }
+        visible_columns.push((column, field_name));
+    }
+
+    for (column, field_name) in &visible_columns {
+        for (other, _) in visible_columns
+            .iter()
+            .filter(|(x, other_name)| x.name != column.name && other_name == field_name)
+        {
+            issues.push(LintIssue::new(
+                "column",
+                column_entity(column),
+                format!(
+                    "field name {field_name} is also used by {}",
+                    column_entity(other)
+                ),
+                "set a different name with the name comment directive",
+            ));
+        }
+    }
+    issues
+}
+
+/// Many-to-many and hierarchy fields are left off a table's type when a column or
+/// relationship already uses their name
+fn lint_relationship_fields(
+    schema: &__Schema,
+    table: &Arc<Table>,
+    fields: &[__Field],
+) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let type_name = schema.graphql_table_base_type_name(table);
+    let is_connection_field = |name: &str, is_match: &dyn Fn(&ConnectionType) -> bool| {
+        fields.iter().any(|x| {
+            x.name_ == name
+                && matches!(x.type_.unmodified_type(), __Type::Connection(c) if is_match(&c))
+        })
+    };
+
+    for junction_table in schema.context.tables.values().sorted_by_key(|x| x.oid) {
+        let Some((a, b)) = schema.graphql_table_junction_foreign_keys(junction_table) else {
+            continue;
+        };
+        for (local_fkey, foreign_fkey) in [(&a, &b), (&b, &a)] {
+            let is_foreign_table_valid = schema
+                .context
+                .get_table_by_oid(foreign_fkey.referenced_table_meta.oid)
+                .is_some_and(|x| schema.graphql_table_select_types_are_valid(x));
+            if local_fkey.referenced_table_meta.oid != table.oid || !is_foreign_table_valid {
+                continue;
+            }
+            let field_name = format!(
+                "{}Collection",
+                schema.graphql_foreign_key_field_name(foreign_fkey, false)
+            );
+            let is_exposed = is_connection_field(&field_name, &|c| {
+                c.junction
+                    .as_ref()
+                    .is_some_and(|x| x.table.oid == junction_table.oid)
+            });
+            if !is_exposed {
+                issues.push(LintIssue::new(
+                    table_entity_type(junction_table),
+                    table_entity(&junction_table.schema, &junction_table.name),
+                    format!(
+                        "many-to-many field {field_name} on type {type_name} is hidden by a column or relationship with the same name"
+                    ),
+                    "rename the column or relationship with the name comment directive",
+                ));
+            }
+        }
+    }
+
+    if schema.graphql_table_hierarchy_foreign_key(table).is_some() {
+        for field_name in [node::ANCESTORS, node::DESCENDANTS] {
+            if !is_connection_field(field_name, &|c| c.hierarchy.is_some()) {
+                issues.push(LintIssue::new(
+                    table_entity_type(table),
+                    table_entity(&table.schema, &table.name),
+                    format!(
+                        "hierarchy field {field_name} on type {type_name} is hidden by a column or relationship with the same name"
+                    ),
+                    "rename the column or relationship with the name comment directive",
+                ));
+            }
+        }
+    }
+    issues
+}
+
+fn lint_foreign_keys(schema: &__Schema, valid_tables: &[&Arc<Table>]) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let is_valid = |oid: u32| valid_tables.iter().any(|x| x.oid == oid);
+
+    for fkey in schema.context.foreign_keys() {
+        let local = &fkey.local_table_meta;
+        let referenced = &fkey.referenced_table_meta;
+        if !is_valid(local.oid) {
+            continue;
+        }
+        let entity = format!(
+            "{}({}) references {}({})",
+            table_entity(&local.schema, &local.name),
+            local.column_names.iter().map(|x| quote_ident(x)).join(", "),
+            table_entity(&referenced.schema, &referenced.name),
+            referenced
+                .column_names
+                .iter()
+                .map(|x| quote_ident(x))
+                .join(", "),
+        );
+
+        if fkey.directives.omit {
+            issues.push(LintIssue::new(
+                "foreign key",
+                entity,
+                "omitted by the omit comment directive".to_string(),
+                "remove omit from the comment directive to expose it",
+            ));
+            continue;
+        }
+
+        let is_enum_table = schema
+            .context
+            .get_table_by_oid(referenced.oid)
+            .is_some_and(|x| schema.graphql_table_enum_types_are_valid(x));
+        if !is_valid(referenced.oid) && !is_enum_table {
+            let referenced_entity = table_entity(&referenced.schema, &referenced.name);
+            issues.push(LintIssue::new(
+                "foreign key",
+                entity,
+                format!("references {referenced_entity}, which is not exposed"),
+                format!("resolve the issues reported for {referenced_entity}"),
+            ));
+        }
+    }
+    issues
+}
+
+/// Functions taking a table's row are exposed as fields of the table's type
+fn lint_computed_fields(schema: &__Schema, table: &Table, fields: &[__Field]) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let role = &schema.context.config.role;
+    let type_name = schema.graphql_table_base_type_name(table);
+
+    for func in table.functions.iter().sorted_by_key(|x| x.oid) {
+        let entity = function_entity(func);
+        let field_name = schema.graphql_function_field_name(func);
+        let is_exposed = fields
+            .iter()
+            .any(|x| matches!(&x.sql_type, Some(NodeSQLType::Function(f)) if f.oid == func.oid));
+
+        let (reason, suggestion) = if func.directives.omit {
+            (
+                "omitted by the omit comment directive".to_string(),
+                "remove omit from the comment directive to expose it".to_string(),
+            )
+        } else if !func.permissions.is_executable {
+            (
+                format!("role {role} can not execute it"),
+                format!(
+                    "grant execute on function {entity} to {}",
+                    quote_ident(role)
+                ),
+            )
+        } else if !table.permissions.is_selectable {
+            (
+                format!(
+                    "computed fields require role {role} to select every column of {}",
+                    table_entity(&table.schema, &table.name)
+                ),
+                format!(
+                    "grant select on {} to {}",
+                    table_entity(&table.schema, &table.name),
+                    quote_ident(role)
+                ),
+            )
+        } else if !is_exposed && !is_valid_graphql_name(&field_name) {
+            (
+                format!("field name {field_name} is not a valid GraphQL name"),
+                "set a valid name with the name comment directive".to_string(),
+            )
+        } else if !is_exposed {
+            (
+                format!("return type {} is not supported", func.type_name),
+                "return a scalar, an array of scalars, or a table's row type".to_string(),
+            )
+        } else if fields.iter().filter(|x| x.name_ == field_name).count() > 1 {
+            (
+                format!("field name {field_name} is also used by another field of {type_name}"),
+                "set a different name with the name comment directive".to_string(),
+            )
+        } else {
+            continue;
+        };
+        issues.push(LintIssue::new("function", entity, reason, suggestion));
+    }
+    issues
+}
+
+fn function_entity(func: &Function) -> String {
+    format!(
+        "{}.{}({})",
+        quote_ident(&func.schema_name),
+        quote_ident(&func.name),
+        func.arg_type_names.join(", ")
+    )
+}
+
+/// Explains why a supported function with a valid, unshadowed name is missing from the
+/// Query or Mutation type
+fn unexposed_function_reason(schema: &Arc<__Schema>, func: &Function) -> (String, String) {
+    if func.is_polymorphic() {
+        return if schema.graphql_function_member_tables(func).is_empty() {
+            (
+                "none of the tables named by its union or interface directive are exposed"
+                    .to_string(),
+                "resolve the issues reported for the member tables".to_string(),
+            )
+        } else {
+            (
+                "its union or interface name is invalid or used by another type".to_string(),
+                "set a distinct valid union name, or name an interface declared by a table"
+                    .to_string(),
+            )
+        };
+    }
+    let return_type = schema
+        .context
+        .types
+        .get(&func.type_oid)
+        .and_then(|x| x.to_graphql_type(None, func.is_set_of, schema));
+    match return_type {
+        None => (
+            format!("return type {} is not supported", func.type_name),
+            "return a scalar, an array of scalars, or a table's row type".to_string(),
+        ),
+        Some(__Type::Node(NodeType { table, .. }))
+        | Some(__Type::Connection(ConnectionType { table, .. }))
+            if !schema.graphql_table_select_types_are_valid(&table) =>
+        {
+            let entity = table_entity(&table.schema, &table.name);
+            (
+                format!("returns records of {entity}, which is not exposed"),
+                format!("resolve the issues reported for {entity}"),
+            )
+        }
+        Some(_) => (
+            "an argument name is also used by an argument of the returned connection".to_string(),
+            "rename the argument".to_string(),
+        ),
+    }
+}
+
+fn lint_functions(schema: &__Schema) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    let role = &schema.context.config.role;
+    let function_name_to_count = Function::function_names_to_count(&schema.context.functions);
+
+    // Functions taking a table's row are computed fields rather than query or mutation fields
+    let computed_field_oids: Vec<u32> = schema
+        .context
+        .tables
+        .values()
+        .flat_map(|x| x.functions.iter().map(|func| func.oid))
+        .collect();
+
+    // Collection, ByPk and other generated fields take precedence over functions
+    let schema_rc = Arc::new(schema.clone());
+    let query_fields = QueryType {
+        schema: Arc::clone(&schema_rc),
+    }
+    .fields(true)
+    .unwrap_or_default();
+    let mutation_fields = MutationType {
+        schema: Arc::clone(&schema_rc),
+    }
+    .fields(true)
+    .unwrap_or_default();
+
+    for func in schema
+        .context
+        .functions
+        .iter()
+        .filter(|x| !computed_field_oids.contains(&x.oid))
+        .sorted_by_key(|x| x.oid)
+    {
+        let entity = function_entity(func);
+        let field_name = schema.graphql_function_field_name(func);
+
+        let (root_type_name, root_fields) = match func.volatility {
+            FunctionVolatility::Volatile => ("Mutation", &mutation_fields),
+            _ => ("Query", &query_fields),
+        };
+        let is_field = |x: &__Field| matches!(&x.sql_type, Some(NodeSQLType::Function(f)) if f.oid == func.oid);
+        let is_exposed = root_fields.iter().any(is_field);
+        let is_shadowed = root_fields
+            .iter()
+            .any(|x| x.name_ == field_name && !is_field(x));
+
+        let (reason, suggestion) =
+            match func.unsupported_reason(&schema.context, &function_name_to_count) {
+                // Functions in system schemas are not user defined
+                Some(FunctionUnsupportedReason::SystemSchema) => continue,
+                Some(FunctionUnsupportedReason::Omitted) => (
+                    "omitted by the omit comment directive".to_string(),
+                    "remove omit from the comment directive to expose it".to_string(),
+                ),
+                Some(FunctionUnsupportedReason::NotExecutable) => (
+                    format!("role {role} can not execute it"),
+                    format!(
+                        "grant execute on function {entity} to {}",
+                        quote_ident(role)
+                    ),
+                ),
+                Some(FunctionUnsupportedReason::Overloaded) => (
+                    format!("function name {} is overloaded", func.name),
+                    "rename the overloads so that each function has a distinct name".to_string(),
+                ),
+                Some(FunctionUnsupportedReason::NamelessArg) => (
+                    "has an argument without a name".to_string(),
+                    "name every argument".to_string(),
+                ),
+                Some(FunctionUnsupportedReason::ArgType) => (
+                    "has an argument of an unsupported type".to_string(),
+                    "use scalar or array of scalar argument types".to_string(),
+                ),
+                Some(FunctionUnsupportedReason::ReturnType) => (
+                    format!("return type {} is not supported", func.type_name),
+                    "return a scalar, an array of scalars, or a table's row type".to_string(),
+                ),
+                None if !is_valid_graphql_name(&field_name) => (
+                    format!("field name {field_name} is not a valid GraphQL name"),
+                    "set a valid name with the name comment directive".to_string(),
+                ),
+                None if is_shadowed => (
+                    format!("field name {field_name} is used by another {root_type_name} field"),
+                    "set a different name with the name comment directive".to_string(),
+                ),
+                None if !is_exposed => unexposed_function_reason(&schema_rc, func),
+                None => continue,
+            };
+        issues.push(LintIssue::new("function", entity, reason, suggestion));
+    }
+    issues
+}
@@ -22,6 +22,32 @@ pub fn normalize_selection_set<'a, 'b, T>(
     type_name: &String,            // for inline fragments
     variables: &serde_json::Value, // for directives
 ) -> GraphQLResult<Vec<Field<'a, T>>>
+where
+    T: Text<'a> + Eq + AsRef<str> + Clone,
+    T::Value: Hash,
+{
+    normalize_selection_set_of_type(
+        selection_set,
+        fragment_definitions,
+        type_name,
+        &[],
+        &[],
+        variables,
+    )
+}
+
+/// Normalizes a selection set for a concrete type that implements, or is a member of,
+/// the abstract types named in `abstract_type_names`. Fragments with any of those type
+/// conditions apply. Named fragments on `sibling_type_names`, the other possible types of
+/// a union or interface result, are skipped rather than rejected
+pub fn normalize_selection_set_of_type<'a, 'b, T>(
+    selection_set: &'b SelectionSet<'a, T>,
+    fragment_definitions: &'b Vec<FragmentDefinition<'a, T>>,
+    type_name: &String,             // for inline fragments
+    abstract_type_names: &[String], // for fragments on interfaces and unions
+    sibling_type_names: &[String],  // for fragments on other possible types
+    variables: &serde_json::Value,  // for directives
+) -> GraphQLResult<Vec<Field<'a, T>>>
 where
     T: Text<'a> + Eq + AsRef<str> + Clone,
     T::Value: Hash,
@@ -30,7 +56,14 @@ where
 
     for selection in &selection_set.items {
         let sel = selection;
-        match normalize_selection(sel, fragment_definitions, type_name, variables) {
+        match normalize_selection(
+            sel,
+            fragment_definitions,
+            type_name,
+            abstract_type_names,
+            sibling_type_names,
+            variables,
+        ) {
             Ok(sels) => selections.extend(sels),
             Err(err) => return Err(err),
         }
@@ -152,8 +185,10 @@ where
 pub fn normalize_selection<'a, 'b, T>(
     query_selection: &'b Selection<'a, T>,
     fragment_definitions: &'b Vec<FragmentDefinition<'a, T>>,
-    type_name: &String,            // for inline fragments
-    variables: &serde_json::Value, // for directives
+    type_name: &String,             // for inline fragments
+    abstract_type_names: &[String], // for fragments on interfaces and unions
+    sibling_type_names: &[String],  // for fragments on other possible types
+    variables: &serde_json::Value,  // for directives
 ) -> GraphQLResult<Vec<Field<'a, T>>>
 where
     T: Text<'a> + Eq + AsRef<str> + Clone,
@@ -161,6 +196,10 @@ where
 {
     let mut selections: Vec<Field<'a, T>> = vec![];
 
+    let type_condition_applies = |condition: &str| {
+        condition == type_name || abstract_type_names.iter().any(|x| x == condition)
+    };
+
     if selection_is_skipped(query_selection, variables)? {
         return Ok(selections);
     }
@@ -180,9 +219,23 @@ where
                 .filter(|x| &x.name == frag_name)
                 .find(|x| match &x.type_condition {
                     // TODO match when no type condition is specified?
-                    TypeCondition::On(frag_type_name) => frag_type_name.as_ref() == type_name,
+                    TypeCondition::On(frag_type_name) => {
+                        type_condition_applies(frag_type_name.as_ref())
+                    }
                 }) {
                 Some(frag) => frag,
+                // Spreads of fragments on the other possible types of an abstract type
+                None if fragment_definitions.iter().any(|x| {
+                    &x.name == frag_name
+                        && match &x.type_condition {
+                            TypeCondition::On(frag_type_name) => sibling_type_names
+                                .iter()
+                                .any(|y| y == frag_type_name.as_ref()),
+                        }
+                }) =>
+                {
+                    return Ok(selections);
+                }
                 None => {
                     return Err(GraphQLError::validation(format!(
                         "no fragment named {} on type {}",
@@ -193,10 +246,12 @@ where
             };
 
             // TODO handle directives?
-            let frag_selections = normalize_selection_set(
+            let frag_selections = normalize_selection_set_of_type(
                 &frag_def.selection_set,
                 fragment_definitions,
                 type_name,
+                abstract_type_names,
+                sibling_type_names,
                 variables,
             );
             match frag_selections {
@@ -207,16 +262,18 @@ where
         Selection::InlineFragment(inline_fragment) => {
             let inline_fragment_applies: bool = match &inline_fragment.type_condition {
                 Some(infrag) => match infrag {
-                    TypeCondition::On(infrag_name) => infrag_name.as_ref() == type_name,
+                    TypeCondition::On(infrag_name) => type_condition_applies(infrag_name.as_ref()),
                 },
                 None => true,
             };
 
             if inline_fragment_applies {
-                let infrag_selections = normalize_selection_set(
+                let infrag_selections = normalize_selection_set_of_type(
                     &inline_fragment.selection_set,
                     fragment_definitions,
                     type_name,
+                    abstract_type_names,
+                    sibling_type_names,
                     variables,
                 )?;
                 selections.extend(infrag_selections);
@@ -492,7 +549,10 @@ pub fn validate_arg_from_type(type_: &__Type, value: &gson::Value) -> GraphQLRes
                                     .and_then(|mappings| mappings.get_by_right(user_input_string))
                                     .map(|val| GsonValue::String(val.clone()))
                                     .unwrap_or_else(|| value.clone()),
-                                EnumSource::FilterIs => value.clone(),
+                                EnumSource::FilterIs
+                                | EnumSource::TableField(_)
+                                | EnumSource::DateTruncUnit
+                                | EnumSource::Table(_) => value.clone(),
                             }
                         }
                         None => {
@@ -573,8 +633,13 @@ pub fn validate_arg_from_type(type_: &__Type, value: &gson::Value) -> GraphQLRes
         __Type::InsertInput(_) => validate_arg_from_input_object(type_, value)?,
         __Type::UpdateInput(_) => validate_arg_from_input_object(type_, value)?,
         __Type::OrderByEntity(_) => validate_arg_from_input_object(type_, value)?,
+        __Type::AggregateOrderBy(_) => validate_arg_from_input_object(type_, value)?,
+        __Type::AggregateGroupOrderBy(_) => validate_arg_from_input_object(type_, value)?,
+        __Type::GroupByKey(_) => validate_arg_from_input_object(type_, value)?,
+        __Type::SearchRankOrderBy(_) => validate_arg_from_input_object(type_, value)?,
         __Type::FilterType(_) => validate_arg_from_input_object(type_, value)?,
         __Type::FilterEntity(_) => validate_arg_from_input_object(type_, value)?,
+        __Type::RelationFilter(_) => validate_arg_from_input_object(type_, value)?,
         _ => {
             return Err(GraphQLError::type_error(format!(
                 "Invalid Type used as input argument {}",
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 use std::hash::Hash;
 
 use crate::builder::*;
-use crate::constants::introspection;
+use crate::constants::{connection, introspection};
 use crate::error::{GraphQLError, GraphQLResult};
 use crate::graphql::*;
 use crate::omit::*;
@@ -233,6 +233,29 @@ where
                                 }),
                             }
                         }
+                        __Type::NodeInterface(_) if field_def.name() == connection::NODES => {
+                            let nodes_builder = to_nodes_builder(
+                                field_def,
+                                selection,
+                                &fragment_definitions,
+                                variables,
+                                variable_definitions,
+                            );
+
+                            match nodes_builder {
+                                Ok(builder) => match builder.execute() {
+                                    Ok(d) => {
+                                        res_data[alias_or_name(selection)] = d;
+                                    }
+                                    Err(msg) => res_errors.push(ErrorMessage {
+                                        message: msg.to_string(),
+                                    }),
+                                },
+                                Err(msg) => res_errors.push(ErrorMessage {
+                                    message: msg.to_string(),
+                                }),
+                            }
+                        }
                         __Type::NodeInterface(_) => {
                             let node_builder = to_node_builder(
                                 field_def,
@@ -258,7 +281,7 @@ where
                             }
                         }
                         __Type::Node(_) => {
-                            // Node types at Query level are *ByPk fields with primary key column args
+                            // Node types at Query level are *ByPk and unique lookup fields with key column args
                             let node_by_pk_builder = to_node_by_pk_builder(
                                 field_def,
                                 selection,
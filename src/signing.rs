@@ -0,0 +1,94 @@
+use hmac::{Hmac, Mac};
+use pgrx::prelude::*;
+use pgrx::{GucContext, GucFlags, GucRegistry, GucSetting};
+use sha2::Sha256;
+use std::ffi::CString;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Separates a token's payload from its signature. Not part of the base64 alphabet
+const SIGNATURE_SEPARATOR: char = '.';
+
+/// Secret used to sign cursors and nodeIds. Signing is disabled while unset
+static SIGNING_SECRET: GucSetting<Option<CString>> = GucSetting::<Option<CString>>::new(None);
+
+pub fn init() {
+    GucRegistry::define_string_guc(
+        c"graphql.signing_secret",
+        c"Secret used to sign cursors and nodeIds",
+        c"When set, cursors and nodeIds are signed with HMAC-SHA256 and rejected if their signature does not match",
+        &SIGNING_SECRET,
+        GucContext::Suset,
+        GucFlags::SUPERUSER_ONLY | GucFlags::NO_SHOW_ALL,
+    );
+}
+
+/// The configured signing secret, if signing is enabled
+pub fn signing_secret() -> Option<String> {
+    SIGNING_SECRET
+        .get()
+        .and_then(|secret| secret.into_string().ok())
+        .filter(|secret| !secret.is_empty())
+}
+
+/// What a token identifies. Signed along with the payload so that a token issued for one
+/// purpose, e.g. a nodeId, is not accepted as another, e.g. a cursor
+#[derive(Clone, Copy, Debug)]
+pub enum TokenPurpose {
+    Cursor,
+    NodeId,
+}
+
+impl TokenPurpose {
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::Cursor => "cursor",
+            Self::NodeId => "node",
+        }
+    }
+}
+
+fn signature(purpose_tag: &str, payload: &str, secret: &str) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(purpose_tag.as_bytes());
+    mac.update(b":");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+/// Appends the signature of `payload` for the purpose tagged `purpose_tag` to it
+pub fn sign(payload: &str, purpose_tag: &str, secret: &str) -> String {
+    let signature = signature(purpose_tag, payload, secret)
+        .finalize()
+        .into_bytes();
+    format!(
+        "{payload}{SIGNATURE_SEPARATOR}{}",
+        base64::encode(signature)
+    )
+}
+
+/// Returns the payload of a cursor or nodeId, checking its signature when signing is enabled
+pub fn verify(token: &str, purpose: TokenPurpose) -> Result<&str, &'static str> {
+    let secret = match signing_secret() {
+        Some(secret) => secret,
+        None => return Ok(token),
+    };
+
+    let (payload, encoded_signature) = token
+        .rsplit_once(SIGNATURE_SEPARATOR)
+        .ok_or("missing signature")?;
+
+    let provided = base64::decode(encoded_signature).map_err(|_| "malformed signature")?;
+
+    signature(purpose.tag(), payload, &secret)
+        .verify_slice(&provided)
+        .map_err(|_| "signature does not match")?;
+
+    Ok(payload)
+}
+
+#[pg_extern(name = "_sign_token", immutable, parallel_safe)]
+fn sign_token(payload: &str, purpose: &str, secret: &str) -> String {
+    sign(payload, purpose, secret)
+}
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::GraphQLResult;
 use std::cmp::Ordering;
 use std::collections::hash_map::DefaultHasher;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::*;
@@ -26,6 +26,41 @@ pub struct ColumnPermissions {
 pub struct ColumnDirectives {
     pub name: Option<String>,
     pub description: Option<String>,
+    // @graphql({"omit": true}) or @graphql({"omit": ["insert", "update", "filter", "order"]})
+    pub omit: Option<ColumnDirectiveOmit>,
+    // @graphql({"deprecated": "use fullName"})
+    pub deprecated: Option<String>,
+    // @graphql({"search_config": "english"})
+    pub search_config: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(untagged)]
+pub enum ColumnDirectiveOmit {
+    All(bool),
+    Operations(Vec<String>),
+}
+
+/// Places a column can be hidden from with the `omit` directive
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum OmitOperation {
+    Select,
+    Insert,
+    Update,
+    Filter,
+    Order,
+}
+
+impl OmitOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Select => "select",
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Filter => "filter",
+            Self::Order => "order",
+        }
+    }
 }
 
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
@@ -43,14 +78,68 @@ pub struct Column {
     pub attribute_num: i32,
     pub permissions: ColumnPermissions,
     pub comment: Option<String>,
+    // Lookup table with the enum directive referenced by the column's foreign key
+    pub enum_table_oid: Option<u32>,
     pub directives: ColumnDirectives,
 }
 
+impl Column {
+    /// Is the column hidden from `operation` by an `omit` directive. Omitting a column
+    /// entirely hides it everywhere, including the fields of its type
+    pub fn is_omitted(&self, operation: OmitOperation) -> bool {
+        match &self.directives.omit {
+            None => false,
+            Some(ColumnDirectiveOmit::All(omit)) => *omit,
+            Some(ColumnDirectiveOmit::Operations(operations)) => {
+                operations.iter().any(|x| x == operation.as_str())
+            }
+        }
+    }
+
+    /// Supports full text search. `tsvector` columns are always searchable while text
+    /// columns require a `search_config` directive naming the `regconfig` to use
+    pub fn is_searchable(&self) -> bool {
+        match self.type_name.as_str() {
+            "tsvector" => true,
+            "text" | "varchar" | "bpchar" | "citext" => self.directives.search_config.is_some(),
+            _ => false,
+        }
+    }
+
+    /// `json` and `jsonb` columns support the `JSONFilter` operators and path extraction
+    pub fn is_json(&self) -> bool {
+        matches!(self.type_name.as_str(), "json" | "jsonb")
+    }
+}
+
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct FunctionDirectives {
     pub name: Option<String>,
     // @graphql({"description": "the address of ..." })
     pub description: Option<String>,
+    // @graphql({"omit": true})
+    pub omit: bool,
+    // @graphql({"deprecated": "use fullName"})
+    pub deprecated: Option<String>,
+    // @graphql({"union": {"name": "SearchResult", "types": ["post", "account"]}})
+    pub union: Option<FunctionDirectiveUnion>,
+    // @graphql({"interface": "PaymentMethod"})
+    pub interface: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FunctionDirectiveUnion {
+    pub name: String,
+    // Names of the member tables, in the function's schema
+    pub types: Vec<String>,
+}
+
+/// A column of a function's `returns table(...)` result
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FunctionReturnColumn {
+    pub name: String,
+    pub type_oid: u32,
+    pub type_name: String,
 }
 
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
@@ -84,6 +173,7 @@ pub struct Function {
     pub type_oid: u32,
     pub type_name: String,
     pub is_set_of: bool,
+    pub return_columns: Option<Vec<FunctionReturnColumn>>,
     pub comment: Option<String>,
     pub directives: FunctionDirectives,
     pub permissions: FunctionPermissions,
@@ -114,13 +204,34 @@ impl Function {
         context: &Context,
         function_name_to_count: &HashMap<&String, u32>,
     ) -> bool {
+        self.unsupported_reason(context, function_name_to_count)
+            .is_none()
+    }
+
+    /// Why the function is left out of the API, if it is
+    pub fn unsupported_reason(
+        &self,
+        context: &Context,
+        function_name_to_count: &HashMap<&String, u32>,
+    ) -> Option<FunctionUnsupportedReason> {
         let types = &context.types;
-        self.return_type_is_supported(types)
-            && self.arg_types_are_supported(types)
-            && !self.is_function_overloaded(function_name_to_count)
-            && !self.has_a_nameless_arg()
-            && self.permissions.is_executable
-            && !self.is_in_a_system_schema()
+        if self.directives.omit {
+            Some(FunctionUnsupportedReason::Omitted)
+        } else if self.is_in_a_system_schema() {
+            Some(FunctionUnsupportedReason::SystemSchema)
+        } else if !self.permissions.is_executable {
+            Some(FunctionUnsupportedReason::NotExecutable)
+        } else if self.is_function_overloaded(function_name_to_count) {
+            Some(FunctionUnsupportedReason::Overloaded)
+        } else if self.has_a_nameless_arg() {
+            Some(FunctionUnsupportedReason::NamelessArg)
+        } else if !self.arg_types_are_supported(types) {
+            Some(FunctionUnsupportedReason::ArgType)
+        } else if !self.return_type_is_supported(types) {
+            Some(FunctionUnsupportedReason::ReturnType)
+        } else {
+            None
+        }
     }
 
     fn arg_types_are_supported(&self, types: &HashMap<u32, Arc<Type>>) -> bool {
@@ -139,7 +250,21 @@ impl Function {
         })
     }
 
+    /// Functions returning rows tagged with the name of the table each row identifies.
+    /// The first returned column holds the table name and the rest its primary key
+    pub fn is_polymorphic(&self) -> bool {
+        (self.directives.union.is_some() || self.directives.interface.is_some())
+            && self.is_set_of
+            && self
+                .return_columns
+                .as_ref()
+                .is_some_and(|columns| columns.len() >= 2)
+    }
+
     fn return_type_is_supported(&self, types: &HashMap<u32, Arc<Type>>) -> bool {
+        if self.is_polymorphic() {
+            return true;
+        }
         if let Some(return_type) = types.get(&self.type_oid) {
             let array_element_type_is_supported = self.array_element_type_is_supported(
                 &return_type.category,
@@ -197,6 +322,17 @@ impl Function {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FunctionUnsupportedReason {
+    Omitted,
+    SystemSchema,
+    NotExecutable,
+    Overloaded,
+    NamelessArg,
+    ArgType,
+    ReturnType,
+}
+
 struct ArgsIterator<'a> {
     index: usize,
     arg_types: &'a [u32],
@@ -405,6 +541,9 @@ pub struct Enum {
 pub struct EnumDirectives {
     pub name: Option<String>,
     pub mappings: Option<BiBTreeMap<String, String>>,
+    // Deprecation reasons keyed by enum value
+    // @graphql({"deprecated": {"archived": "use inactive"}})
+    pub deprecated: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
@@ -435,6 +574,10 @@ pub struct ForeignKeyTableInfo {
 pub struct ForeignKeyDirectives {
     pub local_name: Option<String>,
     pub foreign_name: Option<String>,
+    // @graphql({"omit": true})
+    pub omit: bool,
+    // @graphql({"deprecated": "use author"})
+    pub deprecated: Option<String>,
 }
 
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
@@ -449,11 +592,61 @@ pub struct TableDirectiveTotalCount {
     pub enabled: bool,
 }
 
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TableDirectiveTotalCountEstimate {
+    pub enabled: bool,
+}
+
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDirectiveAggregate {
     pub enabled: bool,
 }
 
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TableDirectiveVersion {
+    pub enabled: bool,
+    // Column holding the row version. When absent, the system column `xmin` is used
+    pub column: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(untagged)]
+pub enum TableDirectiveMutations {
+    // Operations to generate e.g. ["insert", "update"]
+    List(Vec<String>),
+    // Operations to generate, with optional name overrides
+    Map(TableDirectiveMutationsMap),
+}
+
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TableDirectiveMutationsMap {
+    pub insert: Option<TableDirectiveMutation>,
+    pub update: Option<TableDirectiveMutation>,
+    pub delete: Option<TableDirectiveMutation>,
+}
+
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TableDirectiveMutation {
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MutationOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl MutationOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDirectiveForeignKey {
     // Equivalent to ForeignKeyDirectives.local_name
@@ -465,6 +658,9 @@ pub struct TableDirectiveForeignKey {
     pub foreign_schema: String,
     pub foreign_table: String,
     pub foreign_columns: Vec<String>,
+
+    // Equivalent to ForeignKeyDirectives.deprecated
+    pub deprecated: Option<String>,
 }
 
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
@@ -478,6 +674,9 @@ pub struct TableDirectives {
     // @graphql({"totalCount": { "enabled": true } })
     pub total_count: Option<TableDirectiveTotalCount>,
 
+    // @graphql({"totalCountEstimate": { "enabled": true } })
+    pub total_count_estimate: Option<TableDirectiveTotalCountEstimate>,
+
     // @graphql({"aggregate": { "enabled": true } })
     pub aggregate: Option<TableDirectiveAggregate>,
 
@@ -487,6 +686,32 @@ pub struct TableDirectives {
     // @graphql({"max_rows": 20})
     pub max_rows: Option<u64>,
 
+    // @graphql({"version": { "enabled": true, "column": "revision" } })
+    pub version: Option<TableDirectiveVersion>,
+
+    // @graphql({"soft_delete": "deleted_at"})
+    pub soft_delete: Option<String>,
+
+    // @graphql({"node_id_alias": "Post"})
+    pub node_id_alias: Option<String>,
+
+    /*
+    @graphql({"mutations": ["insert", "update"]})
+    or, with name overrides
+    @graphql({"mutations": {"insert": {"name": "createBlogPost"}, "update": {}}})
+    */
+    pub mutations: Option<TableDirectiveMutations>,
+
+    // @graphql({"omit": true})
+    pub omit: bool,
+
+    // @graphql({"junction": true})
+    // Overrides detection of junction tables for many-to-many relationships
+    pub junction: Option<bool>,
+
+    // @graphql({"interfaces": ["PaymentMethod"]})
+    pub interfaces: Option<Vec<String>>,
+
     /*
     @graphql(
       {
@@ -525,6 +750,15 @@ pub struct Table {
     #[serde(default)]
     pub functions: Vec<Arc<Function>>,
     pub directives: TableDirectives,
+    // Rows of a lookup table with the enum directive
+    pub enum_values: Option<Vec<TableEnumValue>>,
+}
+
+/// A row of a lookup table exposed as an enum
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TableEnumValue {
+    pub value: String,
+    pub description: Option<String>,
 }
 
 impl Table {
@@ -590,16 +824,110 @@ impl Table {
             .all(|col| SupportedPrimaryKeyType::from_type_name(&col.type_name).is_some())
     }
 
+    /// Unique indexes other than the primary key that can identify a single record for lookup
+    pub fn unique_lookup_indexes(&self) -> Vec<&Index> {
+        let pkey_column_names = self
+            .primary_key()
+            .map(|x| x.column_names)
+            .unwrap_or_default();
+
+        self.indexes
+            .iter()
+            .filter(|index| {
+                index.is_unique
+                    && !index.is_primary_key
+                    && !index.column_names.is_empty()
+                    && index.column_names != pkey_column_names
+            })
+            .filter(|index| {
+                index.column_names.iter().all(|col_name| {
+                    self.columns.iter().any(|col| {
+                        &col.name == col_name
+                            && col.permissions.is_selectable
+                            && SupportedPrimaryKeyType::from_type_name(&col.type_name).is_some()
+                    })
+                })
+            })
+            .collect()
+    }
+
     pub fn is_any_column_selectable(&self) -> bool {
-        self.columns.iter().any(|x| x.permissions.is_selectable)
+        self.columns
+            .iter()
+            .any(|x| x.permissions.is_selectable && !x.is_omitted(OmitOperation::Select))
     }
 
     pub fn is_any_column_insertable(&self) -> bool {
-        self.columns.iter().any(|x| x.permissions.is_insertable)
+        self.columns
+            .iter()
+            .any(|x| x.permissions.is_insertable && !x.is_omitted(OmitOperation::Insert))
     }
 
     pub fn is_any_column_updatable(&self) -> bool {
-        self.columns.iter().any(|x| x.permissions.is_updatable)
+        self.columns
+            .iter()
+            .any(|x| x.permissions.is_updatable && !x.is_omitted(OmitOperation::Update))
+    }
+
+    /// Where the row version used for optimistic concurrency control comes from.
+    /// Returns None unless the `version` directive is enabled. Without an explicit
+    /// column, the system column `xmin` is used, which is only available on tables
+    pub fn version_source(&self) -> Option<VersionSource> {
+        let directive = self.directives.version.as_ref()?;
+        if !directive.enabled {
+            return None;
+        }
+        match &directive.column {
+            Some(column_name) => self
+                .columns
+                .iter()
+                .find(|col| &col.name == column_name && col.permissions.is_selectable)
+                .map(|col| VersionSource::Column(Arc::clone(col))),
+            None => match self.relkind.as_str() {
+                "r" | "p" => Some(VersionSource::Xmin),
+                _ => None,
+            },
+        }
+    }
+
+    /// Is the mutation generated for this table. All mutations are generated unless the
+    /// `mutations` directive lists a subset of them
+    pub fn is_mutation_enabled(&self, operation: MutationOperation) -> bool {
+        match &self.directives.mutations {
+            None => true,
+            Some(TableDirectiveMutations::List(operations)) => {
+                operations.iter().any(|x| x == operation.as_str())
+            }
+            Some(TableDirectiveMutations::Map(_)) => self.mutation_directive(operation).is_some(),
+        }
+    }
+
+    /// Name override for the mutation's field from the `mutations` directive
+    pub fn mutation_name(&self, operation: MutationOperation) -> Option<&String> {
+        self.mutation_directive(operation)?.name.as_ref()
+    }
+
+    fn mutation_directive(&self, operation: MutationOperation) -> Option<&TableDirectiveMutation> {
+        match &self.directives.mutations {
+            Some(TableDirectiveMutations::Map(mutations)) => match operation {
+                MutationOperation::Insert => mutations.insert.as_ref(),
+                MutationOperation::Update => mutations.update.as_ref(),
+                MutationOperation::Delete => mutations.delete.as_ref(),
+            },
+            _ => None,
+        }
+    }
+
+    /// The column marking a row as deleted when the table has a
+    /// `soft_delete` directive. Rows where the column is null are live.
+    /// The directive is ignored unless it names a date or timestamp column
+    pub fn soft_delete_column(&self) -> Option<&Arc<Column>> {
+        let column_name = self.directives.soft_delete.as_ref()?;
+        self.columns.iter().find(|col| {
+            &col.name == column_name
+                && col.permissions.is_selectable
+                && matches!(col.type_name.as_str(), "date" | "timestamp" | "timestamptz")
+        })
     }
 
     /// Get the effective max_rows value for this table.
@@ -613,6 +941,12 @@ impl Table {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum VersionSource {
+    Column(Arc<Column>),
+    Xmin,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum SupportedPrimaryKeyType {
     // Integer types
@@ -656,6 +990,20 @@ pub struct SchemaDirectives {
     pub max_rows: u64,
     // @graphql({"introspection": true})
     pub introspection_enabled: bool,
+    // @graphql({"node_id_format": "type"})
+    pub node_id_format: NodeIdFormat,
+}
+
+/// Identifies the collection a nodeId belongs to
+#[derive(Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeIdFormat {
+    // ["public", "blog_post", 1]
+    Table,
+    // ["BlogPost", 1]
+    Type,
+    // ["7d0c5b61-...", 1]
+    Opaque,
 }
 
 #[derive(Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
@@ -683,6 +1031,12 @@ pub struct Context {
     pub enums: HashMap<u32, Arc<Enum>>,
     pub composites: Vec<Arc<Composite>>,
     pub functions: Vec<Arc<Function>>,
+    /// Foreign keys of junction tables, keyed by the junction table's oid
+    #[serde(skip)]
+    pub junctions: HashMap<u32, (Arc<ForeignKey>, Arc<ForeignKey>)>,
+    /// Foreign key of each hierarchical table, keyed by the table's oid
+    #[serde(skip)]
+    pub hierarchy_foreign_keys: HashMap<u32, Arc<ForeignKey>>,
 }
 
 impl Hash for Context {
@@ -748,6 +1102,8 @@ impl Context {
                     directives: ForeignKeyDirectives {
                         local_name: directive_fkey.local_name.clone(),
                         foreign_name: directive_fkey.foreign_name.clone(),
+                        omit: false,
+                        deprecated: directive_fkey.deprecated.clone(),
                     },
                 };
 
@@ -757,6 +1113,7 @@ impl Context {
 
         fkeys
             .into_iter()
+            .filter(|fk| !fk.directives.omit)
             .filter(|fk| self.fkey_is_selectable(fk))
             .collect()
     }
@@ -1041,10 +1398,78 @@ pub fn load_sql_context(_config: &Config) -> GraphQLResult<Arc<Context>> {
         context
     }
 
+    /// Foreign keys of a junction table linking two tables many-to-many. Tables are junctions
+    /// when their primary key is made up of exactly two foreign keys, or when marked with the
+    /// `junction` directive and holding exactly two foreign keys
+    fn junction_foreign_keys(
+        table: &Table,
+        fkeys: &[Arc<ForeignKey>],
+    ) -> Option<(Arc<ForeignKey>, Arc<ForeignKey>)> {
+        if table.directives.junction == Some(false) {
+            return None;
+        }
+
+        let [a, b] = fkeys else {
+            return None;
+        };
+
+        if table.directives.junction.is_none() {
+            let pkey_columns: HashSet<&String> = table
+                .primary_key_columns()
+                .iter()
+                .map(|x| &x.name)
+                .collect();
+            let a_columns: HashSet<&String> = a.local_table_meta.column_names.iter().collect();
+            let b_columns: HashSet<&String> = b.local_table_meta.column_names.iter().collect();
+
+            let is_pkey_of_foreign_keys = a_columns.is_disjoint(&b_columns)
+                && a_columns.union(&b_columns).copied().collect::<HashSet<_>>() == pkey_columns;
+            if !is_pkey_of_foreign_keys {
+                return None;
+            }
+        }
+
+        Some((Arc::clone(a), Arc::clone(b)))
+    }
+
+    /// This pass indexes the foreign keys of junction and hierarchical tables. Only tables
+    /// with exactly one foreign key referencing themselves are considered hierarchical
+    fn populate_relationships(mut context: Context) -> Context {
+        let mut table_fkeys: HashMap<u32, Vec<Arc<ForeignKey>>> = HashMap::new();
+        for fkey in context.foreign_keys() {
+            table_fkeys
+                .entry(fkey.local_table_meta.oid)
+                .or_default()
+                .push(fkey);
+        }
+
+        for (oid, fkeys) in &table_fkeys {
+            let Some(table) = context.tables.get(oid) else {
+                continue;
+            };
+
+            if let [fkey] = &fkeys
+                .iter()
+                .filter(|x| x.referenced_table_meta.oid == *oid)
+                .collect::<Vec<_>>()[..]
+            {
+                context
+                    .hierarchy_foreign_keys
+                    .insert(*oid, Arc::clone(fkey));
+            }
+
+            if let Some(junction) = junction_foreign_keys(table, fkeys) {
+                context.junctions.insert(*oid, junction);
+            }
+        }
+        context
+    }
+
     context
         .map(type_details)
         .map(column_types)
         .map(populate_table_functions)
+        .map(populate_relationships)
         .map(Arc::new)
         .map_err(|e| {
             crate::error::GraphQLError::schema(format!(
@@ -1,8 +1,10 @@
 use crate::builder::*;
-use crate::constants::aggregate;
 use crate::error::{GraphQLError, GraphQLResult};
 use crate::graphql::*;
-use crate::sql_types::{Column, ForeignKey, ForeignKeyTableInfo, Function, Table, TypeDetails};
+use crate::signing::{self, TokenPurpose};
+use crate::sql_types::{
+    Column, ForeignKey, ForeignKeyTableInfo, Function, Table, TypeDetails, VersionSource,
+};
 use itertools::Itertools;
 use pgrx::datum::DatumWithOid;
 use pgrx::pg_sys::PgBuiltInOids;
@@ -48,7 +50,7 @@ pub trait MutationEntrypoint<'conn> {
         &self,
         conn: &'c mut SpiClient<'conn>,
     ) -> GraphQLResult<(serde_json::Value, &'c mut SpiClient<'conn>)> {
-        let mut param_context = ParamContext { params: vec![] };
+        let mut param_context = ParamContext::default();
         let sql = &self.to_sql_entrypoint(&mut param_context);
         let sql = match sql {
             Ok(sql) => sql,
@@ -79,7 +81,7 @@ pub trait QueryEntrypoint {
     fn to_sql_entrypoint(&self, param_context: &mut ParamContext) -> GraphQLResult<String>;
 
     fn execute(&self) -> GraphQLResult<serde_json::Value> {
-        let mut param_context = ParamContext { params: vec![] };
+        let mut param_context = ParamContext::default();
         let sql = &self.to_sql_entrypoint(&mut param_context);
         let sql = match sql {
             Ok(sql) => sql,
@@ -108,6 +110,32 @@ pub trait QueryEntrypoint {
     }
 }
 
+impl Column {
+    /// The searchable document for full text search
+    fn to_tsvector_clause(&self, block_name: &str) -> String {
+        let quoted_col = format!("{block_name}.{}", quote_ident(&self.name));
+        match &self.directives.search_config {
+            _ if self.type_name == "tsvector" => quoted_col,
+            Some(config) => format!(
+                "to_tsvector({}::regconfig, {quoted_col})",
+                quote_literal(config)
+            ),
+            None => format!("to_tsvector({quoted_col})"),
+        }
+    }
+
+    /// Parses user provided search terms in web search syntax
+    fn to_tsquery_clause(&self, val_clause: &str) -> String {
+        match &self.directives.search_config {
+            Some(config) => format!(
+                "websearch_to_tsquery({}::regconfig, {val_clause})",
+                quote_literal(config)
+            ),
+            None => format!("websearch_to_tsquery({val_clause})"),
+        }
+    }
+}
+
 impl Table {
     fn to_selectable_columns_clause(&self) -> String {
         self.columns
@@ -118,6 +146,57 @@ impl Table {
             .join(", ")
     }
 
+    /// Selectable columns plus any system columns required by the table's directives.
+    /// Only valid when selecting directly from the table, not from a function returning its rows
+    fn to_returning_columns_clause(&self) -> String {
+        self.returning_columns().join(", ")
+    }
+
+    /// Quoted names of the columns in `to_returning_columns_clause`
+    fn returning_columns(&self) -> Vec<String> {
+        let mut frags: Vec<String> = self
+            .columns
+            .iter()
+            .filter(|x| x.permissions.is_selectable)
+            .map(|x| quote_ident(&x.name))
+            .collect();
+        if let Some(VersionSource::Xmin) = self.version_source() {
+            frags.push("xmin".to_string());
+        }
+        frags
+    }
+
+    /// Clause restricting rows to those at the expected version, or "true" when the
+    /// mutation did not provide an `expectedVersion`
+    fn to_version_clause(
+        &self,
+        block_name: &str,
+        expected_version: &Option<serde_json::Value>,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<Option<String>> {
+        match (expected_version, self.version_source()) {
+            (Some(expected_version), Some(version_source)) => {
+                let val_clause = param_context.clause_for(expected_version, "text")?;
+                Ok(Some(format!(
+                    "{} = {val_clause}",
+                    version_source.to_sql(block_name)
+                )))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Clause excluding soft deleted rows, or "true" when the table has no
+    /// `soft_delete` directive or deleted rows were requested
+    fn to_soft_delete_clause(&self, block_name: &str, include_deleted: bool) -> String {
+        match self.soft_delete_column() {
+            Some(column) if !include_deleted => {
+                format!("{block_name}.{} is null", quote_ident(&column.name))
+            }
+            _ => "true".to_string(),
+        }
+    }
+
     /// a priamry key tuple clause selects the columns of the primary key as a composite record
     /// that is useful in "has_previous_page" by letting us compare records on a known unique key
     fn to_primary_key_tuple_clause(&self, block_name: &str) -> String {
@@ -131,24 +210,25 @@ impl Table {
         format!("({})", pkey_frags.join(","))
     }
 
-    fn to_cursor_clause(&self, block_name: &str, order_by: &OrderByBuilder) -> String {
+    fn to_cursor_clause(
+        &self,
+        block_name: &str,
+        order_by: &OrderByBuilder,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<String> {
         let frags: Vec<String> = order_by
-            .elems
+            .to_sort_keys(block_name, param_context)?
             .iter()
-            .map(|x| {
-                let quoted_col_name = quote_ident(&x.column.name);
-                format!("to_jsonb({block_name}.{quoted_col_name})")
-            })
+            .map(|sort_key| format!("to_jsonb({sort_key})"))
             .collect();
 
         let clause = frags.join(", ");
 
-        format!(
+        param_context.sign_token_clause(TokenPurpose::Cursor, format!(
             "translate(encode(convert_to(jsonb_build_array({clause})::text, 'utf-8'), 'base64'), E'\n', '')"
-        )
+        ))
     }
 
-    #[allow(clippy::only_used_in_recursion)]
     fn to_pagination_clause(
         &self,
         block_name: &str,
@@ -156,6 +236,28 @@ impl Table {
         cursor: &Cursor,
         param_context: &mut ParamContext,
         allow_equality: bool,
+    ) -> GraphQLResult<String> {
+        if cursor.elems.len() > order_by.elems.len() {
+            return Err(GraphQLError::validation(
+                "orderBy clause incompatible with pagination cursor",
+            ));
+        }
+        let sort_keys = order_by.to_sort_keys(block_name, param_context)?;
+        Self::to_pagination_clause_for_keys(
+            &sort_keys,
+            &order_by.elems,
+            &cursor.elems,
+            param_context,
+            allow_equality,
+        )
+    }
+
+    fn to_pagination_clause_for_keys(
+        sort_keys: &[String],
+        order_elems: &[OrderByBuilderElem],
+        cursor_elems: &[CursorElement],
+        param_context: &mut ParamContext,
+        allow_equality: bool,
     ) -> GraphQLResult<String> {
         // When paginating, allowe_equality should be false because we don't want to
         // include the cursor's record in the page
@@ -172,31 +274,20 @@ impl Table {
 
         )"
         */
-        if cursor.elems.is_empty() {
-            return Ok(format!("{allow_equality}"));
-        }
-        let mut next_cursor = cursor.clone();
-        let cursor_elem = next_cursor.elems.remove(0);
-
-        if order_by.elems.is_empty() {
-            return Err(GraphQLError::validation(
-                "orderBy clause incompatible with pagination cursor",
-            ));
-        }
-        let mut next_order_by = order_by.clone();
-        let order_elem = next_order_by.elems.remove(0);
-
-        let column = order_elem.column;
-        let quoted_col = quote_ident(&column.name);
-
-        let val = cursor_elem.value;
+        let (cursor_elem, next_cursor_elems) = match cursor_elems.split_first() {
+            Some(x) => x,
+            None => return Ok(format!("{allow_equality}")),
+        };
+        let order_elem = &order_elems[0];
+        let sort_key = &sort_keys[0];
 
-        let val_clause = param_context.clause_for(&val, &column.type_name)?;
+        let val_clause =
+            param_context.clause_for(&cursor_elem.value, &order_elem.key.type_name())?;
 
-        let recurse_clause = self.to_pagination_clause(
-            block_name,
-            &next_order_by,
-            &next_cursor,
+        let recurse_clause = Self::to_pagination_clause_for_keys(
+            &sort_keys[1..],
+            &order_elems[1..],
+            next_cursor_elems,
             param_context,
             allow_equality,
         )?;
@@ -209,8 +300,8 @@ impl Table {
         };
 
         Ok(format!("(
-            ( {block_name}.{quoted_col} {op} {val_clause}  or ( {block_name}.{quoted_col} is not null and {val_clause} is null and {nulls_first}))
-            or (( {block_name}.{quoted_col} = {val_clause} or ( {block_name}.{quoted_col} is null and {val_clause} is null)) and  {recurse_clause})
+            ( {sort_key} {op} {val_clause}  or ( {sort_key} is not null and {val_clause} is null and {nulls_first}))
+            or (( {sort_key} = {val_clause} or ( {sort_key} is null and {val_clause} is null)) and  {recurse_clause})
 
         )"))
     }
@@ -222,6 +313,32 @@ impl Table {
         quoted_block_name: &str,
         quoted_parent_block_name: &str,
     ) -> GraphQLResult<String> {
+        self.to_join_clause_with(
+            fkey,
+            reverse_reference,
+            quoted_block_name,
+            |_, parent_col| {
+                Ok(format!(
+                    "{}.{}",
+                    quoted_parent_block_name,
+                    quote_ident(parent_col)
+                ))
+            },
+        )
+    }
+
+    /// Join clause where `parent_col_clause` references a column of the parent given the
+    /// names of the local and parent columns
+    fn to_join_clause_with<F>(
+        &self,
+        fkey: &ForeignKey,
+        reverse_reference: bool,
+        quoted_block_name: &str,
+        mut parent_col_clause: F,
+    ) -> GraphQLResult<String>
+    where
+        F: FnMut(&str, &str) -> GraphQLResult<String>,
+    {
         let mut equality_clauses = vec!["true".to_string()];
 
         let table_ref: &ForeignKeyTableInfo;
@@ -243,11 +360,7 @@ impl Table {
             .iter()
             .zip(foreign_ref.column_names.iter())
         {
-            let quoted_parent_literal_col = format!(
-                "{}.{}",
-                quoted_parent_block_name,
-                quote_ident(parent_col_name)
-            );
+            let quoted_parent_literal_col = parent_col_clause(local_col_name, parent_col_name)?;
             let quoted_local_literal_col =
                 format!("{}.{}", quoted_block_name, quote_ident(local_col_name));
 
@@ -274,7 +387,7 @@ impl MutationEntrypoint<'_> for InsertBuilder {
             .map(|x| x.to_sql(&quoted_block_name, param_context))
             .collect::<Result<Vec<_>, _>>()?;
 
-        let selectable_columns_clause = self.table.to_selectable_columns_clause();
+        let selectable_columns_clause = self.table.to_returning_columns_clause();
 
         let select_clause = frags.join(", ");
 
@@ -411,6 +524,44 @@ impl DeleteSelection {
     }
 }
 
+/// Builds the pieces of an update or delete statement that enforce `expectedVersion`.
+///
+/// Returns the where clause for the modifying statement, an extra CTE counting the records
+/// that match the filter regardless of version, and a case branch raising a conflict when
+/// fewer records were affected than matched. Comparing counts, rather than looking for
+/// mismatched versions, also catches records changed by a concurrent transaction after
+/// the statement's snapshot was taken.
+fn to_conflict_clauses(
+    where_clause: &str,
+    version_clause: &Option<String>,
+    quoted_schema: &str,
+    quoted_table: &str,
+    quoted_block_name: &str,
+    operation: &str,
+) -> (String, String, String) {
+    match version_clause {
+        None => (where_clause.to_string(), "".to_string(), "".to_string()),
+        Some(version_clause) => (
+            format!("{where_clause} and {version_clause}"),
+            format!(
+                "
+        expected(expected_count) as (
+            select
+                count(*)
+            from
+                {quoted_schema}.{quoted_table} as {quoted_block_name}
+            where
+                {where_clause}
+        ),"
+            ),
+            format!(
+                "
+                    when total.total_count < (select expected_count from expected) then graphql.exception_conflict($a${operation} conflicts with a concurrent change, record version does not match expectedVersion$a$)::jsonb"
+            ),
+        ),
+    }
+}
+
 impl MutationEntrypoint<'_> for UpdateBuilder {
     fn to_sql_entrypoint(&self, param_context: &mut ParamContext) -> GraphQLResult<String> {
         let quoted_block_name = rand_block_name();
@@ -445,20 +596,35 @@ impl MutationEntrypoint<'_> for UpdateBuilder {
             set_clause_frags.join(", ")
         };
 
-        let selectable_columns_clause = self.table.to_selectable_columns_clause();
+        let selectable_columns_clause = self.table.to_returning_columns_clause();
 
         let where_clause =
             self.filter
                 .to_where_clause(&quoted_block_name, &self.table, param_context)?;
 
+        let version_clause = self.table.to_version_clause(
+            &quoted_block_name,
+            &self.expected_version,
+            param_context,
+        )?;
+
         let at_most = self.at_most;
 
+        let (update_where_clause, conflict_cte, conflict_case) = to_conflict_clauses(
+            &where_clause,
+            &version_clause,
+            &quoted_schema,
+            &quoted_table,
+            &quoted_block_name,
+            "update",
+        );
+
         Ok(format!(
             "
         with impacted as (
             update {quoted_schema}.{quoted_table} as {quoted_block_name}
             set {set_clause}
-            where {where_clause}
+            where {update_where_clause}
             returning {selectable_columns_clause}
         ),
         total(total_count) as (
@@ -466,7 +632,7 @@ impl MutationEntrypoint<'_> for UpdateBuilder {
                 count(*)
             from
                 impacted
-        ),
+        ),{conflict_cte}
         req(res) as (
             select
                 jsonb_build_object({select_clause})
@@ -477,7 +643,7 @@ impl MutationEntrypoint<'_> for UpdateBuilder {
         wrapper(res) as (
             select
                 case
-                    when total.total_count > {at_most} then graphql.exception($a$update impacts too many records$a$)::jsonb
+                    when total.total_count > {at_most} then graphql.exception($a$update impacts too many records$a$)::jsonb{conflict_case}
                     else req.res
                 end
             from
@@ -511,16 +677,42 @@ impl MutationEntrypoint<'_> for DeleteBuilder {
         let where_clause =
             self.filter
                 .to_where_clause(&quoted_block_name, &self.table, param_context)?;
+        let soft_delete_clause = self.table.to_soft_delete_clause(&quoted_block_name, false);
+        let where_clause = format!("{where_clause} and {soft_delete_clause}");
 
-        let selectable_columns_clause = self.table.to_selectable_columns_clause();
+        let version_clause = self.table.to_version_clause(
+            &quoted_block_name,
+            &self.expected_version,
+            param_context,
+        )?;
+
+        let selectable_columns_clause = self.table.to_returning_columns_clause();
 
         let at_most = self.at_most;
 
+        // Soft deleted tables mark records as deleted instead of removing them
+        let delete_clause = match self.table.soft_delete_column() {
+            Some(column) => format!(
+                "update {quoted_schema}.{quoted_table} as {quoted_block_name} set {} = now()",
+                quote_ident(&column.name)
+            ),
+            None => format!("delete from {quoted_schema}.{quoted_table} as {quoted_block_name}"),
+        };
+
+        let (delete_where_clause, conflict_cte, conflict_case) = to_conflict_clauses(
+            &where_clause,
+            &version_clause,
+            &quoted_schema,
+            &quoted_table,
+            &quoted_block_name,
+            "delete",
+        );
+
         Ok(format!(
             "
         with impacted as (
-            delete from {quoted_schema}.{quoted_table} as {quoted_block_name}
-            where {where_clause}
+            {delete_clause}
+            where {delete_where_clause}
             returning {selectable_columns_clause}
         ),
         total(total_count) as (
@@ -528,7 +720,7 @@ impl MutationEntrypoint<'_> for DeleteBuilder {
                 count(*)
             from
                 impacted
-        ),
+        ),{conflict_cte}
         req(res) as (
             select
                 jsonb_build_object({select_clause})
@@ -539,7 +731,7 @@ impl MutationEntrypoint<'_> for DeleteBuilder {
         wrapper(res) as (
             select
                 case
-                    when total.total_count > {at_most} then graphql.exception($a$delete impacts too many records$a$)::jsonb
+                    when total.total_count > {at_most} then graphql.exception($a$delete impacts too many records$a$)::jsonb{conflict_case}
                     else req.res
                 end
             from
@@ -602,6 +794,55 @@ impl FunctionCallBuilder {
                 )?;
                 select_clause.to_string()
             }
+            FuncCallReturnTypeBuilder::Polymorphic(polymorphic_builder) => {
+                // The first returned column names the member table and the rest hold its primary key
+                let (type_column, key_columns) = self
+                    .function
+                    .return_columns
+                    .as_deref()
+                    .and_then(|x| x.split_first())
+                    .ok_or("Polymorphic function without return columns")?;
+
+                let mut case_clauses = vec![];
+                for (table, node_builder) in &polymorphic_builder.members {
+                    let member_block_name = rand_block_name();
+                    let select_clause = node_builder.to_sql(&member_block_name, param_context)?;
+                    let select_clause = if select_clause.is_empty() {
+                        "jsonb_build_object()".to_string()
+                    } else {
+                        select_clause
+                    };
+                    let key_clause = table
+                        .primary_key_columns()
+                        .iter()
+                        .zip(key_columns)
+                        .map(|(pkey_col, key_col)| {
+                            format!(
+                                "{member_block_name}.{} = ({block_name}.{})::{}",
+                                quote_ident(&pkey_col.name),
+                                quote_ident(&key_col.name),
+                                pkey_col.type_name
+                            )
+                        })
+                        .join(" and ");
+                    let soft_delete_clause = table.to_soft_delete_clause(
+                        &member_block_name,
+                        polymorphic_builder.include_deleted,
+                    );
+                    case_clauses.push(format!(
+                        "when {} then (select {select_clause} from {}.{} {member_block_name} where {key_clause} and {soft_delete_clause})",
+                        quote_literal(&table.name),
+                        quote_ident(&table.schema),
+                        quote_ident(&table.name),
+                    ));
+                }
+                let case_clause = case_clauses.join(" ");
+                let type_column = quote_ident(&type_column.name);
+
+                format!(
+                    "select coalesce(jsonb_agg(__poly.obj order by __poly.ix), jsonb_build_array()) from (select {block_name}.ordinality as ix, case {block_name}.{type_column}::text {case_clause} end as obj from {func_schema}.{func_name}{args_clause} with ordinality {block_name}) __poly where __poly.obj is not null;"
+                )
+            }
         };
 
         Ok(query)
@@ -620,22 +861,148 @@ impl QueryEntrypoint for FunctionCallBuilder {
     }
 }
 
+impl OrderByKey {
+    /// The sort key expression for a record of the ordered table aliased as `block_name`
+    fn to_sql(&self, block_name: &str, param_context: &mut ParamContext) -> GraphQLResult<String> {
+        Ok(match self {
+            Self::Column(column) => format!("{block_name}.{}", quote_ident(&column.name)),
+            Self::SearchRank { column, query } => {
+                let query_clause =
+                    param_context.clause_for(&serde_json::Value::String(query.clone()), "text")?;
+                format!(
+                    "ts_rank({}, {})",
+                    column.to_tsvector_clause(block_name),
+                    column.to_tsquery_clause(&query_clause)
+                )
+            }
+            Self::Relation { fkey, table, key } => {
+                let related_block_name = rand_block_name();
+                let join_clause = table.to_join_clause(
+                    &fkey.fkey,
+                    fkey.reverse_reference,
+                    &related_block_name,
+                    block_name,
+                )?;
+                let soft_delete_clause = table.to_soft_delete_clause(&related_block_name, false);
+                format!(
+                    "(select {} from {}.{} as {related_block_name} where {join_clause} and {soft_delete_clause} limit 1)",
+                    key.to_sql(&related_block_name, param_context)?,
+                    quote_ident(&table.schema),
+                    quote_ident(&table.name),
+                )
+            }
+            Self::Count { fkey, table } => {
+                let related_block_name = rand_block_name();
+                let join_clause = table.to_join_clause(
+                    &fkey.fkey,
+                    fkey.reverse_reference,
+                    &related_block_name,
+                    block_name,
+                )?;
+                let soft_delete_clause = table.to_soft_delete_clause(&related_block_name, false);
+                format!(
+                    "(select count(*) from {}.{} as {related_block_name} where {join_clause} and {soft_delete_clause})",
+                    quote_ident(&table.schema),
+                    quote_ident(&table.name),
+                )
+            }
+        })
+    }
+
+    /// Postgres type of the sort key, used to cast cursor values
+    fn type_name(&self) -> String {
+        match self {
+            Self::Column(column) => column.type_name.clone(),
+            Self::SearchRank { .. } => "real".to_string(),
+            Self::Relation { key, .. } => key.type_name(),
+            Self::Count { .. } => "bigint".to_string(),
+        }
+    }
+}
+
 impl OrderByBuilder {
-    fn to_order_by_clause(&self, block_name: &str) -> String {
+    /// Alias of the lateral join holding the relation sort keys of `block_name`
+    fn order_keys_block_name(block_name: &str) -> String {
+        quote_ident(&format!("{}_order_keys", block_name.trim_matches('"')))
+    }
+
+    /// Lateral join computing each relation sort key once per record of `block_name`.
+    /// Must accompany `block_name` wherever `to_sort_keys` or `to_order_by_clause` is used
+    fn to_order_keys_join_clause(
+        &self,
+        block_name: &str,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<String> {
+        let mut frags = vec![];
+
+        for (ix, elem) in self.elems.iter().enumerate() {
+            if let OrderByKey::Relation { .. } = elem.key {
+                frags.push(format!(
+                    "{} as __order_key_{ix}",
+                    elem.key.to_sql(block_name, param_context)?
+                ));
+            }
+        }
+
+        if frags.is_empty() {
+            return Ok("".to_string());
+        }
+        Ok(format!(
+            "left join lateral (select {}) {} on true",
+            frags.join(", "),
+            Self::order_keys_block_name(block_name)
+        ))
+    }
+
+    /// Sort key expressions, reading relation keys from the lateral order keys join
+    fn to_sort_keys(
+        &self,
+        block_name: &str,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<Vec<String>> {
+        let mut sort_keys = vec![];
+
+        for (ix, elem) in self.elems.iter().enumerate() {
+            sort_keys.push(match elem.key {
+                OrderByKey::Relation { .. } => format!(
+                    "{}.__order_key_{ix}",
+                    Self::order_keys_block_name(block_name)
+                ),
+                _ => elem.key.to_sql(block_name, param_context)?,
+            });
+        }
+        Ok(sort_keys)
+    }
+
+    fn to_order_by_clause(
+        &self,
+        block_name: &str,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<String> {
+        let frags: Vec<String> = self
+            .to_sort_keys(block_name, param_context)?
+            .iter()
+            .zip(&self.elems)
+            .map(|(sort_key, elem)| format!("{sort_key} {}", elem.direction.to_sql()))
+            .collect();
+        Ok(frags.join(", "))
+    }
+
+    /// Order by clause with relation sort keys inlined, for use without the order keys join
+    fn to_inline_order_by_clause(
+        &self,
+        block_name: &str,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<String> {
         let mut frags = vec![];
 
         for elem in &self.elems {
-            let quoted_column_name = quote_ident(&elem.column.name);
-            let direction_clause = match elem.direction {
-                OrderDirection::AscNullsFirst => "asc nulls first",
-                OrderDirection::AscNullsLast => "asc nulls last",
-                OrderDirection::DescNullsFirst => "desc nulls first",
-                OrderDirection::DescNullsLast => "desc nulls last",
-            };
-            let elem_clause = format!("{block_name}.{quoted_column_name} {direction_clause}");
+            let sort_key = elem.key.to_sql(block_name, param_context)?;
+            let direction_clause = elem.direction.to_sql();
+            let elem_clause = format!("{sort_key} {direction_clause}");
             frags.push(elem_clause)
         }
-        frags.join(", ")
+        Ok(frags.join(", "))
     }
 }
 
@@ -675,8 +1042,13 @@ pub fn json_to_text_datum(val: &serde_json::Value) -> GraphQLResult<Option<pg_sy
     }
 }
 
+#[derive(Default)]
 pub struct ParamContext<'src> {
     pub params: Vec<DatumWithOid<'src>>,
+    // Reference to the signing secret, pushed on first use
+    signing_secret_clause: Option<String>,
+    // Values referenced by a query text that is run later, see `deferred_query`
+    deferred_args: Option<Vec<String>>,
 }
 
 impl<'src> ParamContext<'src> {
@@ -691,8 +1063,180 @@ impl<'src> ParamContext<'src> {
         let val_datum = json_to_text_datum(value)?;
         let datum_with_oid = unsafe { DatumWithOid::new(val_datum, type_oid.value()) };
         self.params.push(datum_with_oid);
-        Ok(format!("(${}::{})", self.params.len(), type_name))
+        let param_clause = format!("${}", self.params.len());
+
+        match self.deferred_args.is_some() {
+            true => self.deferred_arg_clause(format!("{param_clause}::text"), type_name),
+            false => Ok(format!("({param_clause}::{type_name})")),
+        }
     }
+
+    // Passes a value of the enclosing query to a deferred query and returns a SQL clause
+    // to reference it from within that query's text
+    fn deferred_arg_clause(
+        &mut self,
+        value_clause: String,
+        type_name: &str,
+    ) -> GraphQLResult<String> {
+        let args = self
+            .deferred_args
+            .as_mut()
+            .ok_or("Internal Error: deferred argument outside of a deferred query")?;
+        args.push(value_clause);
+        Ok(format!("(($1)[{}]::{})", args.len(), type_name))
+    }
+
+    // Renders a query as text to be run later, e.g. by EXPLAIN, rather than inlined into the
+    // statement. Values are bound through a single text[] argument instead of the statement's
+    // parameters. Returns the quoted query text and the clause for its argument
+    fn deferred_query<F>(&mut self, to_query: F) -> GraphQLResult<(String, String)>
+    where
+        F: FnOnce(&mut Self) -> GraphQLResult<String>,
+    {
+        let outer_args = self.deferred_args.replace(vec![]);
+        let query = to_query(self);
+        let args = std::mem::replace(&mut self.deferred_args, outer_args).unwrap_or_default();
+
+        Ok((
+            quote_literal(&query?),
+            format!("array[{}]::text[]", args.join(", ")),
+        ))
+    }
+
+    // Wraps a cursor or nodeId clause so it is signed when a signing secret is configured
+    fn sign_token_clause(
+        &mut self,
+        purpose: TokenPurpose,
+        token_clause: String,
+    ) -> GraphQLResult<String> {
+        let secret_clause = match &self.signing_secret_clause {
+            Some(secret_clause) => secret_clause.clone(),
+            None => match signing::signing_secret() {
+                // The secret is bound as a parameter so it never appears in the query text
+                Some(secret) => {
+                    let secret_clause =
+                        self.clause_for(&serde_json::Value::String(secret), "text")?;
+                    self.signing_secret_clause = Some(secret_clause.clone());
+                    secret_clause
+                }
+                None => return Ok(token_clause),
+            },
+        };
+        Ok(format!(
+            "graphql._sign_token({token_clause}, {}, {secret_clause})",
+            quote_literal(purpose.tag())
+        ))
+    }
+}
+
+/// Builds a boolean expression applying a scalar comparison `op` to `lhs`
+fn comparison_sql(
+    lhs: &str,
+    type_name: &str,
+    op: &FilterOp,
+    value: &serde_json::Value,
+    param_context: &mut ParamContext,
+) -> GraphQLResult<String> {
+    Ok(match op {
+        FilterOp::Is => {
+            format!(
+                "{lhs} {}",
+                match value {
+                    serde_json::Value::String(x) => {
+                        match x.as_str() {
+                            "NULL" => "is null",
+                            "NOT_NULL" => "is not null",
+                            _ => {
+                                return Err(GraphQLError::sql_generation(
+                                    "Error transpiling Is filter value",
+                                ));
+                            }
+                        }
+                    }
+                    _ => {
+                        return Err(GraphQLError::sql_generation(
+                            "Error transpiling Is filter value type",
+                        ));
+                    }
+                }
+            )
+        }
+        FilterOp::Between => {
+            let val_clause = param_context.clause_for(value, &format!("{type_name}[]"))?;
+            format!("{lhs} between {val_clause}[1] and {val_clause}[2]")
+        }
+        FilterOp::IEqual | FilterOp::IStartsWith => {
+            let val_clause = param_context.clause_for(value, type_name)?;
+            format!(
+                "lower({lhs}) {} lower({val_clause})",
+                match op {
+                    FilterOp::IEqual => "=",
+                    _ => "^@",
+                }
+            )
+        }
+        FilterOp::EndsWith => {
+            let val_clause = param_context.clause_for(value, type_name)?;
+            format!("right({lhs}, char_length({val_clause})) = {val_clause}")
+        }
+        _ => {
+            let cast_type_name = match op {
+                FilterOp::In => format!("{type_name}[]"),
+                FilterOp::NotIn => format!("{type_name}[]"),
+                FilterOp::Contains => format!("{type_name}[]"),
+                FilterOp::ContainedBy => format!("{type_name}[]"),
+                FilterOp::Overlap => format!("{type_name}[]"),
+                _ => type_name.to_string(),
+            };
+
+            let val_clause = param_context.clause_for(value, &cast_type_name)?;
+
+            format!(
+                "{lhs} {} {}",
+                match op {
+                    FilterOp::Equal => "=",
+                    FilterOp::NotEqual => "<>",
+                    FilterOp::LessThan => "<",
+                    FilterOp::LessThanEqualTo => "<=",
+                    FilterOp::GreaterThan => ">",
+                    FilterOp::GreaterThanEqualTo => ">=",
+                    FilterOp::In => "= any",
+                    FilterOp::NotIn => "<> all",
+                    FilterOp::DistinctFrom => "is distinct from",
+                    FilterOp::NotDistinctFrom => "is not distinct from",
+                    FilterOp::StartsWith => "^@",
+                    FilterOp::Like => "like",
+                    FilterOp::NotLike => "not like",
+                    FilterOp::ILike => "ilike",
+                    FilterOp::NotILike => "not ilike",
+                    FilterOp::RegEx => "~",
+                    FilterOp::IRegEx => "~*",
+                    FilterOp::Contains => "@>",
+                    FilterOp::ContainedBy => "<@",
+                    FilterOp::Overlap => "&&",
+                    FilterOp::Is
+                    | FilterOp::Matches
+                    | FilterOp::Between
+                    | FilterOp::IEqual
+                    | FilterOp::IStartsWith
+                    | FilterOp::EndsWith
+                    | FilterOp::HasKey
+                    | FilterOp::HasAnyKeys
+                    | FilterOp::HasAllKeys
+                    | FilterOp::JsonPath
+                    | FilterOp::AnyEqual
+                    | FilterOp::AnyIn
+                    | FilterOp::AnyStartsWith
+                    | FilterOp::AnyILike
+                    | FilterOp::Length
+                    | FilterOp::IsEmpty => {
+                        return Err(GraphQLError::sql_generation("Error transpiling Is filter"));
+                    }
+                },
+                val_clause
+            )
+        }
+    })
 }
 
 impl FilterBuilderElem {
@@ -704,77 +1248,138 @@ impl FilterBuilderElem {
     ) -> GraphQLResult<String> {
         match self {
             Self::Column { column, op, value } => {
+                let column_clause = format!("{block_name}.{}", quote_ident(&column.name));
                 let frag = match op {
-                    FilterOp::Is => {
+                    FilterOp::Contains
+                    | FilterOp::ContainedBy
+                    | FilterOp::HasKey
+                    | FilterOp::HasAnyKeys
+                    | FilterOp::HasAllKeys
+                    | FilterOp::JsonPath
+                        if column.is_json() =>
+                    {
+                        let (operator, cast_type_name) = match op {
+                            FilterOp::Contains => ("@>", "jsonb"),
+                            FilterOp::ContainedBy => ("<@", "jsonb"),
+                            FilterOp::HasKey => ("?", "text"),
+                            FilterOp::HasAnyKeys => ("?|", "text[]"),
+                            FilterOp::HasAllKeys => ("?&", "text[]"),
+                            _ => ("@?", "jsonpath"),
+                        };
+                        let val_clause = param_context.clause_for(value, cast_type_name)?;
+                        // json columns are compared as jsonb, which the operators require
+                        format!("{column_clause}::jsonb {operator} {val_clause}")
+                    }
+                    FilterOp::Matches => {
+                        let val_clause = param_context.clause_for(value, "text")?;
                         format!(
-                            "{block_name}.{} {}",
-                            quote_ident(&column.name),
-                            match value {
-                                serde_json::Value::String(x) => {
-                                    match x.as_str() {
-                                        "NULL" => "is null",
-                                        "NOT_NULL" => "is not null",
-                                        _ => {
-                                            return Err(GraphQLError::sql_generation(
-                                                "Error transpiling Is filter value",
-                                            ));
-                                        }
+                            "{} @@ {}",
+                            column.to_tsvector_clause(block_name),
+                            column.to_tsquery_clause(&val_clause)
+                        )
+                    }
+                    FilterOp::AnyEqual
+                    | FilterOp::AnyIn
+                    | FilterOp::AnyStartsWith
+                    | FilterOp::AnyILike => {
+                        let element_type_name = column
+                            .type_name
+                            .strip_suffix("[]")
+                            .unwrap_or(&column.type_name);
+                        match op {
+                            FilterOp::AnyEqual => {
+                                let val_clause =
+                                    param_context.clause_for(value, element_type_name)?;
+                                format!("{val_clause} = any({column_clause})")
+                            }
+                            FilterOp::AnyIn => {
+                                let val_clause = param_context
+                                    .clause_for(value, &format!("{element_type_name}[]"))?;
+                                format!("{column_clause} && {val_clause}")
+                            }
+                            _ => {
+                                let val_clause = param_context.clause_for(value, "text")?;
+                                format!(
+                                    "exists(select 1 from unnest({column_clause}) as __elem(val) where __elem.val {} {val_clause})",
+                                    match op {
+                                        FilterOp::AnyStartsWith => "^@",
+                                        _ => "ilike",
                                     }
-                                }
-                                _ => {
-                                    return Err(GraphQLError::sql_generation(
-                                        "Error transpiling Is filter value type",
-                                    ));
-                                }
+                                )
                             }
-                        )
+                        }
                     }
+                    FilterOp::IsEmpty => match value {
+                        serde_json::Value::Bool(true) => {
+                            format!("cardinality({column_clause}) = 0")
+                        }
+                        serde_json::Value::Bool(false) => {
+                            format!("cardinality({column_clause}) > 0")
+                        }
+                        _ => {
+                            return Err(GraphQLError::sql_generation(
+                                "Error transpiling isEmpty filter value",
+                            ));
+                        }
+                    },
                     _ => {
-                        let cast_type_name = match op {
-                            FilterOp::In => format!("{}[]", column.type_name),
-                            FilterOp::Contains => format!("{}[]", column.type_name),
-                            FilterOp::ContainedBy => format!("{}[]", column.type_name),
-                            FilterOp::Overlap => format!("{}[]", column.type_name),
-                            _ => column.type_name.clone(),
-                        };
-
-                        let val_clause = param_context.clause_for(value, &cast_type_name)?;
-
-                        format!(
-                            "{block_name}.{} {} {}",
-                            quote_ident(&column.name),
-                            match op {
-                                FilterOp::Equal => "=",
-                                FilterOp::NotEqual => "<>",
-                                FilterOp::LessThan => "<",
-                                FilterOp::LessThanEqualTo => "<=",
-                                FilterOp::GreaterThan => ">",
-                                FilterOp::GreaterThanEqualTo => ">=",
-                                FilterOp::In => "= any",
-                                FilterOp::StartsWith => "^@",
-                                FilterOp::Like => "like",
-                                FilterOp::ILike => "ilike",
-                                FilterOp::RegEx => "~",
-                                FilterOp::IRegEx => "~*",
-                                FilterOp::Contains => "@>",
-                                FilterOp::ContainedBy => "<@",
-                                FilterOp::Overlap => "&&",
-                                FilterOp::Is => {
-                                    return Err(GraphQLError::sql_generation(
-                                        "Error transpiling Is filter",
-                                    ));
-                                }
-                            },
-                            val_clause
-                        )
+                        comparison_sql(&column_clause, &column.type_name, op, value, param_context)?
                     }
                 };
                 Ok(frag)
             }
+            Self::Cardinality { column, op, value } => comparison_sql(
+                &format!("cardinality({block_name}.{})", quote_ident(&column.name)),
+                "int",
+                op,
+                value,
+                param_context,
+            ),
             Self::NodeId(node_id) => node_id.to_sql(block_name, table, param_context),
             FilterBuilderElem::Compound(compound_builder) => {
                 compound_builder.to_sql(block_name, table, param_context)
             }
+            Self::Relation {
+                fkey,
+                table: related_table,
+                quantifier,
+                filter,
+            } => {
+                let related_block_name = rand_block_name();
+                let quoted_schema = quote_ident(&related_table.schema);
+                let quoted_table = quote_ident(&related_table.name);
+
+                let join_clause = related_table.to_join_clause(
+                    &fkey.fkey,
+                    fkey.reverse_reference,
+                    &related_block_name,
+                    block_name,
+                )?;
+                let soft_delete_clause =
+                    related_table.to_soft_delete_clause(&related_block_name, false);
+                let where_clause =
+                    filter.to_where_clause(&related_block_name, related_table, param_context)?;
+
+                // Subqueries select from the related table directly so row level security applies
+                let subquery = |clause: String| {
+                    format!(
+                        "exists(select 1 from {quoted_schema}.{quoted_table} as {related_block_name} where {join_clause} and {soft_delete_clause} and {clause})"
+                    )
+                };
+
+                Ok(match quantifier {
+                    RelationFilterQuantifier::One | RelationFilterQuantifier::Some => {
+                        subquery(format!("({where_clause})"))
+                    }
+                    RelationFilterQuantifier::None => {
+                        format!("not {}", subquery(format!("({where_clause})")))
+                    }
+                    RelationFilterQuantifier::Every => format!(
+                        "not {}",
+                        subquery(format!("not coalesce(({where_clause}), false)"))
+                    ),
+                })
+            }
         }
     }
 }
@@ -823,42 +1428,502 @@ impl FilterBuilder {
         }
         Ok(frags.join(" and "))
     }
-}
+}
+
+pub struct FromFunction {
+    function: Arc<Function>,
+    input_table: Arc<Table>,
+    // The block name for the functions argument
+    input_block_name: String,
+}
+
+impl ConnectionBuilder {
+    fn page_selections(&self) -> Vec<PageInfoSelection> {
+        self.selections
+            .iter()
+            .flat_map(|x| match x {
+                ConnectionSelection::PageInfo(page_info_builder) => {
+                    page_info_builder.selections.clone()
+                }
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    fn requested_next_page(&self) -> bool {
+        self.page_selections()
+            .iter()
+            .any(|x| matches!(&x, PageInfoSelection::HasNextPage { alias: _ }))
+    }
+
+    fn requested_previous_page(&self) -> bool {
+        self.page_selections()
+            .iter()
+            .any(|x| matches!(&x, PageInfoSelection::HasPreviousPage { alias: _ }))
+    }
+
+    fn is_reverse_pagination(&self) -> bool {
+        self.last.is_some() || self.before.is_some()
+    }
+
+    fn requested_total_count(&self) -> bool {
+        self.selections
+            .iter()
+            .any(|x| matches!(&x, ConnectionSelection::TotalCount { alias: _ }))
+    }
+
+    fn requested_total_count_estimate(&self) -> bool {
+        self.selections
+            .iter()
+            .any(|x| matches!(&x, ConnectionSelection::TotalCountEstimate { alias: _ }))
+    }
+
+    /// Query for the planner's estimate of the number of records matching the filters.
+    /// The query is planned, never executed, by EXPLAIN at execution time so the estimate
+    /// reflects the filter values and the parent record of nested connections
+    fn to_total_count_estimate_query(
+        &self,
+        quoted_parent_block_name: &Option<&str>,
+        param_context: &mut ParamContext,
+        from_func: &Option<FromFunction>,
+        from_clause: &Option<String>,
+        count_query: &str,
+    ) -> GraphQLResult<String> {
+        // Rows returned by a function, or linked through a junction table, can not be
+        // planned apart from the parent record so they are counted instead
+        if from_func.is_some() || from_clause.is_some() || self.source.junction.is_some() {
+            return Ok(count_query.to_string());
+        }
+
+        let table = &self.source.table;
+
+        // Every row of the table matches so the row estimate kept in pg_class is read
+        // directly. reltuples is -1 until the table is first vacuumed or analyzed
+        if self.filter.elems.is_empty()
+            && self.source.fkey.is_none()
+            && self.distinct_on.is_empty()
+            && matches!(table.relkind.as_str(), "r" | "m")
+        {
+            return Ok(format!(
+                "select greatest(reltuples, 0)::bigint from pg_catalog.pg_class where oid = {}",
+                table.oid
+            ));
+        }
+
+        let (query_clause, args_clause) = param_context.deferred_query(|param_context| {
+            let quoted_block_name = rand_block_name();
+            let from_clause = self.from_clause(&quoted_block_name, &None);
+
+            let join_clause = match &self.source.fkey {
+                Some(fkey) => {
+                    let quoted_parent_block_name = quoted_parent_block_name.ok_or(
+                        "Internal Error: Parent block name is required when fkey_ix is set",
+                    )?;
+                    table.to_join_clause_with(
+                        &fkey.fkey,
+                        fkey.reverse_reference,
+                        &quoted_block_name,
+                        |local_col_name, parent_col_name| {
+                            let local_col = table
+                                .columns
+                                .iter()
+                                .find(|c| c.name == local_col_name)
+                                .ok_or_else(|| {
+                                    GraphQLError::internal(format!(
+                                        "Join column {local_col_name} not found"
+                                    ))
+                                })?;
+                            param_context.deferred_arg_clause(
+                                format!(
+                                    "{quoted_parent_block_name}.{}::text",
+                                    quote_ident(parent_col_name)
+                                ),
+                                &local_col.type_name,
+                            )
+                        },
+                    )?
+                }
+                None => "true".to_string(),
+            };
+
+            let where_clause =
+                self.filter
+                    .to_where_clause(&quoted_block_name, table, param_context)?;
+
+            // The planner estimates distinct rows from the column statistics
+            let select_clause = match self.distinct_on.is_empty() {
+                true => "1".to_string(),
+                false => format!(
+                    "distinct {}",
+                    self.distinct_on
+                        .iter()
+                        .map(|col| format!("{quoted_block_name}.{}", quote_ident(&col.name)))
+                        .join(", ")
+                ),
+            };
+
+            Ok(format!(
+                "select {select_clause} from {from_clause} where {join_clause} and {where_clause}"
+            ))
+        })?;
+
+        Ok(format!(
+            "select graphql._estimate_row_count({query_clause}, {args_clause})"
+        ))
+    }
+
+    /// Filter clause for rows of `quoted_block_name`, including the `distinctOn` restriction.
+    /// Restricting the candidate rows, rather than the page, keeps totalCount, pageInfo
+    /// and aggregates consistent with the records returned
+    fn to_source_where_clause(
+        &self,
+        quoted_block_name: &str,
+        quoted_parent_block_name: &Option<&str>,
+        param_context: &mut ParamContext,
+        from_func: &Option<FromFunction>,
+        from_clause: &Option<String>,
+    ) -> GraphQLResult<String> {
+        let where_clause =
+            self.filter
+                .to_where_clause(quoted_block_name, &self.source.table, param_context)?;
+
+        match self.distinct_on.is_empty() {
+            true => Ok(where_clause),
+            false => {
+                let distinct_on_clause = self.to_distinct_on_clause(
+                    quoted_block_name,
+                    quoted_parent_block_name,
+                    param_context,
+                    from_func,
+                    from_clause,
+                )?;
+                Ok(format!("{where_clause} and {distinct_on_clause}"))
+            }
+        }
+    }
+
+    /// Subquery producing a jsonb array with one object per group of matching rows
+    fn to_group_by_clause(
+        &self,
+        group_by: &GroupByBuilder,
+        aggregate_filter: &FilterBuilder,
+        quoted_parent_block_name: &Option<&str>,
+        param_context: &mut ParamContext,
+        from_func: &Option<FromFunction>,
+        from_clause: &Option<String>,
+    ) -> GraphQLResult<String> {
+        let quoted_block_name = rand_block_name();
+
+        let join_clause = self.to_join_clause(&quoted_block_name, quoted_parent_block_name)?;
+        let where_clause = self.to_source_where_clause(
+            &quoted_block_name,
+            quoted_parent_block_name,
+            param_context,
+            from_func,
+            from_clause,
+        )?;
+        let aggregate_where_clause = aggregate_filter.to_where_clause(
+            &quoted_block_name,
+            &self.source.table,
+            param_context,
+        )?;
+        let from_clause = match from_clause {
+            Some(from_clause) => format!("{from_clause} {quoted_block_name}"),
+            None => self.from_clause(&quoted_block_name, from_func),
+        };
+
+        let key_clause = |column: &Column| -> GraphQLResult<String> {
+            group_by
+                .keys
+                .iter()
+                .find(|k| k.column.name == column.name)
+                .ok_or_else(|| GraphQLError::internal("Internal Error: groupBy key not found"))?
+                .to_sql(&quoted_block_name)
+        };
+
+        let group_clause = group_by
+            .keys
+            .iter()
+            .map(|key| key.to_sql(&quoted_block_name))
+            .collect::<GraphQLResult<Vec<String>>>()?
+            .join(", ");
+
+        let order_by_clause = group_by
+            .order_by
+            .iter()
+            .map(|elem| {
+                let sort_key = match &elem.key {
+                    GroupOrderByKey::Count => "count(*)".to_string(),
+                    GroupOrderByKey::Column(column) => key_clause(column)?,
+                };
+                Ok(format!("{sort_key} {}", elem.direction.to_sql()))
+            })
+            .collect::<GraphQLResult<Vec<String>>>()?
+            .join(", ");
+
+        let mut frags = vec![];
+        for selection in &group_by.selections {
+            match selection {
+                GroupSelection::Cursor { alias } => {
+                    let cursor_clause = param_context.sign_token_clause(TokenPurpose::Cursor, format!(
+                        "translate(encode(convert_to(jsonb_build_array(row_number() over (order by {order_by_clause}))::text, 'utf-8'), 'base64'), E'\n', '')"
+                    ))?;
+                    frags.push(format!("{}, {cursor_clause}", quote_literal(alias)))
+                }
+                GroupSelection::Key {
+                    alias,
+                    column_builders,
+                } => {
+                    let key_frags = column_builders
+                        .iter()
+                        .map(|col_builder| {
+                            Ok(format!(
+                                "{}, {}",
+                                quote_literal(&col_builder.alias),
+                                key_clause(&col_builder.column)?
+                            ))
+                        })
+                        .collect::<GraphQLResult<Vec<String>>>()?
+                        .join(", ");
+                    frags.push(format!(
+                        "{}, jsonb_build_object({key_frags})",
+                        quote_literal(alias)
+                    ))
+                }
+                GroupSelection::Aggregate(agg_selection) => {
+                    frags.push(agg_selection.to_sql(&quoted_block_name, "", param_context)?)
+                }
+            }
+        }
+        let object_clause = frags.join(", ");
+
+        let limit = cmp::min(group_by.first.unwrap_or(self.max_rows), self.max_rows);
+        let offset = group_by.after.unwrap_or(0);
+
+        Ok(format!(
+            "(
+                select
+                    coalesce(jsonb_agg(__page.obj order by __page.rn), jsonb_build_array())
+                from
+                    (
+                        select
+                            __groups.rn,
+                            __groups.obj
+                        from
+                            (
+                                select
+                                    row_number() over (order by {order_by_clause}) as rn,
+                                    jsonb_build_object({object_clause}) as obj
+                                from
+                                    {from_clause}
+                                where
+                                    {join_clause}
+                                    and {where_clause}
+                                    and {aggregate_where_clause}
+                                group by
+                                    {group_clause}
+                            ) __groups
+                        where
+                            __groups.rn > {offset}
+                        order by
+                            __groups.rn
+                        limit
+                            {limit}
+                    ) __page
+            )"
+        ))
+    }
+
+    /// Clause limiting `quoted_block_name` to the first row, by the requested
+    /// ordering, of each distinct combination of the `distinctOn` columns
+    fn to_distinct_on_clause(
+        &self,
+        quoted_block_name: &str,
+        quoted_parent_block_name: &Option<&str>,
+        param_context: &mut ParamContext,
+        from_func: &Option<FromFunction>,
+        from_clause: &Option<String>,
+    ) -> GraphQLResult<String> {
+        let table = &self.source.table;
+        let quoted_distinct_block_name = rand_block_name();
+
+        let from_clause = match from_clause {
+            Some(from_clause) => format!("{from_clause} {quoted_distinct_block_name}"),
+            None => self.from_clause(&quoted_distinct_block_name, from_func),
+        };
+        let join_clause =
+            self.to_join_clause(&quoted_distinct_block_name, quoted_parent_block_name)?;
+        let where_clause =
+            self.filter
+                .to_where_clause(&quoted_distinct_block_name, table, param_context)?;
+        let order_by_clause = self
+            .order_by
+            .to_order_by_clause(&quoted_distinct_block_name, param_context)?;
+        let order_keys_join_clause = self
+            .order_by
+            .to_order_keys_join_clause(&quoted_distinct_block_name, param_context)?;
+
+        let distinct_frags = self
+            .distinct_on
+            .iter()
+            .map(|col| format!("{quoted_distinct_block_name}.{}", quote_ident(&col.name)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let pkey_frags = table
+            .primary_key_columns()
+            .iter()
+            .map(|col| format!("{quoted_distinct_block_name}.{}", quote_ident(&col.name)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let pkey_tuple_clause = table.to_primary_key_tuple_clause(quoted_block_name);
+
+        Ok(format!(
+            "{pkey_tuple_clause} in (
+                select distinct on ({distinct_frags})
+                    {pkey_frags}
+                from
+                    {from_clause}
+                    {order_keys_join_clause}
+                where
+                    {join_clause}
+                    and {where_clause}
+                order by
+                    {order_by_clause}
+            )"
+        ))
+    }
+
+    /// Clause restricting `quoted_block_name` to records linked to the parent record by a
+    /// row of the junction table. Records linked more than once are returned once
+    fn to_junction_clause(
+        &self,
+        junction: &JunctionReference,
+        quoted_block_name: &str,
+        quoted_parent_block_name: &str,
+    ) -> GraphQLResult<String> {
+        let quoted_junction_block_name = rand_block_name();
+        let quoted_schema = quote_ident(&junction.table.schema);
+        let quoted_table = quote_ident(&junction.table.name);
+
+        let foreign_join_clause = self.source.table.to_join_clause(
+            &junction.foreign_fkey,
+            false,
+            quoted_block_name,
+            &quoted_junction_block_name,
+        )?;
+        let local_join_clause = self.source.table.to_join_clause(
+            &junction.local_fkey,
+            false,
+            quoted_parent_block_name,
+            &quoted_junction_block_name,
+        )?;
+
+        Ok(format!(
+            "exists (
+                select
+                    1
+                from
+                    {quoted_schema}.{quoted_table} {quoted_junction_block_name}
+                where
+                    {foreign_join_clause}
+                    and {local_join_clause}
+            )"
+        ))
+    }
 
-pub struct FromFunction {
-    function: Arc<Function>,
-    input_table: Arc<Table>,
-    // The block name for the functions argument
-    input_block_name: String,
-}
+    /// Recursive subquery of the records reachable from the parent record through a self
+    /// referencing foreign key, each with its `__depth`. Records already on the path are
+    /// not revisited, so cyclic references terminate
+    fn to_hierarchy_from_clause(
+        &self,
+        hierarchy: &HierarchyBuilder,
+        quoted_parent_block_name: &Option<&str>,
+    ) -> GraphQLResult<String> {
+        let quoted_parent_block_name = quoted_parent_block_name
+            .ok_or("Internal Error: Parent block name is required for hierarchies")?;
+        let table = &self.source.table;
+        let quoted_schema = quote_ident(&table.schema);
+        let quoted_table = quote_ident(&table.name);
 
-impl ConnectionBuilder {
-    fn page_selections(&self) -> Vec<PageInfoSelection> {
-        self.selections
-            .iter()
-            .flat_map(|x| match x {
-                ConnectionSelection::PageInfo(page_info_builder) => {
-                    page_info_builder.selections.clone()
-                }
-                _ => vec![],
-            })
-            .collect()
-    }
+        // Descendants reference the previous record, ancestors are referenced by it
+        let reverse_reference = hierarchy.direction == HierarchyDirection::Descendants;
 
-    fn requested_next_page(&self) -> bool {
-        self.page_selections()
-            .iter()
-            .any(|x| matches!(&x, PageInfoSelection::HasNextPage { alias: _ }))
-    }
+        let quoted_seed_block_name = rand_block_name();
+        let quoted_step_block_name = rand_block_name();
 
-    fn requested_previous_page(&self) -> bool {
-        self.page_selections()
-            .iter()
-            .any(|x| matches!(&x, PageInfoSelection::HasPreviousPage { alias: _ }))
-    }
+        let column_names = table.returning_columns();
+        let columns_clause = |block_name: &str| {
+            column_names
+                .iter()
+                .map(|x| format!("{block_name}.{x}"))
+                .join(", ")
+        };
 
-    fn is_reverse_pagination(&self) -> bool {
-        self.last.is_some() || self.before.is_some()
+        let seed_join_clause = table.to_join_clause(
+            &hierarchy.fkey,
+            reverse_reference,
+            &quoted_seed_block_name,
+            quoted_parent_block_name,
+        )?;
+        let step_join_clause = table.to_join_clause(
+            &hierarchy.fkey,
+            reverse_reference,
+            &quoted_step_block_name,
+            "__hierarchy",
+        )?;
+
+        let (seed_depth_clause, step_depth_clause) = match hierarchy.max_depth {
+            Some(max_depth) => (
+                format!("{max_depth} >= 1"),
+                format!("__hierarchy.__depth < {max_depth}"),
+            ),
+            None => ("true".to_string(), "true".to_string()),
+        };
+
+        let parent_pkey_clause = table.to_primary_key_tuple_clause(quoted_parent_block_name);
+        let seed_pkey_clause = table.to_primary_key_tuple_clause(&quoted_seed_block_name);
+        let step_pkey_clause = table.to_primary_key_tuple_clause(&quoted_step_block_name);
+
+        let seed_soft_delete_clause =
+            table.to_soft_delete_clause(&quoted_seed_block_name, hierarchy.include_deleted);
+        let step_soft_delete_clause =
+            table.to_soft_delete_clause(&quoted_step_block_name, hierarchy.include_deleted);
+
+        let seed_columns_clause = columns_clause(&quoted_seed_block_name);
+        let step_columns_clause = columns_clause(&quoted_step_block_name);
+        let hierarchy_columns_clause = column_names.join(", ");
+
+        Ok(format!(
+            "(
+                with recursive __hierarchy as (
+                    select
+                        {seed_columns_clause},
+                        1 as __depth,
+                        array[{parent_pkey_clause}::text, {seed_pkey_clause}::text] as __path
+                    from
+                        {quoted_schema}.{quoted_table} {quoted_seed_block_name}
+                    where
+                        {seed_join_clause}
+                        and not ({seed_pkey_clause}::text = {parent_pkey_clause}::text)
+                        and {seed_soft_delete_clause}
+                        and {seed_depth_clause}
+                    union all
+                    select
+                        {step_columns_clause},
+                        __hierarchy.__depth + 1,
+                        __hierarchy.__path || {step_pkey_clause}::text
+                    from
+                        {quoted_schema}.{quoted_table} {quoted_step_block_name}
+                        join __hierarchy
+                            on {step_join_clause}
+                    where
+                        not ({step_pkey_clause}::text = any(__hierarchy.__path))
+                        and {step_soft_delete_clause}
+                        and {step_depth_clause}
+                )
+                select {hierarchy_columns_clause}, __depth from __hierarchy
+            )"
+        ))
     }
 
     fn to_join_clause(
@@ -866,6 +1931,12 @@ impl ConnectionBuilder {
         quoted_block_name: &str,
         quoted_parent_block_name: &Option<&str>,
     ) -> GraphQLResult<String> {
+        if let Some(junction) = &self.source.junction {
+            let quoted_parent_block_name = quoted_parent_block_name
+                .ok_or("Internal Error: Parent block name is required for junctions")?;
+            return self.to_junction_clause(junction, quoted_block_name, quoted_parent_block_name);
+        }
+
         match &self.source.fkey {
             Some(fkey) => {
                 let quoted_parent_block_name = quoted_parent_block_name
@@ -934,93 +2005,75 @@ impl ConnectionBuilder {
         }
     }
 
-    // Generates the *contents* of the aggregate jsonb_build_object
-    fn aggregate_select_list(&self, quoted_block_name: &str) -> GraphQLResult<Option<String>> {
-        let Some(agg_builder) = self.selections.iter().find_map(|sel| match sel {
-            ConnectionSelection::Aggregate(builder) => Some(builder),
-            _ => None,
-        }) else {
-            return Ok(None);
-        };
-
-        let mut agg_selections = vec![];
+    // Generates the *contents* of the aggregate jsonb_build_object, one key per
+    // aggregate selection on the connection
+    fn aggregate_select_list(
+        &self,
+        quoted_block_name: &str,
+        quoted_parent_block_name: &Option<&str>,
+        param_context: &mut ParamContext,
+        from_func: &Option<FromFunction>,
+        from_clause: &Option<String>,
+    ) -> GraphQLResult<Option<String>> {
+        let mut builder_selections = vec![];
 
-        for selection in &agg_builder.selections {
-            match selection {
-                AggregateSelection::Count { alias } => {
-                    // Produces: 'count_alias', count(*)
-                    agg_selections.push(format!("{}, count(*)", quote_literal(alias)));
-                }
-                AggregateSelection::Sum {
-                    alias,
-                    column_builders: selections,
-                }
-                | AggregateSelection::Avg {
-                    alias,
-                    column_builders: selections,
-                }
-                | AggregateSelection::Min {
-                    alias,
-                    column_builders: selections,
-                }
-                | AggregateSelection::Max {
-                    alias,
-                    column_builders: selections,
-                } => {
-                    let pg_func = match selection {
-                        AggregateSelection::Sum { .. } => aggregate::SUM,
-                        AggregateSelection::Avg { .. } => aggregate::AVG,
-                        AggregateSelection::Min { .. } => aggregate::MIN,
-                        AggregateSelection::Max { .. } => aggregate::MAX,
-                        AggregateSelection::Count { .. } => {
-                            unreachable!("Count should be handled by its own arm")
-                        }
-                        AggregateSelection::Typename { .. } => {
-                            unreachable!("Typename should be handled by its own arm")
-                        }
-                    };
+        for sel in &self.selections {
+            let ConnectionSelection::Aggregate(agg_builder) = sel else {
+                continue;
+            };
 
-                    let mut field_selections = vec![];
-                    for col_builder in selections {
-                        let col_sql = col_builder.to_sql(quoted_block_name)?;
-                        let col_alias = &col_builder.alias;
+            // Aggregates with their own filter share the scan and only see matching rows
+            let agg_filter = match agg_builder.filter.elems.is_empty() {
+                true => "".to_string(),
+                false => format!(
+                    " filter (where {})",
+                    agg_builder.filter.to_where_clause(
+                        quoted_block_name,
+                        &self.source.table,
+                        param_context
+                    )?
+                ),
+            };
 
-                        // Always cast avg input to numeric for precision
-                        let col_sql_casted = if pg_func == "avg" {
-                            format!("{}::numeric", col_sql)
-                        } else {
-                            col_sql
-                        };
-                        // Produces: 'col_alias', agg_func(col)
-                        field_selections.push(format!(
-                            "{}, {}({})",
-                            quote_literal(col_alias),
-                            pg_func,
-                            col_sql_casted
+            let mut agg_selections = vec![];
+
+            for selection in &agg_builder.selections {
+                match selection {
+                    AggregateSelection::GroupBy(group_by) => {
+                        // Produces: 'group_alias', (select jsonb_agg(...) from ... group by ...)
+                        agg_selections.push(format!(
+                            "{}, {}",
+                            quote_literal(&group_by.alias),
+                            self.to_group_by_clause(
+                                group_by,
+                                &agg_builder.filter,
+                                quoted_parent_block_name,
+                                param_context,
+                                from_func,
+                                from_clause,
+                            )?
                         ));
                     }
-                    // Produces: 'agg_alias', jsonb_build_object('col_alias', agg_func(col), ...)
-                    agg_selections.push(format!(
-                        "{}, jsonb_build_object({})",
-                        quote_literal(alias),
-                        field_selections.join(", ")
-                    ));
-                }
-                AggregateSelection::Typename { alias, typename } => {
-                    // Produces: '__typename', 'AggregateTypeName'
-                    agg_selections.push(format!(
-                        "{}, {}",
-                        quote_literal(alias),
-                        quote_literal(typename)
-                    ));
+                    _ => agg_selections.push(selection.to_sql(
+                        quoted_block_name,
+                        &agg_filter,
+                        param_context,
+                    )?),
                 }
             }
+
+            // Produces: 'agg_alias', jsonb_build_object('count', count(*) filter (where ...), ...)
+            builder_selections.push(format!(
+                "{}, jsonb_build_object({})",
+                quote_literal(&agg_builder.alias),
+                agg_selections.join(", ")
+            ));
         }
 
-        if agg_selections.is_empty() {
+        if builder_selections.is_empty() {
             Ok(None)
         } else {
-            Ok(Some(agg_selections.join(", ")))
+            Ok(Some(builder_selections.join(", ")))
         }
     }
 
@@ -1033,20 +2086,67 @@ impl ConnectionBuilder {
     ) -> GraphQLResult<String> {
         let quoted_block_name = rand_block_name();
 
-        let from_clause = match from_clause {
+        // Rows returned by functions do not carry system columns
+        let selectable_columns_clause = match &self.source.hierarchy {
+            Some(_) => format!(
+                "{}, __depth",
+                self.source.table.to_returning_columns_clause()
+            ),
+            None => match from_func.is_none() && from_clause.is_none() {
+                true => self.source.table.to_returning_columns_clause(),
+                false => self.source.table.to_selectable_columns_clause(),
+            },
+        };
+
+        // Hierarchies select from a recursive subquery in place of the table
+        let from_clause = match (&self.source.hierarchy, from_clause) {
+            (Some(hierarchy), None) => {
+                Some(self.to_hierarchy_from_clause(hierarchy, &quoted_parent_block_name)?)
+            }
+            (_, from_clause) => from_clause,
+        };
+
+        // The record's depth is kept apart from its row, which computed fields cast to the
+        // table's type
+        let records_join_clause = match &self.source.hierarchy {
+            Some(_) => format!(
+                "left join __records __hierarchy_records on true
+                        cross join lateral (select {}) {quoted_block_name}",
+                self.source
+                    .table
+                    .returning_columns()
+                    .iter()
+                    .map(|x| format!("__hierarchy_records.{x}"))
+                    .join(", ")
+            ),
+            None => format!("left join __records {quoted_block_name} on true"),
+        };
+
+        let from_clause_arg = from_clause;
+        let from_clause = match &from_clause_arg {
             Some(from_clause) => format!("{from_clause} {quoted_block_name}"),
             None => self.from_clause(&quoted_block_name, &from_func),
         };
 
-        let where_clause =
-            self.filter
-                .to_where_clause(&quoted_block_name, &self.source.table, param_context)?;
+        let where_clause = self.to_source_where_clause(
+            &quoted_block_name,
+            &quoted_parent_block_name,
+            param_context,
+            &from_func,
+            &from_clause_arg,
+        )?;
 
-        let order_by_clause = self.order_by.to_order_by_clause(&quoted_block_name);
+        let order_by_clause = self
+            .order_by
+            .to_order_by_clause(&quoted_block_name, param_context)?;
         let order_by_clause_reversed = self
             .order_by
             .reverse()
-            .to_order_by_clause(&quoted_block_name);
+            .to_order_by_clause(&quoted_block_name, param_context)?;
+
+        let order_keys_join_clause = self
+            .order_by
+            .to_order_keys_join_clause(&quoted_block_name, param_context)?;
 
         let order_by_clause_records = match self.is_reverse_pagination() {
             true => &order_by_clause_reversed,
@@ -1061,9 +2161,13 @@ impl ConnectionBuilder {
         let cursor = &self.before.clone().or_else(|| self.after.clone());
 
         let object_clause = self.object_clause(&quoted_block_name, param_context)?;
-        let aggregate_select_list = self.aggregate_select_list(&quoted_block_name)?;
-
-        let selectable_columns_clause = self.source.table.to_selectable_columns_clause();
+        let aggregate_select_list = self.aggregate_select_list(
+            &quoted_block_name,
+            &quoted_parent_block_name,
+            param_context,
+            &from_func,
+            &from_clause_arg,
+        )?;
 
         let pkey_tuple_clause_from_block = self
             .source
@@ -1103,6 +2207,7 @@ impl ConnectionBuilder {
                     1
                 from
                     {from_clause}
+                    {order_keys_join_clause}
                 where
                     {join_clause}
                     and {where_clause}
@@ -1122,6 +2227,7 @@ impl ConnectionBuilder {
                     not ({pkey_tuple_clause_from_block} = any( __records.seen )) is_pkey_in_records
                 from
                     {from_clause}
+                    {order_keys_join_clause}
                     left join (select array_agg({pkey_tuple_clause_from_records}) from __records ) __records(seen)
                         on true
                 where
@@ -1158,6 +2264,8 @@ impl ConnectionBuilder {
                     where
                         {join_clause}
                         and {where_clause}
+                    -- Always a single row, even when no aggregate functions are selected
+                    group by ()
                 )
                 "#
             )
@@ -1168,6 +2276,34 @@ impl ConnectionBuilder {
             .to_string()
         };
 
+        let count_query = format!(
+            "
+                    select
+                        count(*)
+                    from
+                        {from_clause}
+                    where
+                        {join_clause}
+                        and {where_clause}
+                "
+        );
+
+        let total_count_query = match self.requested_total_count() {
+            true => count_query.clone(),
+            false => "select null::bigint".to_string(),
+        };
+
+        let total_count_estimate_query = match self.requested_total_count_estimate() {
+            true => self.to_total_count_estimate_query(
+                &quoted_parent_block_name,
+                param_context,
+                &from_func,
+                &from_clause_arg,
+                &count_query,
+            )?,
+            false => "select null::bigint".to_string(),
+        };
+
         // Add helper cte to set page info correctly for empty collections
         let has_records_cte = r#"
         ,__has_records(has_records) as (select exists(select 1 from __records))
@@ -1176,22 +2312,9 @@ impl ConnectionBuilder {
         // Clause containing selections *not* including the aggregate
         let base_object_clause = object_clause; // Renamed original object_clause
 
-        // Clause to merge the aggregate result if requested
+        // Clause to merge the aggregate results, already keyed by alias, if requested
         let aggregate_merge_clause = if requested_aggregates {
-            let agg_alias = self
-                .selections
-                .iter()
-                .find_map(|sel| match sel {
-                    ConnectionSelection::Aggregate(builder) => Some(builder.alias.clone()),
-                    _ => None,
-                })
-                .ok_or(
-                    "Internal Error: Aggregate builder not found when requested_aggregates is true",
-                )?;
-            format!(
-                "|| jsonb_build_object({}, coalesce(__aggregates.agg_result, '{{}}'::jsonb))",
-                quote_literal(&agg_alias)
-            )
+            "|| coalesce(__aggregates.agg_result, '{}'::jsonb)".to_string()
         } else {
             "".to_string()
         };
@@ -1204,6 +2327,7 @@ impl ConnectionBuilder {
                         {selectable_columns_clause}
                     from
                         {from_clause}
+                        {order_keys_join_clause}
                     where
                         true
                         and {join_clause}
@@ -1217,13 +2341,10 @@ impl ConnectionBuilder {
                         {offset}
                 ),
                 __total_count(___total_count) as (
-                    select
-                        count(*)
-                    from
-                        {from_clause}
-                    where
-                        {join_clause}
-                        and {where_clause}
+                    {total_count_query}
+                ),
+                __total_count_estimate(___total_count_estimate) as (
+                    {total_count_estimate_query}
                 ),
                 __has_next_page(___has_next_page) as (
                     {has_next_page_query}
@@ -1237,12 +2358,15 @@ impl ConnectionBuilder {
                      select jsonb_build_object({base_object_clause}) as obj
                      from
                         __total_count
+                        cross join __total_count_estimate
                         cross join __has_next_page
                         cross join __has_previous_page
                         cross join __has_records
-                        left join __records {quoted_block_name} on true
+                        {records_join_clause}
+                        {order_keys_join_clause}
                      group by
                         __total_count.___total_count,
+                        __total_count_estimate.___total_count_estimate,
                         __has_next_page.___has_next_page,
                         __has_previous_page.___has_previous_page,
                         __has_records.has_records
@@ -1259,6 +2383,160 @@ impl ConnectionBuilder {
     }
 }
 
+impl AggregateSelection {
+    /// Key and value of the selection within an aggregate's jsonb_build_object
+    ///
+    /// `agg_filter` is an optional ` filter (where ...)` clause attached to every
+    /// aggregate function call
+    fn to_sql(
+        &self,
+        quoted_block_name: &str,
+        agg_filter: &str,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<String> {
+        match self {
+            AggregateSelection::Count { alias } => {
+                // Produces: 'count_alias', count(*)
+                Ok(format!("{}, count(*){agg_filter}", quote_literal(alias)))
+            }
+            AggregateSelection::Sum {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::Avg {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::Min {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::Max {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::CountDistinct {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::Stddev {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::Variance {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::BoolAnd {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::BoolOr {
+                alias,
+                column_builders: selections,
+            }
+            | AggregateSelection::ArrayAgg {
+                alias,
+                column_builders: selections,
+                ..
+            }
+            | AggregateSelection::PercentileCont {
+                alias,
+                column_builders: selections,
+                ..
+            } => {
+                let mut field_selections = vec![];
+                for col_builder in selections {
+                    let col_sql = col_builder.to_sql(quoted_block_name)?;
+                    let col_alias = &col_builder.alias;
+
+                    // Numeric statistics are computed on numeric for precision
+                    let agg_sql = match self {
+                        AggregateSelection::Sum { .. } => format!("sum({col_sql})"),
+                        AggregateSelection::Avg { .. } => format!("avg({col_sql}::numeric)"),
+                        AggregateSelection::Min { .. } => format!("min({col_sql})"),
+                        AggregateSelection::Max { .. } => format!("max({col_sql})"),
+                        AggregateSelection::CountDistinct { .. } => {
+                            format!("count(distinct {col_sql})")
+                        }
+                        AggregateSelection::Stddev { .. } => {
+                            format!("stddev_samp({col_sql}::numeric)")
+                        }
+                        AggregateSelection::Variance { .. } => {
+                            format!("var_samp({col_sql}::numeric)")
+                        }
+                        AggregateSelection::BoolAnd { .. } => format!("bool_and({col_sql})"),
+                        AggregateSelection::BoolOr { .. } => format!("bool_or({col_sql})"),
+                        AggregateSelection::ArrayAgg { order_by, .. } => format!(
+                            "array_agg({col_sql} order by {})",
+                            order_by.to_inline_order_by_clause(quoted_block_name, param_context)?
+                        ),
+                        AggregateSelection::PercentileCont { fraction, .. } => format!(
+                            "percentile_cont({fraction}::float8) within group (order by {col_sql}::numeric::float8)"
+                        ),
+                        AggregateSelection::Count { .. }
+                        | AggregateSelection::Typename { .. }
+                        | AggregateSelection::GroupBy(_) => {
+                            unreachable!("Selection should be handled by its own arm")
+                        }
+                    };
+                    // Produces: 'col_alias', agg_func(col)
+                    field_selections.push(format!(
+                        "{}, {agg_sql}{agg_filter}",
+                        quote_literal(col_alias)
+                    ));
+                }
+                // Produces: 'agg_alias', jsonb_build_object('col_alias', agg_func(col), ...)
+                Ok(format!(
+                    "{}, jsonb_build_object({})",
+                    quote_literal(alias),
+                    field_selections.join(", ")
+                ))
+            }
+            AggregateSelection::Typename { alias, typename } => {
+                // Produces: '__typename', 'AggregateTypeName'
+                Ok(format!(
+                    "{}, {}",
+                    quote_literal(alias),
+                    quote_literal(typename)
+                ))
+            }
+            AggregateSelection::GroupBy(_) => Err(GraphQLError::internal(
+                "Internal Error: groupBy must be transpiled by its connection",
+            )),
+        }
+    }
+}
+
+impl GroupByKeyBuilder {
+    fn to_sql(&self, quoted_block_name: &str) -> GraphQLResult<String> {
+        match &self.truncate {
+            Some(unit) => Ok(format!(
+                "date_trunc({}, {quoted_block_name}.{})::{}",
+                quote_literal(unit),
+                quote_ident(&self.column.name),
+                self.column.type_name
+            )),
+            None => ColumnBuilder {
+                alias: String::new(),
+                column: Arc::clone(&self.column),
+            }
+            .to_sql(quoted_block_name),
+        }
+    }
+}
+
+impl OrderDirection {
+    fn to_sql(&self) -> &str {
+        match self {
+            Self::AscNullsFirst => "asc nulls first",
+            Self::AscNullsLast => "asc nulls last",
+            Self::DescNullsFirst => "desc nulls first",
+            Self::DescNullsLast => "desc nulls last",
+        }
+    }
+}
+
 impl QueryEntrypoint for ConnectionBuilder {
     fn to_sql_entrypoint(&self, param_context: &mut ParamContext) -> GraphQLResult<String> {
         self.to_sql(None, param_context, None, None)
@@ -1271,11 +2549,12 @@ impl PageInfoBuilder {
         _block_name: &str,
         order_by: &OrderByBuilder,
         table: &Table,
+        param_context: &mut ParamContext,
     ) -> GraphQLResult<String> {
         let frags: Vec<String> = self
             .selections
             .iter()
-            .map(|x| x.to_sql(_block_name, order_by, table))
+            .map(|x| x.to_sql(_block_name, order_by, table, param_context))
             .collect::<Result<Vec<_>, _>>()?;
 
         let x = frags.join(", ");
@@ -1290,11 +2569,14 @@ impl PageInfoSelection {
         block_name: &str,
         order_by: &OrderByBuilder,
         table: &Table,
+        param_context: &mut ParamContext,
     ) -> GraphQLResult<String> {
-        let order_by_clause = order_by.to_order_by_clause(block_name);
-        let order_by_clause_reversed = order_by.reverse().to_order_by_clause(block_name);
+        let order_by_clause = order_by.to_order_by_clause(block_name, param_context)?;
+        let order_by_clause_reversed = order_by
+            .reverse()
+            .to_order_by_clause(block_name, param_context)?;
 
-        let cursor_clause = table.to_cursor_clause(block_name, order_by);
+        let cursor_clause = table.to_cursor_clause(block_name, order_by, param_context)?;
 
         Ok(match self {
             Self::StartCursor { alias } => {
@@ -1345,12 +2627,16 @@ impl ConnectionSelection {
             Self::PageInfo(x) => Some(format!(
                 "{}, {}",
                 quote_literal(&x.alias),
-                x.to_sql(block_name, order_by, table)?
+                x.to_sql(block_name, order_by, table, param_context)?
             )),
             Self::TotalCount { alias } => Some(format!(
                 "{}, coalesce(__total_count.___total_count, 0)",
                 quote_literal(alias),
             )),
+            Self::TotalCountEstimate { alias } => Some(format!(
+                "{}, coalesce(__total_count_estimate.___total_count_estimate, 0)",
+                quote_literal(alias),
+            )),
             Self::Typename { alias, typename } => Some(format!(
                 "{}, {}",
                 quote_literal(alias),
@@ -1378,7 +2664,7 @@ impl EdgeBuilder {
             .collect::<Result<Vec<_>, _>>()?;
 
         let x = frags.join(", ");
-        let order_by_clause = order_by.to_order_by_clause(block_name);
+        let order_by_clause = order_by.to_order_by_clause(block_name, param_context)?;
 
         // Get the first primary key column name to use in the filter
         let first_pk_col = table.primary_key_columns().first().map(|col| &col.name);
@@ -1416,7 +2702,7 @@ impl EdgeSelection {
     ) -> GraphQLResult<String> {
         Ok(match self {
             Self::Cursor { alias } => {
-                let cursor_clause = table.to_cursor_clause(block_name, order_by);
+                let cursor_clause = table.to_cursor_clause(block_name, order_by, param_context)?;
                 format!("{}, {cursor_clause}", quote_literal(alias))
             }
             Self::Node(builder) => format!(
@@ -1424,6 +2710,10 @@ impl EdgeSelection {
                 quote_literal(&builder.alias),
                 builder.to_sql(block_name, param_context)?
             ),
+            // Projected alongside the record by `ancestors` and `descendants` connections
+            Self::Depth { alias } => {
+                format!("{}, __hierarchy_records.__depth", quote_literal(alias))
+            }
             Self::Typename { alias, typename } => {
                 format!("{}, {}", quote_literal(alias), quote_literal(typename))
             }
@@ -1488,6 +2778,9 @@ impl NodeBuilder {
             &quoted_block_name,
             parent_block_name,
         )?;
+        let soft_delete_clause = self
+            .table
+            .to_soft_delete_clause(&quoted_block_name, self.include_deleted);
 
         Ok(format!(
             "
@@ -1498,6 +2791,7 @@ impl NodeBuilder {
                     {quoted_schema}.{quoted_table} as {quoted_block_name}
                 where
                     {join_clause}
+                    and {soft_delete_clause}
             )"
         ))
     }
@@ -1514,6 +2808,9 @@ impl QueryEntrypoint for NodeBuilder {
             Some(node_id) => node_id.to_sql(&quoted_block_name, &self.table, param_context)?,
             None => "true".to_string(),
         };
+        let soft_delete_clause = self
+            .table
+            .to_soft_delete_clause(&quoted_block_name, self.include_deleted);
 
         Ok(format!(
             "
@@ -1524,12 +2821,91 @@ impl QueryEntrypoint for NodeBuilder {
                     {quoted_schema}.{quoted_table} as {quoted_block_name}
                 where
                     {where_clause}
+                    and {soft_delete_clause}
             )
             "
         ))
     }
 }
 
+impl NodesLookup {
+    /// Selects the requested records of one collection as an object keyed by their
+    /// position in the request
+    fn to_sql(&self, param_context: &mut ParamContext) -> GraphQLResult<String> {
+        let quoted_block_name = rand_block_name();
+        let quoted_schema = quote_ident(&self.node.table.schema);
+        let quoted_table = quote_ident(&self.node.table.name);
+        let object_clause = self.node.to_sql(&quoted_block_name, param_context)?;
+
+        let pkey_cols = self.node.table.primary_key_columns();
+
+        let mut rows = vec![];
+        for (ix, node_id) in &self.node_ids {
+            let mut row = vec![ix.to_string()];
+            for (column, value) in pkey_cols.iter().zip(&node_id.values) {
+                row.push(param_context.clause_for(value, &column.type_name)?);
+            }
+            rows.push(format!("({})", row.join(", ")));
+        }
+        let rows_clause = rows.join(", ");
+
+        let key_names: Vec<String> = (0..pkey_cols.len()).map(|i| format!("key_{i}")).collect();
+        let key_names_clause = key_names.join(", ");
+
+        let join_clause = pkey_cols
+            .iter()
+            .zip(&key_names)
+            .map(|(column, key_name)| {
+                format!(
+                    "{quoted_block_name}.{} = __ids.{key_name}",
+                    quote_ident(&column.name)
+                )
+            })
+            .join(" and ");
+
+        let soft_delete_clause = self
+            .node
+            .table
+            .to_soft_delete_clause(&quoted_block_name, self.node.include_deleted);
+
+        Ok(format!(
+            "
+            (
+                select
+                    coalesce(jsonb_object_agg(__ids.ix, {object_clause}), '{{}}')
+                from
+                    (values {rows_clause}) as __ids(ix, {key_names_clause})
+                    join {quoted_schema}.{quoted_table} as {quoted_block_name}
+                        on {join_clause}
+                where
+                    {soft_delete_clause}
+            )"
+        ))
+    }
+}
+
+impl QueryEntrypoint for NodesBuilder {
+    fn to_sql_entrypoint(&self, param_context: &mut ParamContext) -> GraphQLResult<String> {
+        // One query per collection, reassembled in the order the ids were requested
+        let mut found_clause = "'{}'::jsonb".to_string();
+        for lookup in &self.lookups {
+            found_clause.push_str(" || ");
+            found_clause.push_str(&lookup.to_sql(param_context)?);
+        }
+
+        Ok(format!(
+            "
+            select
+                coalesce(jsonb_agg(__found.nodes -> __positions.ix::text order by __positions.ix), '[]')
+            from
+                generate_series(0, {}) as __positions(ix),
+                (select {found_clause}) as __found(nodes)
+            ",
+            self.ids_count as i64 - 1
+        ))
+    }
+}
+
 impl NodeByPkBuilder {
     pub fn to_sql(
         &self,
@@ -1586,6 +2962,9 @@ impl QueryEntrypoint for NodeByPkBuilder {
         let object_clause = self.to_sql(&quoted_block_name, param_context)?;
 
         let where_clause = self.to_pk_where_clause(&quoted_block_name, param_context)?;
+        let soft_delete_clause = self
+            .table
+            .to_soft_delete_clause(&quoted_block_name, self.include_deleted);
 
         Ok(format!(
             "
@@ -1596,6 +2975,7 @@ impl QueryEntrypoint for NodeByPkBuilder {
                     {quoted_schema}.{quoted_table} as {quoted_block_name}
                 where
                     {where_clause}
+                    and {soft_delete_clause}
             )
             "
         ))
@@ -1700,6 +3080,24 @@ impl NodeSelection {
                     type_adjustment_clause
                 )
             }
+            Self::JsonPath(builder) => {
+                let path = serde_json::Value::Array(
+                    builder
+                        .path
+                        .iter()
+                        .map(|key| serde_json::Value::String(key.clone()))
+                        .collect(),
+                );
+                // Extracted values are serialized JSON, so strings keep their quotes and
+                // remain distinguishable from other scalars
+                format!(
+                    "{}, ({}.{} #> {})::text",
+                    quote_literal(&builder.alias),
+                    block_name,
+                    quote_ident(&builder.column.name),
+                    param_context.clause_for(&path, "text[]")?
+                )
+            }
             Self::Function(builder) => {
                 let type_adjustment_clause = apply_suffix_casts(builder.function.type_oid);
                 format!(
@@ -1712,7 +3110,12 @@ impl NodeSelection {
             Self::NodeId(builder) => format!(
                 "{}, {}",
                 quote_literal(&builder.alias),
-                builder.to_sql(block_name)?
+                builder.to_sql(block_name, param_context)?
+            ),
+            Self::Version(builder) => format!(
+                "{}, {}",
+                quote_literal(&builder.alias),
+                builder.source.to_sql(block_name)
             ),
             Self::Typename { alias, typename } => {
                 format!("{}, {}", quote_literal(alias), quote_literal(typename))
@@ -1751,18 +3154,36 @@ impl ColumnBuilder {
     }
 }
 
+impl VersionSource {
+    /// The row version as text, so column and system versions compare the same way
+    pub fn to_sql(&self, block_name: &str) -> String {
+        match self {
+            Self::Column(column) => format!("{block_name}.{}::text", quote_ident(&column.name)),
+            Self::Xmin => format!("{block_name}.xmin::text"),
+        }
+    }
+}
+
 impl NodeIdBuilder {
-    pub fn to_sql(&self, block_name: &str) -> GraphQLResult<String> {
+    pub fn to_sql(
+        &self,
+        block_name: &str,
+        param_context: &mut ParamContext,
+    ) -> GraphQLResult<String> {
         let column_selects: Vec<String> = self
             .columns
             .iter()
             .map(|col| format!("{}.{}", block_name, col.name))
             .collect();
         let column_clause = column_selects.join(", ");
-        let schema_name = quote_literal(&self.schema_name);
-        let table_name = quote_literal(&self.table_name);
-        Ok(format!(
-            "translate(encode(convert_to(jsonb_build_array({schema_name}, {table_name}, {column_clause})::text, 'utf-8'), 'base64'), E'\n', '')"
+        let prefix_clause = self
+            .prefix
+            .iter()
+            .map(|x| quote_literal(x))
+            .collect::<Vec<_>>()
+            .join(", ");
+        param_context.sign_token_clause(TokenPurpose::NodeId, format!(
+            "translate(encode(convert_to(jsonb_build_array({prefix_clause}, {column_clause})::text, 'utf-8'), 'base64'), E'\n', '')"
         ))
     }
 }
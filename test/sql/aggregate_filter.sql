@@ -0,0 +1,95 @@
+begin;
+
+    create table ticket(
+        id int primary key,
+        status text not null,
+        priority int not null
+    );
+    comment on table ticket is e'@graphql({"aggregate": {"enabled": true}})';
+
+    insert into ticket(id, status, priority)
+    values
+        (1, 'open', 1),
+        (2, 'open', 2),
+        (3, 'closed', 3),
+        (4, 'open', 1),
+        (5, 'pending', 2),
+        (6, 'closed', 5);
+
+    -- Several aliased aggregates, each with its own filter, in one request
+
+    select graphql.resolve($$
+        {
+          ticketCollection(first: 2) {
+            edges {
+              node {
+                id
+              }
+            }
+            open: aggregate(filter: {status: {eq: "open"}}) {
+              count
+              sum {
+                priority
+              }
+            }
+            closed: aggregate(filter: {status: {eq: "closed"}}) {
+              count
+              max {
+                priority
+              }
+            }
+            aggregate {
+              count
+            }
+          }
+        }
+    $$);
+
+    -- Aggregate filters narrow the connection's filter
+
+    select graphql.resolve($$
+        {
+          ticketCollection(filter: {priority: {gt: 1}}) {
+            urgent: aggregate(filter: {priority: {gte: 3}}) {
+              count
+            }
+            aggregate {
+              count
+            }
+          }
+        }
+    $$);
+
+    -- No matching records
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate(filter: {status: {eq: "missing"}}) {
+              count
+              min {
+                priority
+              }
+            }
+          }
+        }
+    $$);
+
+    -- groupBy only buckets records matching the aggregate's filter
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            open: aggregate(filter: {status: {eq: "open"}}) {
+              groupBy(keys: [{field: priority}]) {
+                key {
+                  priority
+                }
+                count
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
@@ -0,0 +1,165 @@
+begin;
+    comment on schema public is e'@graphql({"inflect_names": true, "introspection": true})';
+
+    create table reading(
+        id int primary key,
+        sensor text not null,
+        value int not null,
+        ok boolean
+    );
+    comment on table reading is e'@graphql({"aggregate": {"enabled": true}})';
+
+    insert into reading(id, sensor, value, ok)
+    values
+        (1, 'north', 1, true),
+        (2, 'south', 1, true),
+        (3, 'north', 3, true),
+        (4, 'south', 5, false),
+        (5, 'north', 5, null);
+
+    -- Each operation is exposed when the table has a column it applies to
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "ReadingAggregate") {
+                fields {
+                  name
+                  args {
+                    name
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Boolean aggregates are only offered for boolean columns
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "ReadingBoolAndAggregateResult") {
+                fields {
+                  name
+                  type {
+                    name
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Distinct counts, sample statistics and boolean aggregates
+
+    select graphql.resolve($$
+        {
+          readingCollection {
+            aggregate {
+                countDistinct {
+                  sensor
+                  value
+                  ok
+                }
+                stddev {
+                  value
+                }
+                variance {
+                  value
+                }
+                boolAnd {
+                  ok
+                }
+                boolOr {
+                  ok
+                }
+            }
+          }
+        }
+    $$);
+
+    -- Lists follow the primary key unless ordered explicitly
+
+    select graphql.resolve($$
+        {
+          readingCollection {
+            aggregate {
+                arrayAgg {
+                  sensor
+                }
+                byValue: arrayAgg(orderBy: [{value: DescNullsLast}]) {
+                  sensor
+                  value
+                }
+            }
+          }
+        }
+    $$);
+
+    -- Percentiles are interpolated between values
+
+    select graphql.resolve($$
+        {
+          readingCollection(filter: {sensor: {eq: "north"}}) {
+            aggregate {
+                median: percentileCont(fraction: 0.5) {
+                  value
+                }
+                p75: percentileCont(fraction: 0.75) {
+                  value
+                }
+            }
+          }
+        }
+    $$);
+
+    -- Aggregates are available on groupBy buckets
+
+    select graphql.resolve($$
+        {
+          readingCollection {
+            aggregate {
+                groupBy(keys: [{field: sensor}]) {
+                  key {
+                    sensor
+                  }
+                  countDistinct {
+                    value
+                  }
+                  boolOr {
+                    ok
+                  }
+                }
+            }
+          }
+        }
+    $$);
+
+    -- Fraction must be between 0 and 1
+
+    select graphql.resolve($$
+        {
+          readingCollection {
+            aggregate {
+                percentileCont(fraction: 1.5) {
+                  value
+                }
+            }
+          }
+        }
+    $$);
+
+    -- Only boolean columns can be combined with boolAnd
+
+    select graphql.resolve($$
+        {
+          readingCollection {
+            aggregate {
+                boolAnd {
+                  value
+                }
+            }
+          }
+        }
+    $$);
+
+rollback;
@@ -0,0 +1,174 @@
+begin;
+    comment on schema public is e'@graphql({"inflect_names": true})';
+
+    create table ticket(
+        id int primary key,
+        status text not null,
+        priority int not null,
+        opened_at date not null
+    );
+    comment on table ticket is e'@graphql({"aggregate": {"enabled": true}})';
+
+    insert into ticket(id, status, priority, opened_at)
+    values
+        (1, 'open', 1, '2025-01-05'),
+        (2, 'open', 2, '2025-01-20'),
+        (3, 'closed', 3, '2025-02-01'),
+        (4, 'open', 1, '2025-02-14'),
+        (5, 'pending', 2, '2025-03-03'),
+        (6, 'closed', 5, '2025-03-10');
+
+    -- Groups are sorted by their keys by default
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: status}]) {
+                key {
+                  status
+                }
+                count
+                sum {
+                  priority
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Date and datetime keys may be truncated
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: openedAt, truncate: MONTH}]) {
+                key {
+                  openedAt
+                }
+                count
+                max {
+                  priority
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Multiple keys
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: status}, {field: priority}], orderBy: [{priority: DescNullsLast}]) {
+                key {
+                  status
+                  priority
+                }
+                count
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Ordering and pagination over groups
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: status}], orderBy: [{count: DescNullsLast}], first: 1) {
+                cursor
+                key {
+                  status
+                }
+                count
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: status}], orderBy: [{count: DescNullsLast}], first: 1, after: "WzFd") {
+                cursor
+                key {
+                  status
+                }
+                count
+              }
+            }
+          }
+        }
+    $$);
+
+    -- The connection's filter applies to groups
+
+    select graphql.resolve($$
+        {
+          ticketCollection(filter: {priority: {gt: 1}}) {
+            aggregate {
+              groupBy(keys: [{field: status}]) {
+                key {
+                  status
+                }
+                count
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Only grouping keys may be selected
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: status}]) {
+                key {
+                  priority
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Only grouping keys and count may be used for ordering
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: status}], orderBy: [{priority: AscNullsLast}]) {
+                count
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Truncation requires a date or datetime field
+
+    select graphql.resolve($$
+        {
+          ticketCollection {
+            aggregate {
+              groupBy(keys: [{field: status, truncate: DAY}]) {
+                count
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
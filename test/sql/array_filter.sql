@@ -0,0 +1,62 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create table post(
+        id int primary key,
+        tags text[],
+        scores int[]
+    );
+
+    insert into public.post(id, tags, scores)
+    values
+        (1, '{"rust", "postgres"}', '{1, 2, 3}'),
+        (2, '{"Rust"}', '{5}'),
+        (3, '{}', '{}'),
+        (4, null, null),
+        (5, '{"graphql", "postgres", "sql"}', '{2, 4}');
+
+    -- anyEq: any element is equal to the value
+
+    select graphql.resolve($${postCollection(filter: {tags: {anyEq: "postgres"}}) { edges { node { id } } }}$$);
+
+    select graphql.resolve($${postCollection(filter: {scores: {anyEq: 2}}) { edges { node { id } } }}$$);
+
+    -- anyIn: any element is contained by the value list
+
+    select graphql.resolve($${postCollection(filter: {tags: {anyIn: ["rust", "sql"]}}) { edges { node { id } } }}$$);
+
+    -- anyStartsWith
+
+    select graphql.resolve($${postCollection(filter: {tags: {anyStartsWith: "post"}}) { edges { node { id } } }}$$);
+
+    -- anyIlike
+
+    select graphql.resolve($${postCollection(filter: {tags: {anyIlike: "rust"}}) { edges { node { id } } }}$$);
+
+    -- isEmpty
+
+    select graphql.resolve($${postCollection(filter: {tags: {isEmpty: true}}) { edges { node { id } } }}$$);
+
+    select graphql.resolve($${postCollection(filter: {tags: {isEmpty: false}}) { edges { node { id } } }}$$);
+
+    -- length compares the number of elements
+
+    select graphql.resolve($${postCollection(filter: {tags: {length: {eq: 1}}}) { edges { node { id } } }}$$);
+
+    select graphql.resolve($${postCollection(filter: {tags: {length: {gt: 0, lt: 3}}}) { edges { node { id } } }}$$);
+
+    select graphql.resolve($${postCollection(filter: {scores: {length: {between: [1, 2]}}}) { edges { node { id } } }}$$);
+
+    select graphql.resolve($${postCollection(filter: {scores: {length: {in: [0, 3]}}}) { edges { node { id } } }}$$);
+
+    -- length bounds are validated like IntFilter
+
+    select graphql.resolve($${postCollection(filter: {scores: {length: {between: [1]}}}) { edges { node { id } } }}$$);
+
+    -- Pattern operators are only available on text arrays
+    select graphql.resolve($${__type(name: "StringListFilter") { inputFields { name } }}$$);
+    select graphql.resolve($${__type(name: "IntListFilter") { inputFields { name } }}$$);
+
+    select graphql.resolve($${postCollection(filter: {scores: {anyIlike: "1"}}) { edges { node { id } } }}$$);
+
+rollback;
@@ -0,0 +1,127 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create type account_status as enum ('active', 'inactive', 'archived');
+    comment on type account_status is e'@graphql({"deprecated": {"archived": "use inactive"}})';
+
+    create table account(
+        id int primary key,
+        name text,
+        full_name text,
+        status account_status
+    );
+    comment on column account.name is e'@graphql({"deprecated": "use full_name"})';
+
+    create function name_upper(account)
+        returns text
+        stable
+        language sql
+    as $$ select upper($1.full_name); $$;
+    comment on function name_upper(account) is e'@graphql({"deprecated": "use full_name"})';
+
+    create table blog(
+        id int primary key,
+        account_id int,
+        constraint blog_account_id_fkey foreign key (account_id) references account(id)
+    );
+    comment on constraint blog_account_id_fkey on blog is e'@graphql({"deprecated": "use owner"})';
+
+    insert into account(id, name, full_name, status) values (1, 'a', 'A B', 'archived');
+
+    -- deprecated fields are hidden by default
+    select graphql.resolve($$
+        {
+          __type(name: "Account") {
+            fields {
+              name
+            }
+          }
+        }
+    $$);
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "Account") {
+                fields(includeDeprecated: true) {
+                  name
+                  isDeprecated
+                  deprecationReason
+                }
+              }
+            }
+        $$)
+    );
+
+    -- relationships are deprecated in both directions
+    select graphql.resolve($$
+        {
+          __type(name: "Blog") {
+            fields(includeDeprecated: true) {
+              name
+              deprecationReason
+            }
+          }
+        }
+    $$);
+
+    -- enum values
+    select graphql.resolve($$
+        {
+          __type(name: "account_status") {
+            default: enumValues {
+              name
+            }
+            all: enumValues(includeDeprecated: true) {
+              name
+              deprecationReason
+            }
+          }
+        }
+    $$);
+
+    -- input fields backed by deprecated columns
+    select graphql.resolve($$
+        {
+          __type(name: "AccountUpdateInput") {
+            default: inputFields {
+              name
+            }
+            all: inputFields(includeDeprecated: true) {
+              name
+              isDeprecated
+            }
+          }
+        }
+    $$);
+
+    -- deprecated fields remain queryable
+    select graphql.resolve($$
+        {
+          accountCollection {
+            edges {
+              node {
+                name
+                name_upper
+                status
+              }
+            }
+          }
+        }
+    $$);
+
+    -- reasons that are not strings are ignored
+    comment on type account_status is e'@graphql({"deprecated": {"archived": 1, "inactive": "use active"}})';
+
+    select graphql.resolve($$
+        {
+          __type(name: "account_status") {
+            enumValues(includeDeprecated: true) {
+              name
+              deprecationReason
+            }
+          }
+        }
+    $$);
+
+rollback;
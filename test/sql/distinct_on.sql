@@ -0,0 +1,188 @@
+begin;
+    comment on schema public is e'@graphql({"inflect_names": true, "introspection": true})';
+
+    create table reading(
+        id int primary key,
+        device_id int not null,
+        taken_at int not null,
+        value int
+    );
+
+    insert into reading(id, device_id, taken_at, value)
+    values
+        (1, 1, 1, 10),
+        (2, 1, 2, 11),
+        (3, 2, 1, 20),
+        (4, 2, 3, 21),
+        (5, 3, 1, 30);
+
+    select graphql.resolve($$
+        {
+          __type(name: "ReadingField") {
+            kind
+            enumValues {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- Latest reading per device
+
+    select graphql.resolve($$
+        {
+          readingCollection(distinctOn: [deviceId], orderBy: [{deviceId: AscNullsFirst}, {takenAt: DescNullsLast}]) {
+            totalCount
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Without orderBy, records are sorted by the distinct fields
+
+    select graphql.resolve($$
+        {
+          readingCollection(distinctOn: [deviceId]) {
+            totalCount
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Duplicate fields are ignored
+
+    select graphql.resolve($$
+        {
+          readingCollection(distinctOn: [deviceId, deviceId]) {
+            totalCount
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Filters are applied before selecting distinct records
+
+    select graphql.resolve($$
+        {
+          readingCollection(distinctOn: [deviceId], filter: {takenAt: {eq: 1}}) {
+            totalCount
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Pagination and pageInfo reflect the distinct records
+
+    select graphql.resolve($$
+        {
+          readingCollection(first: 1, distinctOn: [deviceId], orderBy: [{deviceId: AscNullsFirst}, {takenAt: DescNullsLast}]) {
+            totalCount
+            pageInfo {
+              hasNextPage
+              hasPreviousPage
+            }
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          readingCollection(first: 1, after: "WzEsIDIsIDJd", distinctOn: [deviceId], orderBy: [{deviceId: AscNullsFirst}, {takenAt: DescNullsLast}]) {
+            totalCount
+            pageInfo {
+              hasNextPage
+              hasPreviousPage
+            }
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          readingCollection(last: 1, distinctOn: [deviceId], orderBy: [{deviceId: AscNullsFirst}, {takenAt: DescNullsLast}]) {
+            totalCount
+            pageInfo {
+              hasNextPage
+              hasPreviousPage
+            }
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Multiple distinct fields may be listed in any order within the orderBy prefix
+
+    select graphql.resolve($$
+        {
+          readingCollection(distinctOn: [deviceId, takenAt], orderBy: [{takenAt: AscNullsFirst}, {deviceId: AscNullsFirst}]) {
+            totalCount
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- orderBy must begin with the distinctOn fields
+
+    select graphql.resolve($$
+        {
+          readingCollection(distinctOn: [deviceId], orderBy: [{takenAt: DescNullsLast}]) {
+            totalCount
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Unknown fields are rejected
+
+    select graphql.resolve($$
+        {
+          readingCollection(distinctOn: [nope]) {
+            totalCount
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
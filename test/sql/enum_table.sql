@@ -0,0 +1,157 @@
+begin;
+    comment on schema public is '@graphql({"inflect_names": true, "introspection": true})';
+
+    create table order_status(
+        code text primary key,
+        description text
+    );
+    comment on table order_status is e'@graphql({"enum": true})';
+
+    insert into order_status(code, description) values
+        ('PENDING', 'Awaiting payment'),
+        ('SHIPPED', null);
+
+    create table purchase(
+        id int primary key,
+        status text not null references order_status(code)
+    );
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "OrderStatus") {
+                kind
+                enumValues {
+                  name
+                  description
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Referencing columns are typed as the enum, and the lookup table is not exposed
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "Purchase") {
+                fields {
+                  name
+                  type {
+                    ofType {
+                      kind
+                      name
+                    }
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    select graphql.resolve($$
+        {
+          __type(name: "PurchaseFilter") {
+            inputFields {
+              name
+              type {
+                name
+              }
+            }
+          }
+        }
+    $$) -> 'data' -> '__type' -> 'inputFields' -> 1;
+
+    select graphql.resolve($$
+        mutation {
+          insertIntoPurchaseCollection(objects: [{id: 1, status: PENDING}, {id: 2, status: SHIPPED}]) {
+            records {
+              id
+              status
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          purchaseCollection(filter: {status: {eq: SHIPPED}}) {
+            edges {
+              node {
+                id
+                status
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          purchaseCollection(filter: {status: {in: [ON_HOLD]}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Rows changed later are picked up with the schema, unless a trigger refreshes it
+    insert into order_status(code) values ('RETURNED');
+
+    select graphql.resolve($$
+        {
+          __type(name: "OrderStatus") {
+            enumValues {
+              name
+            }
+          }
+        }
+    $$);
+
+    create trigger order_status_refresh
+        after insert or update or delete or truncate on order_status
+        for each statement
+        execute function graphql.refresh_enum_table();
+
+    insert into order_status(code) values ('CANCELLED');
+
+    select graphql.resolve($$
+        {
+          __type(name: "OrderStatus") {
+            enumValues {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- Tables with keys that are not valid enum values are exposed as regular tables
+    insert into order_status(code) values ('ON-HOLD');
+
+    select graphql.resolve($$
+        {
+          __type(name: "OrderStatus") {
+            kind
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "PurchaseFilter") {
+            inputFields {
+              name
+              type {
+                name
+              }
+            }
+          }
+        }
+    $$) -> 'data' -> '__type' -> 'inputFields' -> 1;
+
+    select reason from graphql.lint() where entity = 'public.order_status';
+
+rollback;
@@ -0,0 +1,161 @@
+begin;
+    comment on schema public is '@graphql({"inflect_names": true, "introspection": true})';
+
+    create table category(
+        id int primary key,
+        name text not null,
+        parent_id int references category(id)
+    );
+    comment on table category is e'@graphql({"totalCount": {"enabled": true}})';
+
+    insert into category(id, name, parent_id)
+    values
+        (1, 'root', null),
+        (2, 'a', 1),
+        (3, 'b', 1),
+        (4, 'a1', 2),
+        (5, 'a2', 2),
+        (6, 'a1x', 4);
+
+    -- Hierarchy edges expose the depth of each record
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "CategoryHierarchyEdge") {
+                fields {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Descendants at any depth
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              categoryByPk(id: 1) {
+                descendants {
+                  totalCount
+                  edges {
+                    depth
+                    node {
+                      id
+                      name
+                    }
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Ancestors up to the root
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              categoryByPk(id: 6) {
+                ancestors {
+                  edges {
+                    depth
+                    node {
+                      id
+                      parentId
+                    }
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- maxDepth, filter and orderBy apply to the hierarchy
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              categoryByPk(id: 1) {
+                descendants(
+                  maxDepth: 2
+                  filter: {name: {startsWith: "a"}}
+                  orderBy: [{name: DescNullsLast}]
+                ) {
+                  edges {
+                    depth
+                    node {
+                      name
+                    }
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Hierarchies are resolved for each record of a collection
+    select graphql.resolve($$
+        {
+          categoryCollection(filter: {id: {in: [4, 5]}}) {
+            edges {
+              node {
+                id
+                ancestors(maxDepth: 1) {
+                  edges {
+                    node {
+                      id
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($${categoryByPk(id: 1) { descendants(maxDepth: 0) { totalCount } }}$$);
+    select graphql.resolve($${categoryByPk(id: 1) { descendants(maxDepth: -1) { totalCount } }}$$);
+
+    -- Cyclic references terminate and exclude the starting record
+    update category set parent_id = 6 where id = 1;
+
+    select graphql.resolve($$
+        {
+          categoryByPk(id: 2) {
+            ancestors {
+              edges {
+                depth
+                node {
+                  id
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Row level security applies to every level of the hierarchy
+    create role api;
+    grant usage on schema graphql to api;
+    grant usage on schema public to api;
+    grant select on category to api;
+
+    alter table category enable row level security;
+    create policy category_select on category for select using (id <> 4);
+
+    set role api;
+
+    select graphql.resolve($$
+        {
+          categoryByPk(id: 2) {
+            descendants {
+              edges {
+                depth
+                node {
+                  id
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
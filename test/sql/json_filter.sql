@@ -0,0 +1,159 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create table doc(
+        id int primary key,
+        data jsonb,
+        meta json
+    );
+
+    insert into doc(id, data, meta)
+    values
+        (1, '{"tags": ["a", "b"], "author": {"name": "Ann"}, "views": 10}', '{"draft": false}'),
+        (2, '{"tags": ["b"], "author": {"name": "Bob"}, "views": 3}', '{"draft": true}'),
+        (3, '{"author": {"name": "Cy"}}', null),
+        (4, null, null);
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "JSONFilter") {
+                inputFields {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    -- contains
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {data: {contains: "{\"tags\": [\"b\"]}"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- containedBy
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {data: {containedBy: "{\"tags\": [\"b\"], \"author\": {\"name\": \"Bob\"}, \"views\": 3, \"extra\": 1}"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- hasKey
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {data: {hasKey: "tags"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- hasAnyKeys
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {data: {hasAnyKeys: ["tags", "author"]}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- hasAllKeys
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {data: {hasAllKeys: ["author", "views"]}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- jsonPath
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {data: {jsonPath: "$.views ? (@ > 5)"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- json columns are compared as jsonb
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {meta: {contains: "{\"draft\": true}"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- is
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {data: {is: NULL}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- path returns only the extracted part of the document
+
+    select graphql.resolve($$
+        {
+          docCollection(filter: {id: {in: [1, 3, 4]}}) {
+            edges {
+              node {
+                id
+                name: data(path: ["author", "name"])
+                firstTag: data(path: ["tags", "0"])
+                author: data(path: ["author"])
+                data(path: [])
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
@@ -0,0 +1,121 @@
+begin;
+    create table account(
+        id int primary key,
+        email text,
+        email_address text
+    );
+    comment on column account.email_address is e'@graphql({"name": "email"})';
+    -- Missing parentheses, so the directive is ignored
+    comment on table account is e'@graphql{"totalCount": {"enabled": true}}';
+
+    create table no_key(
+        id int
+    );
+
+    create table hidden(
+        id int primary key
+    );
+    comment on table hidden is e'@graphql({"omit": true})';
+
+    create table post(
+        id int primary key,
+        hidden_id int references hidden(id)
+    );
+
+    create function add_one(a int)
+        returns int
+        immutable
+        language sql
+    as $$ select a + 1 $$;
+
+    create function add_one(a text)
+        returns text
+        immutable
+        language sql
+    as $$ select a || '1' $$;
+
+    create function nameless(int)
+        returns int
+        immutable
+        language sql
+    as $$ select $1 $$;
+
+    create function do_nothing()
+        returns void
+        volatile
+        language sql
+    as $$ $$;
+
+    select * from graphql.lint();
+
+    -- Fields left off a type because another field uses their name, and columns omitted from some operations
+    comment on column post.hidden_id is e'@graphql({"omit": ["filter", "order"]})';
+
+    create table tag(
+        id int primary key
+    );
+
+    create table post_tag(
+        post_id int references post(id),
+        tag_id int references tag(id),
+        primary key (post_id, tag_id)
+    );
+
+    alter table post add column "tagCollection" text;
+
+    create table category(
+        id int primary key,
+        parent_id int references category(id),
+        ancestors text
+    );
+
+    create function account_count()
+        returns int
+        stable
+        language sql
+    as $$ select count(*)::int from account $$;
+    comment on function account_count() is e'@graphql({"name": "accountCollection"})';
+
+    create function new_account()
+        returns int
+        volatile
+        language sql
+    as $$ select 1 $$;
+    comment on function new_account() is e'@graphql({"name": "insertIntoAccountCollection"})';
+
+    -- Functions returning records of tables that are not exposed
+    create function no_key_rows()
+        returns setof no_key
+        stable
+        language sql
+    as $$ select * from no_key $$;
+
+    create function hidden_search()
+        returns table(type text, id int)
+        stable
+        language sql
+    as $$ select 'hidden', id from hidden $$;
+    comment on function hidden_search() is e'@graphql({"union": {"name": "HiddenResult", "types": ["hidden"]}})';
+
+    -- Computed fields
+    create function hidden_id(post)
+        returns int
+        stable
+        language sql
+    as $$ select 1 $$;
+
+    create function post_score(post)
+        returns int
+        stable
+        language sql
+    as $$ select 1 $$;
+    comment on function post_score(post) is e'@graphql({"omit": true})';
+
+    select * from graphql.lint();
+
+    -- Directives that are not valid JSON prevent the schema from loading, so other checks are skipped
+    comment on table post is e'@graphql({"name": "Post",})';
+
+    select * from graphql.lint();
+
+rollback;
@@ -0,0 +1,146 @@
+begin;
+    comment on schema public is '@graphql({"inflect_names": true, "introspection": true})';
+
+    create table post(
+        id int primary key,
+        title text not null
+    );
+
+    create table tag(
+        id int primary key,
+        name text not null
+    );
+
+    -- Primary key made up of two foreign keys
+    create table post_tag(
+        post_id int references post(id),
+        tag_id int references tag(id),
+        primary key (post_id, tag_id)
+    );
+
+    insert into post(id, title) values (1, 'Hello'), (2, 'World'), (3, 'Empty');
+    insert into tag(id, name) values (1, 'db'), (2, 'graphql'), (3, 'rust');
+    insert into post_tag(post_id, tag_id) values (1, 1), (1, 2), (1, 3), (2, 2);
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "Post") {
+                fields {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Ordering and pagination apply to the far table
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              postCollection {
+                edges {
+                  node {
+                    id
+                    tagCollection(orderBy: [{name: DescNullsLast}], first: 2) {
+                      pageInfo {
+                        hasNextPage
+                      }
+                      edges {
+                        node {
+                          name
+                        }
+                      }
+                    }
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Filtering applies to the far table
+    select graphql.resolve($$
+        {
+          tagByPk(id: 2) {
+            postCollection(filter: {title: {eq: "World"}}) {
+              edges {
+                node {
+                  title
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Tables with other primary keys may be marked as junctions
+    create table person(
+        id int primary key,
+        name text not null
+    );
+
+    create table follow(
+        id serial primary key,
+        follower_id int not null references person(id),
+        followed_id int not null references person(id)
+    );
+    comment on table follow is e'@graphql({"junction": true})';
+
+    insert into person(id, name) values (1, 'alice'), (2, 'bob'), (3, 'carol');
+    insert into follow(follower_id, followed_id) values (1, 2), (1, 3), (2, 1);
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              personByPk(id: 1) {
+                followedCollection {
+                  edges {
+                    node {
+                      name
+                    }
+                  }
+                }
+                followerCollection {
+                  edges {
+                    node {
+                      name
+                    }
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Detection can be disabled
+    comment on table post_tag is e'@graphql({"junction": false})';
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "Post") {
+                fields {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Junction tables must be selectable
+    comment on table follow is e'@graphql({"junction": true, "omit": true})';
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "Person") {
+                fields {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+rollback;
@@ -0,0 +1,84 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create table account(
+        id int primary key,
+        email text not null
+    );
+    comment on table account is e'@graphql({"mutations": ["insert"]})';
+
+    create table blog(
+        id int primary key,
+        title text not null
+    );
+    comment on table blog is e'@graphql({"mutations": {"insert": {"name": "createBlog"}, "delete": {}}})';
+
+    create table note(
+        id int primary key
+    );
+
+    -- only the listed mutations are generated, using any name overrides
+    select jsonb_pretty(
+        graphql.resolve($$
+        {
+          __type(name: "Mutation") {
+            fields {
+              name
+            }
+          }
+        }
+        $$)
+    );
+
+    -- renamed mutation
+    select graphql.resolve($$
+        mutation {
+          createBlog(objects: [{id: 1, title: "A"}]) {
+            affectedCount
+            records {
+              id
+              title
+            }
+          }
+        }
+    $$);
+
+    -- omitted mutation
+    select graphql.resolve($$
+        mutation {
+          updateAccountCollection(set: {email: "a@x.com"}) {
+            affectedCount
+          }
+        }
+    $$);
+
+    -- names that are invalid or taken by another mutation are ignored
+    create table topic(
+        id int primary key
+    );
+    comment on table topic is e'@graphql({"mutations": {"insert": {"name": "insertIntoAccountCollection"}, "update": {"name": "bad-name"}, "delete": {"name": 5}}})';
+
+    create table label(
+        id int primary key
+    );
+    comment on table label is e'@graphql({"mutations": {"insert": {"name": "createLabel"}, "update": {"name": "createLabel"}}})';
+
+    -- entries of the wrong type are ignored
+    create table tag(
+        id int primary key
+    );
+    comment on table tag is e'@graphql({"mutations": ["insert", 1]})';
+
+    select jsonb_pretty(
+        graphql.resolve($$
+        {
+          __type(name: "Mutation") {
+            fields {
+              name
+            }
+          }
+        }
+        $$)
+    );
+
+rollback;
@@ -0,0 +1,56 @@
+begin;
+    create table account(
+        id int primary key,
+        email text not null
+    );
+
+    create table blog_post(
+        id int primary key,
+        title text not null
+    );
+    comment on table blog_post is e'@graphql({"node_id_alias": "Post"})';
+
+    insert into account(id, email) values (1, 'aardvark@x.com');
+    insert into blog_post(id, title) values (1, 'Hello');
+
+    -- "type" identifies the collection by its type name, or its node_id_alias
+    comment on schema public is e'@graphql({"inflect_names": true, "node_id_format": "type"})';
+
+    select graphql.resolve($$
+        {
+          accountCollection { edges { node { nodeId } } }
+          blogPostCollection { edges { node { nodeId } } }
+        }
+    $$);
+    select graphql.resolve($${node(nodeId: "WyJQb3N0IiwgMV0=") { nodeId ... on BlogPost { title } }}$$);
+    select graphql.resolve($${blogPostCollection(filter: {nodeId: {eq: "WyJQb3N0IiwgMV0="}}) { edges { node { id } } }}$$);
+
+    -- nodeIds in the default "table" format remain valid
+    select graphql.resolve($${node(nodeId: "WyJwdWJsaWMiLCAiYmxvZ19wb3N0IiwgMV0=") { nodeId ... on BlogPost { title } }}$$);
+
+    -- Unknown collections are rejected
+    select graphql.resolve($${node(nodeId: "WyJOb3BlIiwgMV0=") { nodeId ... on BlogPost { title } }}$$);
+
+    -- "opaque" identifies the collection by a UUID derived from its type name, or its node_id_alias
+    comment on schema public is e'@graphql({"inflect_names": true, "node_id_format": "opaque"})';
+
+    select graphql.resolve($$
+        {
+          accountCollection { edges { node { nodeId } } }
+          blogPostCollection { edges { node { nodeId } } }
+        }
+    $$);
+    select graphql.resolve($${node(nodeId: "WyJhNTU1NDYyMi1jNjU1LTg3YTctYTQ3MC1jMTE1ZjM3NGQ5MjUiLCAxXQ==") { nodeId ... on BlogPost { title } }}$$);
+    select graphql.resolve($${node(nodeId: "WyJwdWJsaWMiLCAiYmxvZ19wb3N0IiwgMV0=") { nodeId ... on BlogPost { title } }}$$);
+
+    -- Tables sharing a node_id_alias can't be told apart, so they keep the "table" format
+    comment on table account is e'@graphql({"node_id_alias": "Post"})';
+
+    select graphql.resolve($$
+        {
+          accountCollection { edges { node { nodeId } } }
+          blogPostCollection { edges { node { nodeId } } }
+        }
+    $$);
+
+rollback;
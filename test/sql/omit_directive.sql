@@ -0,0 +1,175 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create table account(
+        id int primary key,
+        email text not null,
+        password_hash text,
+        created_at timestamp not null default '2020-01-01',
+        rank int
+    );
+    comment on column account.password_hash is e'@graphql({"omit": true})';
+    comment on column account.created_at is e'@graphql({"omit": ["insert", "update"]})';
+    comment on column account.rank is e'@graphql({"omit": ["filter", "order"]})';
+
+    create table blog(
+        id int primary key,
+        account_id int,
+        constraint blog_account_id_fkey foreign key (account_id) references account(id)
+    );
+    comment on constraint blog_account_id_fkey on blog is e'@graphql({"omit": true})';
+
+    create table audit_log(
+        id int primary key
+    );
+    comment on table audit_log is e'@graphql({"omit": true})';
+
+    create function score(account)
+        returns int
+        stable
+        language sql
+    as $$ select 1; $$;
+    comment on function score(account) is e'@graphql({"omit": true})';
+
+    create function greeting()
+        returns text
+        stable
+        language sql
+    as $$ select 'hello'; $$;
+
+    create function internal_greeting()
+        returns text
+        stable
+        language sql
+    as $$ select 'hello'; $$;
+    comment on function internal_greeting() is e'@graphql({"omit": true})';
+
+    insert into account(id, email, password_hash, rank)
+    values (1, 'a@x.com', 'secret', 5);
+
+    -- omitted tables and functions are not exposed
+    select graphql.resolve($$
+        {
+          __type(name: "Query") {
+            fields {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- omitted columns, computed fields and relationships are not exposed on the type
+    select graphql.resolve($$
+        {
+          __type(name: "Account") {
+            fields {
+              name
+            }
+          }
+        }
+    $$);
+    select graphql.resolve($$
+        {
+          __type(name: "Blog") {
+            fields {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- columns can be omitted from individual inputs
+    select graphql.resolve($$
+        {
+          __type(name: "AccountInsertInput") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+    select graphql.resolve($$
+        {
+          __type(name: "AccountUpdateInput") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+    select graphql.resolve($$
+        {
+          __type(name: "AccountFilter") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+    select graphql.resolve($$
+        {
+          __type(name: "AccountOrderBy") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- omitted columns are rejected as input
+    select graphql.resolve($$
+        mutation {
+          insertIntoAccountCollection(objects: [{id: 2, email: "b@x.com", password_hash: "x"}]) {
+            affectedCount
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          accountCollection {
+            edges {
+              node {
+                password_hash
+              }
+            }
+          }
+        }
+    $$);
+
+    -- the column remains available from SQL
+    select password_hash from account;
+
+    -- entries of the wrong type are ignored
+    comment on column account.email is e'@graphql({"omit": "insert"})';
+    comment on column account.rank is e'@graphql({"omit": ["filter", 1]})';
+
+    select graphql.resolve($$
+        {
+          __type(name: "AccountInsertInput") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+    select graphql.resolve($$
+        {
+          __type(name: "AccountFilter") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+    select graphql.resolve($$
+        {
+          __type(name: "AccountOrderBy") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+rollback;
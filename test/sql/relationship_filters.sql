@@ -0,0 +1,169 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create role rel_filter_user;
+    alter default privileges in schema public grant all on tables to rel_filter_user;
+    grant usage on schema public to rel_filter_user;
+    grant usage on schema graphql to rel_filter_user;
+    grant all on function graphql.resolve to rel_filter_user;
+
+    create table account(
+        id int primary key,
+        email text not null
+    );
+
+    create table blog(
+        id int primary key,
+        owner_id int not null references account(id),
+        name text not null
+    );
+
+    insert into account(id, email)
+    values
+        (1, 'a@x.com'),
+        (2, 'b@x.com'),
+        (3, 'c@x.com');
+
+    insert into blog(id, owner_id, name)
+    values
+        (1, 1, 'x'),
+        (2, 1, 'y'),
+        (3, 2, 'x');
+
+    select graphql.resolve($$
+        {
+          __type(name: "AccountFilter") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "BlogRelationFilter") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- some: at least one related record matches
+
+    select graphql.resolve($$
+        {
+          accountCollection(filter: {blogCollection: {some: {name: {eq: "y"}}}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- every: all related records match, including accounts with no blogs
+
+    select graphql.resolve($$
+        {
+          accountCollection(filter: {blogCollection: {every: {name: {eq: "x"}}}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- none: no related records match
+
+    select graphql.resolve($$
+        {
+          accountCollection(filter: {blogCollection: {none: {name: {eq: "x"}}}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- quantifiers combine with compound filters
+
+    select graphql.resolve($$
+        {
+          accountCollection(filter: {not: {blogCollection: {some: {}}}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- to-one relationships take the related filter directly
+
+    select graphql.resolve($$
+        {
+          blogCollection(filter: {account: {email: {eq: "b@x.com"}}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- nested relationships
+
+    select graphql.resolve($$
+        {
+          blogCollection(filter: {account: {blogCollection: {some: {name: {eq: "y"}}}}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- row level security applies to related records
+    create policy blog_select
+        on public.blog
+        as permissive
+        for select
+        to rel_filter_user
+        using (name <> 'y');
+
+    create policy account_select
+        on public.account
+        as permissive
+        for select
+        to rel_filter_user
+        using (true);
+
+    alter table public.blog enable row level security;
+    alter table public.account enable row level security;
+
+    set role rel_filter_user;
+
+    select graphql.resolve($$
+        {
+          accountCollection(filter: {blogCollection: {some: {name: {eq: "y"}}}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
@@ -0,0 +1,148 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create table account(
+        id int primary key,
+        email text not null
+    );
+
+    create table blog(
+        id int primary key,
+        owner_id int not null references account(id),
+        name text not null
+    );
+
+    insert into account(id, email)
+    values
+        (1, 'c@x.com'),
+        (2, 'a@x.com'),
+        (3, 'b@x.com');
+
+    insert into blog(id, owner_id, name)
+    values
+        (1, 1, 'w'),
+        (2, 3, 'x'),
+        (3, 3, 'y'),
+        (4, 2, 'z');
+
+    select graphql.resolve($$
+        {
+          __type(name: "AccountOrderBy") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "BlogOrderBy") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "BlogAggregateOrderBy") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- order by a column of a to-one related record
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              blogCollection(orderBy: [{account: {email: AscNullsLast}}]) {
+                edges {
+                  cursor
+                  node {
+                    id
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- cursors include the related sort key
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              blogCollection(
+                orderBy: [{account: {email: AscNullsLast}}]
+                first: 2
+                after: "WyJiQHguY29tIiwgMl0="
+              ) {
+                pageInfo {
+                  hasNextPage
+                  hasPreviousPage
+                }
+                edges {
+                  node {
+                    id
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- order by the number of related records
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              accountCollection(orderBy: [{blogCollectionAggregate: {count: DescNullsLast}}]) {
+                edges {
+                  cursor
+                  node {
+                    id
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              accountCollection(
+                orderBy: [{blogCollectionAggregate: {count: DescNullsLast}}]
+                last: 2
+                before: "WzEsIDJd"
+              ) {
+                edges {
+                  node {
+                    id
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- soft deleted related records do not provide a sort key
+    alter table account add column deleted_at timestamptz;
+    comment on table account is e'@graphql({"soft_delete": "deleted_at"})';
+    update account set deleted_at = now() where id = 2;
+
+    select graphql.resolve($$
+        {
+          blogCollection(orderBy: [{account: {email: AscNullsLast}}]) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
@@ -254,6 +254,58 @@ begin;
     select graphql.resolve($$query AAA($nin: [String!]) { accountCollection(filter: {name: {in: $nin}}) { edges { node { id } } }}$$, '{}');
     rollback to savepoint a;
 
+    -- nin - int
+    select graphql.resolve($${accountCollection(filter: {id: {nin: [1, 2]}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- nin - text coerce to list
+    select graphql.resolve($${accountCollection(filter: {name: {nin: "foo"}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- between - int
+    select graphql.resolve($${accountCollection(filter: {id: {between: [2, 3]}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- between - text
+    select graphql.resolve($${accountCollection(filter: {name: {between: ["bar", "baz"]}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- between - requires exactly two values
+    select graphql.resolve($${accountCollection(filter: {id: {between: [1]}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- distinctFrom - null safe inequality
+    select graphql.resolve($${accountCollection(filter: {phone: {distinctFrom: "1111111111"}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- distinctFrom - null literal
+    select graphql.resolve($${accountCollection(filter: {phone: {distinctFrom: null}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- notDistinctFrom - null literal
+    select graphql.resolve($${accountCollection(filter: {phone: {notDistinctFrom: null}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- ieq
+    select graphql.resolve($${accountCollection(filter: {name: {ieq: "FOO"}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- istartsWith
+    select graphql.resolve($${accountCollection(filter: {name: {istartsWith: "BA"}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- endsWith
+    select graphql.resolve($${accountCollection(filter: {name: {endsWith: "ui"}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- notLike
+    select graphql.resolve($${accountCollection(filter: {name: {notLike: "ba%"}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
+    -- notIlike
+    select graphql.resolve($${accountCollection(filter: {name: {notIlike: "BA%"}}) { edges { node { id } } }}$$);
+    rollback to savepoint a;
+
     -- Variable: In, mixed List Int
     select jsonb_pretty(
         graphql.resolve($$
@@ -0,0 +1,67 @@
+begin;
+    create table account(
+        id int primary key,
+        name text
+    );
+
+    create table blog(
+        id int primary key,
+        name text
+    );
+
+    create table order_item(
+        order_id int,
+        line int,
+        sku text,
+        primary key (order_id, line)
+    );
+
+    insert into account(id, name) values (1, 'foo'), (2, 'bar'), (3, 'baz');
+    insert into blog(id, name) values (1, 'Blog 1'), (2, 'Blog 2');
+    insert into order_item(order_id, line, sku) values (1, 1, 'A'), (1, 2, 'B');
+
+    -- Records are returned in the order requested, across collections
+    -- Missing records and ids that can not be decoded resolve to null
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              nodes(ids: ["WyJwdWJsaWMiLCAiYmxvZyIsIDJd", "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDNd", "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDk5XQ==", "not an id", "WyJwdWJsaWMiLCAib3JkZXJfaXRlbSIsIDEsIDJd", "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDFd"]) {
+                __typename
+                nodeId
+                ... on Account {
+                  name
+                }
+                ... on Blog {
+                  name
+                }
+                ... on OrderItem {
+                  sku
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Repeated ids each produce a result
+    select graphql.resolve($${nodes(ids: ["WyJwdWJsaWMiLCAiYWNjb3VudCIsIDFd", "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDFd"]) { ... on Account { id } }}$$);
+
+    -- Ids may be passed as a variable
+    select graphql.resolve(
+        $$query Nodes($ids: [ID!]!) { nodes(ids: $ids) { ... on Blog { name } } }$$,
+        variables:= '{"ids": ["WyJwdWJsaWMiLCAiYmxvZyIsIDFd"]}'
+    );
+    select graphql.resolve($${nodes(ids: []) { nodeId }}$$);
+
+    -- Ids holding values of the wrong type for the primary key resolve to null
+    select graphql.resolve($${nodes(ids: ["WyJwdWJsaWMiLCAiYWNjb3VudCIsICJhYmMiXQ==", "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDFd", "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDk5OTk5OTk5OTk5XQ==", "WyJwdWJsaWMiLCAib3JkZXJfaXRlbSIsIDEsICJ4Il0="]) { ... on Account { id } }}$$);
+
+    -- Ids of collections the role can not select resolve to null
+    create role api;
+    grant usage on schema graphql to api;
+    grant all on all tables in schema graphql to api;
+    grant usage on schema public to api;
+    grant select on public.account to api;
+    set local role api;
+    select graphql.resolve($${nodes(ids: ["WyJwdWJsaWMiLCAiYWNjb3VudCIsIDFd", "WyJwdWJsaWMiLCAiYmxvZyIsIDFd"]) { nodeId }}$$);
+
+rollback;
@@ -0,0 +1,164 @@
+begin;
+    comment on schema public is e'@graphql({"introspection": true})';
+
+    create table post(
+        id int primary key,
+        title text not null,
+        body text not null,
+        fts tsvector generated always as (to_tsvector('english', title)) stored
+    );
+    comment on column post.body is e'@graphql({"search_config": "english"})';
+
+    insert into post(id, title, body)
+    values
+        (1, 'The quick brown fox', 'jumps over the lazy dog'),
+        (2, 'Lazy dogs sleep', 'foxes and dogs'),
+        (3, 'Cats', 'cats are not dogs');
+
+    select graphql.resolve($$
+        {
+          __type(name: "SearchFilter") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "PostFilter") {
+            inputFields {
+              name
+              type {
+                name
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "StringSearchFilter") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "PostOrderBy") {
+            inputFields {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- tsvector columns
+
+    select graphql.resolve($$
+        {
+          postCollection(filter: {fts: {matches: "fox"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- text columns with a search_config use web search syntax
+
+    select graphql.resolve($$
+        {
+          postCollection(filter: {body: {matches: "dogs -cats"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          postCollection(filter: {body: {matches: "\"lazy dog\""}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- string operators remain available on searchable text columns
+
+    select graphql.resolve($$
+        {
+          postCollection(filter: {body: {matches: "dogs", startsWith: "cats"}}) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- rank by relevance
+
+    select graphql.resolve($$
+        {
+          postCollection(
+            filter: {body: {matches: "dog or fox"}}
+            orderBy: [{bodyRank: {query: "dog or fox", direction: DescNullsLast}}]
+          ) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- cursors include the rank
+
+    select graphql.resolve($$
+        {
+          postCollection(
+            filter: {body: {matches: "dog or fox"}}
+            orderBy: [{bodyRank: {query: "dog or fox", direction: DescNullsLast}}]
+            after: "WzAuMDUsIDBd"
+          ) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- query is required
+
+    select graphql.resolve($$
+        {
+          postCollection(orderBy: [{bodyRank: {direction: DescNullsLast}}]) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
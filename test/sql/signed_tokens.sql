@@ -0,0 +1,49 @@
+begin;
+    create table account(
+        id int primary key,
+        email text not null
+    );
+
+    insert into public.account(id, email)
+    values
+        (1, 'aardvark@x.com'),
+        (2, 'bat@x.com');
+
+    set local graphql.signing_secret = 'correct horse battery staple';
+
+    -- Cursors and nodeIds are signed
+    select graphql.resolve($$
+        {
+          accountCollection(first: 1) {
+            edges { cursor node { nodeId } }
+            pageInfo { endCursor }
+          }
+        }
+    $$);
+
+    -- Signed cursors paginate
+    select graphql.resolve($${accountCollection(after: "WzFd.keySFvMR1eybw4xAkSMLSK4Ft3mEkngZVWD+Hi9pODA=") { edges { node { id } } }}$$);
+
+    -- Signed nodeIds resolve
+    select graphql.resolve($${node(nodeId: "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDJd.l3aZ6QGzPjDYg5uMaMXvWCABAmwwy8Yboyjk0PL21eU=") { ... on Account { email } }}$$);
+
+    -- Unsigned cursors are rejected
+    select graphql.resolve($${accountCollection(after: "WzFd") { edges { node { id } } }}$$);
+
+    -- Tampered cursors are rejected
+    select graphql.resolve($${accountCollection(after: "WzJd.keySFvMR1eybw4xAkSMLSK4Ft3mEkngZVWD+Hi9pODA=") { edges { node { id } } }}$$);
+
+    -- Forged nodeIds are rejected
+    select graphql.resolve($${node(nodeId: "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDJd") { ... on Account { email } }}$$);
+    select graphql.resolve($${node(nodeId: "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDJd.g36TipjqsGLU6OxHadNd40rwVUwm6qanVlzxt0rF7hw=") { ... on Account { email } }}$$);
+    select graphql.resolve($${accountCollection(filter: {nodeId: {eq: "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDJd"}}) { edges { node { id } } }}$$);
+
+    -- Tokens are only accepted for the purpose they were signed for
+    select graphql.resolve($${accountCollection(after: "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDFd.g36TipjqsGLU6OxHadNd40rwVUwm6qanVlzxt0rF7hw=") { edges { node { id } } }}$$);
+    select graphql.resolve($${node(nodeId: "WyJwdWJsaWMiLCAiYWNjb3VudCIsIDJd.LJUO9UCMVubTG9hTXaiVRHrMF/vnNGXCQfBsbY8ZCDk=") { ... on Account { email } }}$$);
+
+    -- Signing is disabled when the secret is unset
+    reset graphql.signing_secret;
+    select graphql.resolve($${accountCollection(after: "WzFd") { edges { cursor node { nodeId } } }}$$);
+
+rollback;
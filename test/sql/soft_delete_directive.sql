@@ -0,0 +1,200 @@
+begin;
+    create table blog(
+        id int primary key,
+        title text not null,
+        deleted_at timestamptz
+    );
+    comment on table blog is e'@graphql({"soft_delete": "deleted_at"})';
+
+    create table note(
+        id int primary key
+    );
+
+    insert into blog(id, title, deleted_at)
+    values
+        (1, 'A', null),
+        (2, 'B', null),
+        (3, 'C', now());
+
+    -- soft deleted records are excluded by default
+    select graphql.resolve($$
+        {
+          blogCollection {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- unless explicitly requested
+    select graphql.resolve($$
+        {
+          blogCollection(includeDeleted: true) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- ByPk
+    select graphql.resolve($$
+        {
+          deleted: blogByPk(id: 3) {
+            id
+          }
+          included: blogByPk(id: 3, includeDeleted: true) {
+            id
+          }
+        }
+    $$);
+
+    -- node
+    select graphql.resolve($$
+        {
+          deleted: node(nodeId: "WyJwdWJsaWMiLCAiYmxvZyIsIDNd") {
+            nodeId
+          }
+          included: node(nodeId: "WyJwdWJsaWMiLCAiYmxvZyIsIDNd", includeDeleted: true) {
+            nodeId
+          }
+        }
+    $$);
+
+    -- delete sets the soft delete column instead of removing the record
+    select graphql.resolve($$
+        mutation {
+          deleteFromBlogCollection(
+            filter: {id: {eq: 1}}
+          ) {
+            affectedCount
+            records {
+              id
+              title
+            }
+          }
+        }
+    $$);
+
+    select id from blog where deleted_at is not null order by id;
+
+    -- already soft deleted records are not deleted again
+    select graphql.resolve($$
+        mutation {
+          deleteFromBlogCollection(
+            filter: {id: {in: [1, 3]}}
+          ) {
+            affectedCount
+          }
+        }
+    $$);
+
+    -- tables without the directive do not accept includeDeleted
+    select graphql.resolve($$
+        {
+          noteCollection(includeDeleted: true) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- the directive is ignored unless it names a date or timestamp column
+    create table draft(
+        id int primary key,
+        deleted boolean
+    );
+    comment on table draft is e'@graphql({"soft_delete": "deleted"})';
+
+    select graphql.resolve($$
+        {
+          draftCollection(includeDeleted: true) {
+            edges {
+              node {
+                id
+              }
+            }
+          }
+        }
+    $$);
+
+    -- to-one relationships do not return soft deleted records, so they are nullable
+    create table reply(
+        id int primary key,
+        blog_id int not null references blog(id)
+    );
+
+    insert into reply(id, blog_id) values (1, 2), (2, 3);
+
+    select graphql.resolve($$
+        {
+          replyCollection {
+            edges {
+              node {
+                id
+                blog {
+                  id
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "Reply") {
+            fields {
+              name
+              type {
+                kind
+              }
+            }
+          }
+        }
+    $$);
+
+    -- hierarchies do not walk through soft deleted records
+    create table folder(
+        id int primary key,
+        parent_id int references folder(id),
+        deleted_at timestamptz
+    );
+    comment on table folder is e'@graphql({"soft_delete": "deleted_at"})';
+
+    insert into folder(id, parent_id, deleted_at)
+    values
+        (1, null, null),
+        (2, 1, now()),
+        (3, 2, null);
+
+    select graphql.resolve($$
+        {
+          folderByPk(id: 3) {
+            ancestors {
+              edges {
+                node {
+                  id
+                }
+              }
+            }
+            included: ancestors(includeDeleted: true, orderBy: [{id: AscNullsLast}]) {
+              edges {
+                node {
+                  id
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+rollback;
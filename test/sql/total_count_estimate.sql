@@ -0,0 +1,69 @@
+begin;
+    comment on schema public is e'@graphql({"inflect_names": true})';
+
+    create table account(
+        id int primary key,
+        email text not null
+    );
+
+    create table blog_post(
+        id int primary key,
+        account_id int not null references account(id),
+        status text not null
+    );
+
+    insert into account(id, email)
+    select x, 'a' || x || '@x.com' from generate_series(1, 4) x;
+
+    insert into blog_post(id, account_id, status)
+    select x, (x % 4) + 1, case when x % 2 = 0 then 'published' else 'draft' end
+    from generate_series(1, 20) x;
+
+    -- Estimates come from planner statistics
+    analyze account;
+    analyze blog_post;
+
+    -- Should fail. totalCountEstimate not enabled
+    select graphql.resolve($${accountCollection { totalCountEstimate }}$$);
+
+    comment on table account is e'@graphql({"totalCount": {"enabled": true}, "totalCountEstimate": {"enabled": true}})';
+    comment on table blog_post is e'@graphql({"totalCountEstimate": {"enabled": true}})';
+
+    select graphql.resolve($${accountCollection { totalCount totalCountEstimate }}$$);
+
+    -- The planner never estimates fewer than one row
+    select graphql.resolve($${accountCollection(filter: {email: {eq: "nobody@x.com"}}) { totalCount totalCountEstimate }}$$);
+
+    -- Nested collections are estimated for each parent record
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              accountCollection(first: 2) {
+                edges {
+                  node {
+                    id
+                    blogPostCollection {
+                      totalCountEstimate
+                    }
+                  }
+                }
+              }
+            }
+        $$)
+    );
+
+    -- distinctOn estimates the number of distinct values
+    select graphql.resolve($${blogPostCollection(distinctOn: [status]) { totalCountEstimate }}$$);
+
+    -- Unfiltered collections read the table's row estimate, which is 0 until the table is analyzed
+    create table tag(
+        id int primary key
+    );
+    comment on table tag is e'@graphql({"totalCount": {"enabled": true}, "totalCountEstimate": {"enabled": true}})';
+
+    insert into tag(id)
+    select x from generate_series(1, 3) x;
+
+    select graphql.resolve($${tagCollection { totalCount totalCountEstimate }}$$);
+
+rollback;
@@ -0,0 +1,259 @@
+begin;
+    comment on schema public is '@graphql({"inflect_names": true, "introspection": true})';
+
+    create table post(
+        id int primary key,
+        title text not null
+    );
+
+    create table payment_card(
+        id int primary key,
+        holder text not null,
+        last_four text not null
+    );
+    comment on table payment_card is e'@graphql({"interfaces": ["PaymentMethod"]})';
+
+    create table payment_bank(
+        id int primary key,
+        holder text not null,
+        iban text not null
+    );
+    comment on table payment_bank is e'@graphql({"interfaces": ["PaymentMethod"]})';
+
+    insert into post(id, title) values (1, 'Alpha'), (2, 'Beta');
+    insert into payment_card(id, holder, last_four) values (1, 'Alice', '4242');
+    insert into payment_bank(id, holder, iban) values (1, 'Alan', 'DE89');
+
+    -- Interfaces expose the fields shared by every implementing table
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "PaymentMethod") {
+                kind
+                fields {
+                  name
+                }
+                possibleTypes {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "PaymentCard") {
+                interfaces {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Fragments on an interface apply to the tables implementing it
+    select graphql.resolve($$
+        {
+          paymentCardCollection {
+            edges {
+              node {
+                ... on PaymentMethod {
+                  holder
+                }
+              }
+            }
+          }
+        }
+    $$);
+
+    -- Functions returning a type name and primary key resolve to a union
+    create function search(term text)
+        returns table(type text, id int)
+        stable
+        language sql
+    as $$
+        select 'post', id from post where title ilike '%' || term || '%'
+        union all
+        select 'payment_card', id from payment_card where holder ilike '%' || term || '%'
+        union all
+        select 'payment_bank', id from payment_bank where holder ilike '%' || term || '%'
+        order by 1, 2
+    $$;
+    comment on function search is e'@graphql({"union": {"name": "SearchResult", "types": ["post", "payment_card"]}})';
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "SearchResult") {
+                kind
+                possibleTypes {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    -- Rows of tables outside the union are omitted
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              search(term: "al") {
+                __typename
+                ...PostFields
+                ... on PaymentMethod {
+                  holder
+                }
+                ... on PaymentCard {
+                  lastFour
+                }
+              }
+            }
+            fragment PostFields on Post {
+              title
+            }
+        $$)
+    );
+
+    select graphql.resolve($$
+        {
+          search(term: "nothing") {
+            __typename
+          }
+        }
+    $$);
+
+    -- Functions may return an interface instead
+    create function payment_methods()
+        returns table(type text, id int)
+        stable
+        language sql
+    as $$
+        select 'payment_bank', id from payment_bank
+        union all
+        select 'payment_card', id from payment_card
+        order by 1, 2
+    $$;
+    comment on function payment_methods is e'@graphql({"interface": "PaymentMethod"})';
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              paymentMethods {
+                __typename
+                holder
+              }
+            }
+        $$)
+    );
+
+    -- Soft deleted records are omitted unless requested
+    alter table payment_bank add column deleted_at timestamptz;
+    comment on table payment_bank is e'@graphql({"interfaces": ["PaymentMethod"], "soft_delete": "deleted_at"})';
+    update payment_bank set deleted_at = now();
+
+    select graphql.resolve($$
+        {
+          paymentMethods {
+            __typename
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          paymentMethods(includeDeleted: true) {
+            __typename
+          }
+        }
+    $$);
+
+    -- Names may not collide with other types
+    comment on function search is e'@graphql({"union": {"name": "Post", "types": ["post", "payment_card"]}})';
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              search(term: "al") {
+                __typename
+              }
+            }
+        $$)
+    );
+
+    -- Deprecated fields are shared only when deprecated fields are included
+    comment on column payment_card.holder is e'@graphql({"deprecated": "use name"})';
+
+    select graphql.resolve($$
+        {
+          __type(name: "PaymentMethod") {
+            fields {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "PaymentMethod") {
+            fields(includeDeprecated: true) {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- Entries of the wrong type are ignored
+    comment on table payment_card is e'@graphql({"interfaces": ["PaymentMethod", 1]})';
+    comment on function search is e'@graphql({"union": {"name": "SearchResult", "types": ["post", 1]}})';
+
+    select graphql.resolve($$
+        {
+          __type(name: "PaymentCard") {
+            interfaces {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          __type(name: "SearchResult") {
+            possibleTypes {
+              name
+            }
+          }
+        }
+    $$);
+
+    -- Union names declared by more than one function are ignored
+    create function search_posts()
+        returns table(type text, id int)
+        stable
+        language sql
+    as $$ select 'post', id from post order by id $$;
+    comment on function search_posts is e'@graphql({"union": {"name": "SearchResult", "types": ["post", "payment_bank"]}})';
+
+    select graphql.resolve($$
+        {
+          __type(name: "SearchResult") {
+            possibleTypes {
+              name
+            }
+          }
+        }
+    $$);
+
+    select graphql.resolve($$
+        {
+          searchPosts {
+            __typename
+          }
+        }
+    $$);
+
+rollback;
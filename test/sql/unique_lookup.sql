@@ -0,0 +1,44 @@
+begin;
+    comment on schema public is e'@graphql({"inflect_names": true, "introspection": true})';
+
+    create table account(
+        id int primary key,
+        email text not null unique,
+        handle text not null,
+        is_deleted bool not null default false
+    );
+
+    -- Partial and expression unique indexes do not produce lookup fields
+    create unique index account_handle_active on account(handle) where not is_deleted;
+    create unique index account_email_lower on account(lower(email));
+
+    create table product(
+        id int primary key,
+        vendor_id int not null,
+        sku text not null,
+        unique (vendor_id, sku)
+    );
+
+    insert into account(id, email, handle) values (1, 'aardvark@x.com', 'aardvark');
+    insert into product(id, vendor_id, sku) values (1, 1, 'A-1'), (2, 2, 'A-1');
+
+    select jsonb_pretty(
+        graphql.resolve($$
+            {
+              __type(name: "Query") {
+                fields {
+                  name
+                }
+              }
+            }
+        $$)
+    );
+
+    select graphql.resolve($${accountByEmail(email: "aardvark@x.com") { id handle }}$$);
+    select graphql.resolve($${accountByEmail(email: "nobody@x.com") { id }}$$);
+
+    -- Composite unique constraints take an argument per column
+    select graphql.resolve($${productByVendorIdAndSku(vendorId: 2, sku: "A-1") { id vendorId sku }}$$);
+    select graphql.resolve($${productByVendorIdAndSku(vendorId: 2) { id }}$$);
+
+rollback;
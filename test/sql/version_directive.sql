@@ -0,0 +1,161 @@
+begin;
+    create table account(
+        id int primary key,
+        email text not null
+    );
+    comment on table account is e'@graphql({"version": {"enabled": true}})';
+
+    create table blog(
+        id int primary key,
+        title text not null,
+        revision int not null default 1
+    );
+    comment on table blog is e'@graphql({"version": {"enabled": true, "column": "revision"}})';
+
+    create table note(
+        id int primary key
+    );
+
+    insert into account(id, email) values (1, 'a@x.com');
+    insert into blog(id, title) values (1, 'A'), (2, 'B');
+
+    -- version defaults to the system column xmin
+    select
+        graphql.resolve($$
+            {
+              accountCollection {
+                edges {
+                  node {
+                    version
+                  }
+                }
+              }
+            }
+        $$) #>> '{data, accountCollection, edges, 0, node, version}' = (select xmin::text from account where id = 1);
+
+    -- version read from the column named in the directive
+    select graphql.resolve($$
+        {
+          blogCollection {
+            edges {
+              node {
+                id
+                version
+              }
+            }
+          }
+        }
+    $$);
+
+    -- update succeeds when the version matches
+    select graphql.resolve($$
+        mutation {
+          updateBlogCollection(
+            set: {title: "A2", revision: 2}
+            filter: {id: {eq: 1}}
+            expectedVersion: "1"
+          ) {
+            affectedCount
+            records {
+              id
+              title
+              version
+            }
+          }
+        }
+    $$);
+
+    -- update fails with a conflict when the version is stale
+    select graphql.resolve($$
+        mutation {
+          updateBlogCollection(
+            set: {title: "A3"}
+            filter: {id: {eq: 1}}
+            expectedVersion: "1"
+          ) {
+            affectedCount
+          }
+        }
+    $$);
+
+    -- no conflict when the filter matches nothing
+    select graphql.resolve($$
+        mutation {
+          updateBlogCollection(
+            set: {title: "A3"}
+            filter: {id: {eq: 99}}
+            expectedVersion: "1"
+          ) {
+            affectedCount
+          }
+        }
+    $$);
+
+    -- expectedVersion is optional
+    select graphql.resolve($$
+        mutation {
+          updateBlogCollection(
+            set: {title: "B2"}
+            filter: {id: {eq: 2}}
+          ) {
+            affectedCount
+          }
+        }
+    $$);
+
+    -- delete fails with a conflict when the version is stale
+    select graphql.resolve($$
+        mutation {
+          deleteFromAccountCollection(
+            filter: {id: {eq: 1}}
+            expectedVersion: "0"
+          ) {
+            affectedCount
+          }
+        }
+    $$);
+
+    -- delete succeeds when the version matches
+    select graphql.resolve(
+        $$
+        mutation DeleteAccount($version: String) {
+          deleteFromAccountCollection(
+            filter: {id: {eq: 1}}
+            expectedVersion: $version
+          ) {
+            affectedCount
+          }
+        }
+        $$,
+        jsonb_build_object('version', (select xmin::text from account where id = 1))
+    );
+
+    -- tables without the directive do not accept expectedVersion
+    select graphql.resolve($$
+        mutation {
+          deleteFromNoteCollection(
+            expectedVersion: "1"
+          ) {
+            affectedCount
+          }
+        }
+    $$);
+
+    -- other serialization failures are not reported as conflicts
+    create function fail_serialization()
+        returns int
+        volatile
+        language plpgsql
+    as $$
+    begin
+        raise exception using errcode='40001', message='could not serialize access';
+    end;
+    $$;
+
+    select graphql.resolve($$
+        mutation {
+          fail_serialization
+        }
+    $$);
+
+rollback;